@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::run_with_loader;
+
+use drive_core::inputs::FileCommentUpdateInput;
+
+use crate::presenters::FileCommentPresenter;
+use crate::server_fns::{attempt_to_delete_file_comment, attempt_to_update_file_comment};
+
+#[component]
+pub fn FileCommentThread(
+    file_comment: FileCommentPresenter,
+    file_comments: ReadSignal<Vec<FileCommentPresenter>>,
+    current_user_id: Option<Uuid>,
+    highlighted_id: Signal<Option<Uuid>>,
+    on_reply: Callback<Uuid>,
+    on_update: Callback,
+) -> Element {
+    let mut is_editing = use_signal(|| false);
+    let mut body_value = use_signal(|| file_comment.body.clone());
+    let file_comment_id = file_comment.id;
+    let is_author = current_user_id == Some(file_comment.author.id());
+    let replies = use_memo(move || {
+        file_comments
+            .read()
+            .iter()
+            .filter(|reply| reply.parent_comment_id == Some(file_comment_id))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
+    rsx! {
+        div {
+            class: "border-l-2 pl-3 py-1",
+            class: if highlighted_id() == Some(file_comment_id) { "border-primary" } else { "border-base-300" },
+            onmouseenter: move |_| highlighted_id.set(Some(file_comment_id)),
+            onmouseleave: move |_| highlighted_id.set(None),
+
+            div { class: "flex gap-2 items-center text-sm",
+                div { class: "avatar avatar-placeholder",
+                    div { class: "bg-neutral text-neutral-content w-6 rounded-full",
+                        span { class: "text-xs", {file_comment.author.initials.clone()} }
+                    }
+                }
+                span { class: "font-bold", {file_comment.author.display_name.clone()} }
+                span { class: "text-gray-400",
+                    {file_comment.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                    if file_comment.is_edited {
+                        " (edited)"
+                    }
+                }
+                if file_comment.region.is_some() {
+                    span { class: "badge badge-sm badge-primary", "Region" }
+                }
+            }
+
+            if is_editing() {
+                textarea {
+                    class: "textarea w-full mt-1",
+                    value: body_value,
+                    oninput: move |event| body_value.set(event.value()),
+                }
+                div { class: "flex gap-2 mt-1",
+                    button {
+                        class: "btn btn-xs btn-primary",
+                        onclick: move |_| async move {
+                            let input = FileCommentUpdateInput {
+                                id: file_comment_id,
+                                body: body_value(),
+                            };
+
+                            if run_with_loader("update-file-comment", move || attempt_to_update_file_comment(input.clone()))
+                                .await
+                                .is_ok()
+                            {
+                                is_editing.set(false);
+                                on_update.call(());
+                            }
+                        },
+                        "Save"
+                    }
+                    button {
+                        class: "btn btn-xs",
+                        onclick: move |_| is_editing.set(false),
+                        "Cancel"
+                    }
+                }
+            } else {
+                p { class: "whitespace-pre-wrap my-1", {file_comment.body.clone()} }
+
+                div { class: "flex gap-2 text-xs",
+                    a {
+                        class: "link",
+                        onclick: move |_| on_reply.call(file_comment_id),
+                        "Reply"
+                    }
+                    if is_author {
+                        a {
+                            class: "link",
+                            onclick: move |_| is_editing.set(true),
+                            "Edit"
+                        }
+                        a {
+                            class: "link link-error",
+                            onclick: move |_| async move {
+                                if run_with_loader("delete-file-comment", move || attempt_to_delete_file_comment(file_comment_id))
+                                    .await
+                                    .is_ok()
+                                {
+                                    on_update.call(());
+                                }
+                            },
+                            "Delete"
+                        }
+                    }
+                }
+            }
+
+            for reply in replies() {
+                FileCommentThread {
+                    key: "{reply.id}",
+                    file_comment: reply.clone(),
+                    file_comments,
+                    current_user_id,
+                    highlighted_id,
+                    on_reply,
+                    on_update,
+                }
+            }
+        }
+    }
+}
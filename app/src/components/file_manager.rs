@@ -6,28 +6,33 @@ use sdk::components::{Form, FormSuccessModal, Modal, SelectField, TextField};
 use sdk::hooks::{use_form_provider, use_resource_with_loader};
 use sdk::run_with_loader;
 
-use drive_core::enums::FileVisibility;
-use drive_core::inputs::FileInput;
+use drive_core::enums::{ConflictPolicy, FileVisibility, FolderItemsSortBy, SortDirection};
+use drive_core::inputs::{FileInput, FolderItemsQuery};
 
-use crate::components::FolderItemMenu;
+use crate::components::{
+    AddToAlbumModal, ConflictPolicyModal, FILE_VISIBILITY_OPTIONS, FolderItemCard, FolderItemMenu,
+    can_paste_folder_item, folder_item_ids, paste_conflicting_names, paste_folder_item,
+};
+use crate::constants::FOLDER_ITEMS_PAGE_SIZE;
 use crate::hooks::use_current_user;
 use crate::icons::{
-    ArrowUpTrayOutline, CheckCircleOutline, ExclamationTriangleOutline, FolderOutline, FolderPlusOutline, MoveOutline,
+    ArrowUpTrayOutline, BarsArrowDownOutline, BarsArrowUpOutline, CheckCircleOutline, DocumentDuplicateOutline,
+    ExclamationTriangleOutline, FolderPlusOutline, MoveOutline, RectangleStackOutline, TrashOutline,
 };
-use crate::presenters::FolderPresenter;
+use crate::presenters::{BatchItemResultPresenter, FolderPresenter};
 use crate::routes::Routes;
 use crate::server_fns::{
-    attempt_to_create_folder, attempt_to_move_file, attempt_to_move_folder, attempt_to_upload_file,
-    get_all_folder_items,
+    attempt_to_create_folder, attempt_to_move_items_to_trash, attempt_to_update_items_visibility,
+    attempt_to_upload_file, get_all_folder_items, get_conflicting_item_names,
 };
-use crate::signals::MOVE_FOLDER_ITEM;
-use crate::utils::can_be_moved;
-
-const FILE_VISIBILITY_OPTIONS: [(&str, FileVisibility); 4] = [
-    ("Private", FileVisibility::Private),
-    ("Only followers", FileVisibility::Followers),
-    ("Only users", FileVisibility::Users),
-    ("Public", FileVisibility::Public),
+use crate::signals::{COPY_FOLDER_ITEM, MOVE_FOLDER_ITEM, MOVE_FOLDER_ITEMS};
+
+const SORT_BY_OPTIONS: [(&str, FolderItemsSortBy); 5] = [
+    ("Name", FolderItemsSortBy::Name),
+    ("Size", FolderItemsSortBy::Size),
+    ("Created", FolderItemsSortBy::CreatedAt),
+    ("Modified", FolderItemsSortBy::UpdatedAt),
+    ("Type", FolderItemsSortBy::Type),
 ];
 
 #[component]
@@ -38,9 +43,98 @@ pub fn FileManager(
     let navigator = use_navigator();
     let folder_id = use_memo(move || folder().map(|folder| folder.id));
     let mut show_new_folder_modal = use_signal(|| false);
+    let mut show_add_to_album_modal = use_signal(|| false);
+    let mut show_conflict_modal = use_signal(|| false);
+    let mut conflicting_names = use_signal(Vec::new);
     let mut pending_files = use_signal(Vec::new);
-    let mut all_folder_items = use_resource_with_loader("folder-items", move || get_all_folder_items(folder_id()));
+    let mut folder_items_query = use_signal(|| FolderItemsQuery {
+        limit: Some(FOLDER_ITEMS_PAGE_SIZE),
+        ..Default::default()
+    });
+    let mut folder_items = use_signal(Vec::new);
+    let mut next_cursor = use_signal(|| None);
+    let mut folder_items_page = use_resource_with_loader("folder-items", move || {
+        get_all_folder_items(folder_id(), folder_items_query())
+    });
     let mut current_user = use_current_user();
+    let mut selected_ids = use_signal(Vec::<Uuid>::new);
+    let mut last_selected_index = use_signal(|| None::<usize>);
+    let mut failed_items_count = use_signal(|| 0);
+    let selected_items = use_memo(move || {
+        folder_items
+            .read()
+            .iter()
+            .filter(|folder_item| selected_ids.read().contains(&folder_item.id))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    let mut select_folder_item = move |index: usize, event: MouseEvent| {
+        let Some(id) = folder_items.peek().get(index).map(|folder_item| folder_item.id) else {
+            return;
+        };
+        let last_index = *last_selected_index.peek();
+
+        if event.modifiers().shift()
+            && let Some(last_index) = last_index
+        {
+            let mut selected_ids = selected_ids.write();
+
+            for folder_item in &folder_items.peek()[last_index.min(index)..=last_index.max(index)] {
+                if !selected_ids.contains(&folder_item.id) {
+                    selected_ids.push(folder_item.id);
+                }
+            }
+        } else if selected_ids.peek().contains(&id) {
+            selected_ids.write().retain(|selected_id| *selected_id != id);
+        } else {
+            selected_ids.write().push(id);
+        }
+
+        last_selected_index.set(Some(index));
+    };
+    let mut reload_folder_items = move || {
+        if folder_items_query.peek().cursor.is_some() {
+            folder_items_query.write().cursor = None;
+        } else {
+            folder_items_page.restart();
+        }
+    };
+
+    use_effect(move || {
+        if let Some(Ok(page)) = &*folder_items_page.read() {
+            if folder_items_query.peek().cursor.is_some() {
+                folder_items.write().extend(page.items.clone());
+            } else {
+                *folder_items.write() = page.items.clone();
+            }
+
+            *next_cursor.write() = page.next_cursor;
+        }
+    });
+
+    use_effect(move || {
+        let _ = folder_id();
+
+        selected_ids.write().clear();
+        last_selected_index.set(None);
+
+        if folder_items_query.peek().cursor.is_some() {
+            folder_items_query.write().cursor = None;
+        }
+    });
+
+    let mut finish_batch = move |results: Vec<BatchItemResultPresenter>| {
+        let failed_ids = results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .map(|result| result.id)
+            .collect::<Vec<_>>();
+
+        failed_items_count.set(failed_ids.len());
+        *selected_ids.write() = failed_ids;
+        last_selected_index.set(None);
+        reload_folder_items();
+    };
 
     rsx! {
         div { class: "flex gap-2 justify-between",
@@ -70,6 +164,7 @@ pub fn FileManager(
                                                 parent_folder_id: folder_id(),
                                                 name: file_name.clone(),
                                                 content: file_engine.read_file(&file_name).await.unwrap(),
+                                                conflict_policy: ConflictPolicy::Fail,
                                             });
                                     }
                                 }
@@ -83,20 +178,102 @@ pub fn FileManager(
                 }
             }
 
-            if let Some(folder) = folder() {
-                FolderItemMenu {
-                    folder_item: folder,
-                    on_update: move |_| {
-                        navigator.push(Routes::home());
-                    },
+            div { class: "flex gap-2 items-center",
+                if let Some(stats) = folder().and_then(|folder| folder.stats) {
+                    div { class: "text-sm text-gray-400",
+                        {format!("{} files, {} folders · {}", stats.files_count, stats.folders_count, stats.byte_size)}
+                    }
+                }
+
+                label { class: "label",
+                    input {
+                        checked: folder_items_query.read().folders_first,
+                        class: "checkbox",
+                        r#type: "checkbox",
+                        onchange: move |event| {
+                            let mut query = folder_items_query.write();
+                            query.folders_first = event.checked();
+                            query.cursor = None;
+                        },
+                    }
+                    "Folders first"
+                }
+
+                div { class: "join",
+                    select {
+                        class: "select join-item",
+                        onchange: move |event| {
+                            if let Ok(sort_by) = event.value().parse() {
+                                let mut query = folder_items_query.write();
+                                query.sort_by = sort_by;
+                                query.cursor = None;
+                            }
+                        },
+                        for (label , value) in SORT_BY_OPTIONS {
+                            option {
+                                selected: folder_items_query.read().sort_by == value,
+                                value: value.to_string(),
+                                {label}
+                            }
+                        }
+                    }
+
+                    button {
+                        class: "btn btn-outline join-item",
+                        onclick: move |_| {
+                            let mut query = folder_items_query.write();
+                            query.sort_direction = query.sort_direction.reverse();
+                            query.cursor = None;
+                        },
+                        if folder_items_query.read().sort_direction == SortDirection::Asc {
+                            BarsArrowUpOutline {}
+                        } else {
+                            BarsArrowDownOutline {}
+                        }
+                    }
+                }
+
+                if let Some(folder) = folder() {
+                    FolderItemMenu {
+                        folder_item: folder,
+                        on_update: move |_| {
+                            navigator.push(Routes::home());
+                        },
+                    }
                 }
             }
         }
 
+        AddToAlbumModal {
+            is_open: show_add_to_album_modal,
+            file_ids: selected_items()
+                .iter()
+                .filter(|folder_item| folder_item.is_file)
+                .map(|folder_item| folder_item.id)
+                .collect::<Vec<_>>(),
+            on_success: move |_| {
+                selected_ids.write().clear();
+                last_selected_index.set(None);
+            },
+        }
+
+        ConflictPolicyModal {
+            is_open: show_conflict_modal,
+            names: conflicting_names,
+            can_merge: MOVE_FOLDER_ITEM.read().as_ref().is_some_and(|folder_item| !folder_item.is_file),
+            can_skip: !MOVE_FOLDER_ITEMS.read().is_empty(),
+            on_select: move |policy| async move {
+                if paste_folder_item(folder_id(), policy).await {
+                    current_user.restart();
+                    reload_folder_items();
+                }
+            },
+        }
+
         NewFolderModal {
             is_open: show_new_folder_modal,
             min_visibility,
-            on_close: move |_| all_folder_items.restart(),
+            on_close: move |_| reload_folder_items(),
             parent_folder_id: folder_id,
         }
 
@@ -104,73 +281,197 @@ pub fn FileManager(
             files: pending_files,
             on_close: move |_| {
                 current_user.restart();
-                all_folder_items.restart();
+                reload_folder_items();
             },
         }
 
-        if let Some(Ok(folder_items)) = &*all_folder_items.read() {
-            if !folder_items.is_empty() {
-                div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
-                    for folder_item in folder_items {
-                        div { class: "relative aspect-square",
-                            if folder_item.is_file {
-                                Link {
-                                    class: "btn flex-col gap-2 p-2 h-full w-full",
-                                    to: Routes::file(folder_item.id),
-                                    img {
-                                        class: "rounded-lg m-auto min-h-0",
-                                        src: folder_item.variant_url(200, 200, false).unwrap().to_string(),
-                                    }
-                                    div { class: "normal-case truncate w-full shrink-0",
-                                        {folder_item.name.clone()}
-                                    }
-                                }
+        if !folder_items.read().is_empty() {
+            div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                for (index , folder_item) in folder_items().into_iter().enumerate() {
+                    FolderItemCard {
+                        key: "{folder_item.id}",
+                        is_selected: selected_ids.read().contains(&folder_item.id),
+                        is_selecting: !selected_ids.read().is_empty(),
+                        folder_item: folder_item.clone(),
+                        on_select: move |event| select_folder_item(index, event),
+                        on_update: move |_| reload_folder_items(),
+                    }
+                }
+            }
 
-                                div { class: "absolute top-0.5 right-0.5",
-                                    FolderItemMenu {
-                                        folder_item: folder_item.clone(),
-                                        on_update: move |_| all_folder_items.restart(),
-                                    }
-                                }
-                            } else {
-                                Link {
-                                    class: "btn flex-col gap-2 normal-case p-2 h-full w-full",
-                                    to: Routes::folder(folder_item.id),
-                                    FolderOutline { class: "size-[90%] text-gray-400 hover:text-gray-200" }
-
-                                    div { class: "normal-case truncate w-full shrink-0",
-                                        {folder_item.name.clone()}
-                                    }
-                                }
+            if let Some(cursor) = next_cursor() {
+                div {
+                    class: "h-1",
+                    onvisible: move |event| {
+                        if event.data().is_intersecting().unwrap_or(false) {
+                            folder_items_query.write().cursor = Some(cursor);
+                        }
+                    },
+                }
+            }
+        } else if let Some(Ok(_)) = &*folder_items_page.read() {
+            div { class: "text-center mt-6", "This folder is empty" }
+        }
 
-                                div { class: "absolute top-0.5 right-0.5",
-                                    FolderItemMenu {
-                                        folder_item: folder_item.clone(),
-                                        on_update: move |_| all_folder_items.restart(),
-                                    }
+        if !selected_ids.read().is_empty() {
+            div { class: "fixed bottom-4 flex",
+                div { role: "alert", class: "alert",
+                    {format!("{} selected", selected_ids.read().len())}
+
+                    if failed_items_count() > 0 {
+                        span { class: "text-error",
+                            {format!("{} could not be updated", failed_items_count())}
+                        }
+                    }
+
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        onclick: move |_| {
+                            *selected_ids.write() = folder_items
+                                .read()
+                                .iter()
+                                .map(|folder_item| folder_item.id)
+                                .collect();
+                        },
+                        "Select all"
+                    }
+
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        onclick: move |_| {
+                            selected_ids.write().clear();
+                            last_selected_index.set(None);
+                            failed_items_count.set(0);
+                        },
+                        "Clear"
+                    }
+
+                    select {
+                        class: "select select-sm w-auto",
+                        onchange: move |event| {
+                            let Ok(visibility) = serde_json::from_value::<FileVisibility>(Value::String(event.value())) else {
+                                return;
+                            };
+                            let ids = folder_item_ids(&selected_items());
+
+                            async move {
+                                let result = run_with_loader(
+                                        "update-items-visibility",
+                                        move || attempt_to_update_items_visibility(ids.clone(), visibility),
+                                    )
+                                    .await;
+
+                                if let Ok(results) = result {
+                                    finish_batch(results);
                                 }
                             }
+                        },
+                        option { disabled: true, selected: true, value: "", "Visibility" }
+                        for (label , value) in FILE_VISIBILITY_OPTIONS.iter().skip_while(|(_, value)| *value != min_visibility) {
+                            option { value: value.to_string(), {*label} }
                         }
                     }
+
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        disabled: selected_items.read().iter().any(|folder_item| !folder_item.is_file),
+                        onclick: move |_| {
+                            *show_add_to_album_modal.write() = true;
+                        },
+                        RectangleStackOutline {}
+                        "Add to album"
+                    }
+
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        onclick: move |_| {
+                            *COPY_FOLDER_ITEM.write() = None;
+                            *MOVE_FOLDER_ITEM.write() = None;
+                            *MOVE_FOLDER_ITEMS.write() = selected_items();
+                            selected_ids.write().clear();
+                            last_selected_index.set(None);
+                        },
+                        MoveOutline {}
+                        "Move"
+                    }
+
+                    button {
+                        class: "btn btn-sm btn-error",
+                        onclick: move |_| {
+                            let ids = folder_item_ids(&selected_items());
+
+                            async move {
+                                let result = run_with_loader(
+                                        "move-items-to-trash",
+                                        move || attempt_to_move_items_to_trash(ids.clone()),
+                                    )
+                                    .await;
+
+                                if let Ok(results) = result {
+                                    finish_batch(results);
+                                }
+                            }
+                        },
+                        TrashOutline {}
+                        "Move to trash"
+                    }
                 }
-            } else {
-                div { class: "text-center mt-6", "This folder is empty" }
             }
-        }
-
-        if let Some(move_folder_item) = &*MOVE_FOLDER_ITEM.read() {
+        } else if !MOVE_FOLDER_ITEMS.read().is_empty() {
             div { class: "fixed bottom-4 flex",
                 div { role: "alert", class: "alert",
                     MoveOutline {}
 
-                    "Moving \""
-                    {move_folder_item.name.clone()}
+                    {format!("Moving {} items", MOVE_FOLDER_ITEMS.read().len())}
+
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        onclick: move |event| {
+                            event.prevent_default();
+                            MOVE_FOLDER_ITEMS.write().clear();
+                        },
+                        "Cancel"
+                    }
+
+                    button {
+                        class: "btn btn-sm btn-primary",
+                        disabled: !can_paste_folder_item(folder().as_ref()),
+                        onclick: move |event| {
+                            event.prevent_default();
+                            async move {
+                                let names = paste_conflicting_names(folder_id()).await;
+                                if !names.is_empty() {
+                                    conflicting_names.set(names);
+                                    *show_conflict_modal.write() = true;
+                                } else if paste_folder_item(folder_id(), ConflictPolicy::Fail).await {
+                                    reload_folder_items();
+                                }
+                            }
+                        },
+                        "Paste here"
+                    }
+                }
+            }
+        } else if let Some(clipboard_item) = COPY_FOLDER_ITEM.read().clone().or(MOVE_FOLDER_ITEM.read().clone()) {
+            div { class: "fixed bottom-4 flex",
+                div { role: "alert", class: "alert",
+                    if COPY_FOLDER_ITEM.read().is_some() {
+                        DocumentDuplicateOutline {}
+
+                        "Copying \""
+                    } else {
+                        MoveOutline {}
+
+                        "Moving \""
+                    }
+                    {clipboard_item.name.clone()}
                     "\""
 
                     button {
                         class: "btn btn-sm btn-outline",
                         onclick: move |event| {
                             event.prevent_default();
+                            *COPY_FOLDER_ITEM.write() = None;
                             *MOVE_FOLDER_ITEM.write() = None;
                         },
                         "Cancel"
@@ -178,36 +479,17 @@ pub fn FileManager(
 
                     button {
                         class: "btn btn-sm btn-primary",
-                        disabled: !can_be_moved(move_folder_item, folder().as_ref()),
-                        onclick: {
-                            let move_folder_item_id = move_folder_item.id;
-                            let move_folder_item_is_file = move_folder_item.is_file;
-                            move |event| {
-                                event.prevent_default();
-                                async move {
-                                    let result = if move_folder_item_is_file {
-                                        run_with_loader(
-                                                "move-file",
-                                                move || attempt_to_move_file(
-                                                    move_folder_item_id,
-                                                    folder_id(),
-                                                ),
-                                            )
-                                            .await
-                                    } else {
-                                        run_with_loader(
-                                                "move-folder",
-                                                move || attempt_to_move_folder(
-                                                    move_folder_item_id,
-                                                    folder_id(),
-                                                ),
-                                            )
-                                            .await
-                                    };
-                                    if result.is_ok() {
-                                        *MOVE_FOLDER_ITEM.write() = None;
-                                        all_folder_items.restart();
-                                    }
+                        disabled: !can_paste_folder_item(folder().as_ref()),
+                        onclick: move |event| {
+                            event.prevent_default();
+                            async move {
+                                let names = paste_conflicting_names(folder_id()).await;
+                                if !names.is_empty() {
+                                    conflicting_names.set(names);
+                                    *show_conflict_modal.write() = true;
+                                } else if paste_folder_item(folder_id(), ConflictPolicy::Fail).await {
+                                    current_user.restart();
+                                    reload_folder_items();
                                 }
                             }
                         },
@@ -264,23 +546,56 @@ fn NewFolderModal(
 #[component]
 pub fn UploadFilesModal(files: Signal<Vec<FileInput>>, on_close: Callback) -> Element {
     let mut is_open = use_signal(|| false);
+    let mut show_conflict_modal = use_signal(|| false);
+    let mut conflicting_names = use_signal(Vec::new);
     let mut uploads_result = use_signal(Vec::new);
 
+    let upload_files = move |conflict_policy: ConflictPolicy| {
+        *is_open.write() = true;
+
+        spawn(async move {
+            for file in files() {
+                let input = FileInput {
+                    conflict_policy,
+                    ..file
+                };
+
+                uploads_result.write().push(attempt_to_upload_file(input).await);
+            }
+        });
+    };
+
     use_effect(move || {
-        if files().is_empty() {
+        let files = files();
+
+        if files.is_empty() {
             return;
         }
 
-        *is_open.write() = true;
-
         spawn(async move {
-            for file in files() {
-                uploads_result.write().push(attempt_to_upload_file(file).await);
+            let parent_folder_id = files[0].parent_folder_id;
+            let names = files.iter().map(|file| file.name.clone()).collect();
+            let names = get_conflicting_item_names(parent_folder_id, names, None)
+                .await
+                .unwrap_or_default();
+
+            if names.is_empty() {
+                upload_files(ConflictPolicy::Fail);
+            } else {
+                conflicting_names.set(names);
+                *show_conflict_modal.write() = true;
             }
         });
     });
 
     rsx! {
+        ConflictPolicyModal {
+            is_open: show_conflict_modal,
+            names: conflicting_names,
+            can_skip: true,
+            on_select: upload_files,
+        }
+
         Modal { is_open, is_closable: false,
             h2 { class: "h2", "Uploading Files" }
 
@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::run_with_loader;
+
+use drive_core::inputs::{ItemMetadataInput, ItemProperties};
+
+use crate::icons::{PlusOutline, TrashOutline};
+use crate::server_fns::{attempt_to_update_file_metadata, attempt_to_update_folder_metadata};
+
+#[component]
+pub fn ItemDetails(
+    id: Uuid,
+    is_file: bool,
+    description: Option<String>,
+    properties: ItemProperties,
+    #[props(default = true)] is_editable: bool,
+    on_update: Callback,
+) -> Element {
+    let mut is_editing = use_signal(|| false);
+    let mut description_value = use_signal(String::new);
+    let mut property_rows = use_signal(Vec::<(String, String)>::new);
+    let mut has_error = use_signal(|| false);
+
+    let start_editing = {
+        let description = description.clone();
+        let properties = properties.clone();
+        move |_| {
+            description_value.set(description.clone().unwrap_or_default());
+            property_rows.set(properties.clone().into_iter().collect());
+            has_error.set(false);
+            is_editing.set(true);
+        }
+    };
+
+    rsx! {
+        div { class: "max-w-2xl m-auto my-4",
+            div { class: "flex items-center justify-between mb-2",
+                h2 { class: "h4", "Details" }
+
+                if is_editable && !is_editing() {
+                    button { class: "btn btn-xs", onclick: start_editing, "Edit" }
+                }
+            }
+
+            if is_editing() {
+                textarea {
+                    class: "textarea w-full",
+                    placeholder: "Description",
+                    value: description_value,
+                    oninput: move |event| description_value.set(event.value()),
+                }
+
+                for (index , (key , value)) in property_rows().into_iter().enumerate() {
+                    div { class: "flex gap-2 mt-2",
+                        input {
+                            class: "input input-sm w-1/3",
+                            placeholder: "Name",
+                            value: key,
+                            oninput: move |event| property_rows.write()[index].0 = event.value(),
+                        }
+                        input {
+                            class: "input input-sm grow",
+                            placeholder: "Value",
+                            value,
+                            oninput: move |event| property_rows.write()[index].1 = event.value(),
+                        }
+                        button {
+                            class: "btn btn-sm btn-square",
+                            onclick: move |_| {
+                                property_rows.write().remove(index);
+                            },
+                            TrashOutline {}
+                        }
+                    }
+                }
+
+                if has_error() {
+                    div { class: "text-error text-sm mt-2",
+                        "Properties need a name of up to 64 characters and values of up to 1024 characters."
+                    }
+                }
+
+                div { class: "flex gap-2 mt-2",
+                    button {
+                        class: "btn btn-sm",
+                        onclick: move |_| property_rows.write().push(Default::default()),
+                        PlusOutline {}
+                        "Add property"
+                    }
+                    button {
+                        class: "btn btn-sm btn-primary",
+                        onclick: move |_| async move {
+                            let input = ItemMetadataInput {
+                                id,
+                                description: description_value(),
+                                properties: property_rows()
+                                    .into_iter()
+                                    .filter(|(key, value)| !key.trim().is_empty() || !value.trim().is_empty())
+                                    .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+                                    .collect(),
+                            };
+
+                            let result = if is_file {
+                                run_with_loader("update-file-metadata", move || attempt_to_update_file_metadata(input.clone()))
+                                    .await
+                            } else {
+                                run_with_loader("update-folder-metadata", move || attempt_to_update_folder_metadata(input.clone()))
+                                    .await
+                            };
+
+                            if result.is_ok() {
+                                is_editing.set(false);
+                                on_update.call(());
+                            } else {
+                                has_error.set(true);
+                            }
+                        },
+                        "Save"
+                    }
+                    button { class: "btn btn-sm", onclick: move |_| is_editing.set(false), "Cancel" }
+                }
+            } else {
+                if let Some(description) = &description {
+                    p { class: "whitespace-pre-wrap", {description.clone()} }
+                }
+
+                if !properties.is_empty() {
+                    table { class: "table table-sm mt-2",
+                        tbody {
+                            for (key , value) in properties.clone() {
+                                tr { key: "{key}",
+                                    th { class: "w-1/3", {key.clone()} }
+                                    td { {value} }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if description.is_none() && properties.is_empty() {
+                    p { class: "text-sm text-gray-400", "No description or properties yet." }
+                }
+            }
+        }
+    }
+}
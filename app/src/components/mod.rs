@@ -1,21 +1,147 @@
 use dioxus::prelude::*;
+use uuid::Uuid;
 
 use sdk::components::ConfirmationModal;
 use sdk::hooks::use_resource_with_loader;
 use sdk::run_with_loader;
 
-use crate::components::modals::RenameModal;
+use drive_core::enums::{ConflictPolicy, FileVisibility};
+use drive_core::inputs::FolderItemIds;
+
+use crate::components::modals::{ConflictPolicyModal, RenameModal};
+use crate::constants::THUMBNAIL_SIZE;
+use crate::hooks::use_current_user;
 use crate::icons::*;
-use crate::presenters::{FilePresenter, FolderItemPresenter};
+use crate::presenters::{FilePresenter, FolderItemPresenter, FolderPresenter};
+use crate::routes::Routes;
 use crate::server_fns::*;
-use crate::signals::MOVE_FOLDER_ITEM;
-use crate::utils::can_be_moved;
+use crate::signals::{COPY_FOLDER_ITEM, MOVE_FOLDER_ITEM, MOVE_FOLDER_ITEMS};
+use crate::utils::{can_be_copied, can_be_moved};
 
+mod file_comments;
 mod file_manager;
+mod item_details;
 mod modals;
+mod notifications_menu;
 
+pub use file_comments::FileCommentThread;
 pub use file_manager::FileManager;
-pub use modals::{AboutModal, SubscriptionModal};
+pub use item_details::ItemDetails;
+pub use modals::{
+    AboutModal, AddToAlbumModal, AlbumModal, ConflictPolicyModal, FolderPickerModal, SmartFolderModal,
+    SubscriptionModal,
+};
+pub use notifications_menu::NotificationsMenu;
+
+const FILE_VISIBILITY_OPTIONS: [(&str, FileVisibility); 4] = [
+    ("Private", FileVisibility::Private),
+    ("Only followers", FileVisibility::Followers),
+    ("Only users", FileVisibility::Users),
+    ("Public", FileVisibility::Public),
+];
+
+fn can_paste_folder_item(target_folder: Option<&FolderPresenter>) -> bool {
+    if let Some(copy_folder_item) = &*COPY_FOLDER_ITEM.read() {
+        can_be_copied(copy_folder_item, target_folder)
+    } else if let Some(move_folder_item) = &*MOVE_FOLDER_ITEM.read() {
+        can_be_moved(move_folder_item, target_folder)
+    } else if !MOVE_FOLDER_ITEMS.read().is_empty() {
+        MOVE_FOLDER_ITEMS
+            .read()
+            .iter()
+            .any(|folder_item| can_be_moved(folder_item, target_folder))
+    } else {
+        false
+    }
+}
+
+async fn paste_conflicting_names(target_folder_id: Option<Uuid>) -> Vec<String> {
+    if let Some(folder_item) = MOVE_FOLDER_ITEM.read().clone() {
+        return get_conflicting_item_names(target_folder_id, vec![folder_item.name], Some(folder_item.id))
+            .await
+            .unwrap_or_default();
+    }
+
+    let names = MOVE_FOLDER_ITEMS
+        .read()
+        .iter()
+        .filter(|folder_item| folder_item.parent_folder_id != target_folder_id)
+        .map(|folder_item| folder_item.name.clone())
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    get_conflicting_item_names(target_folder_id, names, None)
+        .await
+        .unwrap_or_default()
+}
+
+async fn paste_folder_item(target_folder_id: Option<Uuid>, policy: ConflictPolicy) -> bool {
+    let move_folder_items = MOVE_FOLDER_ITEMS.read().clone();
+
+    if !move_folder_items.is_empty() {
+        let ids = folder_item_ids(&move_folder_items);
+        let result = run_with_loader("move-items", move || {
+            attempt_to_move_items(ids.clone(), target_folder_id, policy)
+        })
+        .await;
+
+        if result.is_ok() {
+            MOVE_FOLDER_ITEMS.write().clear();
+        }
+
+        return result.is_ok();
+    }
+
+    let copy_folder_item = COPY_FOLDER_ITEM.read().clone();
+    let move_folder_item = MOVE_FOLDER_ITEM.read().clone();
+
+    let result = match (copy_folder_item, move_folder_item) {
+        (Some(folder_item), _) if folder_item.is_file => {
+            run_with_loader("copy-file", move || {
+                attempt_to_copy_file(folder_item.id, target_folder_id)
+            })
+            .await
+        }
+        (Some(folder_item), _) => {
+            run_with_loader("copy-folder", move || {
+                attempt_to_copy_folder(folder_item.id, target_folder_id)
+            })
+            .await
+        }
+        (None, Some(folder_item)) if folder_item.is_file => {
+            run_with_loader("move-file", move || {
+                attempt_to_move_file(folder_item.id, target_folder_id, policy)
+            })
+            .await
+        }
+        (None, Some(folder_item)) => {
+            run_with_loader("move-folder", move || {
+                attempt_to_move_folder(folder_item.id, target_folder_id, policy)
+            })
+            .await
+        }
+        (None, None) => return false,
+    };
+
+    if result.is_ok() {
+        *COPY_FOLDER_ITEM.write() = None;
+        *MOVE_FOLDER_ITEM.write() = None;
+    }
+
+    result.is_ok()
+}
+
+pub fn folder_item_ids(folder_items: &[FolderItemPresenter]) -> FolderItemIds {
+    let (files, folders): (Vec<_>, Vec<_>) = folder_items.iter().partition(|folder_item| folder_item.is_file);
+
+    FolderItemIds {
+        file_ids: files.iter().map(|file| file.id).collect(),
+        folder_ids: folders.iter().map(|folder| folder.id).collect(),
+    }
+}
 
 #[cfg(feature = "web")]
 #[component]
@@ -39,7 +165,7 @@ fn DownloadLink(#[props(into)] file: FilePresenter) -> Element {
         a {
             onclick: move |_| {
                 async move {
-
+                    let _ = attempt_to_record_file_access(file.id).await;
                     let result = run_with_loader("get-file-url", move || get_file_url(file.id))
                         .await;
                     if let Ok(mut file_url) = result {
@@ -74,7 +200,7 @@ fn DownloadLink(#[props(into)] file: FilePresenter) -> Element {
         a {
             onclick: move |_| {
                 async move {
-
+                    let _ = attempt_to_record_file_access(file.id).await;
                     let result = run_with_loader("get-file-url", move || get_file_url(file.id))
                         .await;
                     if let Ok(mut file_url) = result {
@@ -101,9 +227,92 @@ fn DownloadLink(#[props(into)] file: FilePresenter) -> Element {
 }
 
 #[component]
-pub fn FolderItemMenu(#[props(into)] folder_item: FolderItemPresenter, #[props(into)] on_update: Callback) -> Element {
+pub fn FolderItemCard(
+    #[props(into)] folder_item: FolderItemPresenter,
+    #[props(into)] on_update: Callback,
+    #[props(default)] is_selected: bool,
+    #[props(default)] is_selecting: bool,
+    #[props(optional)] on_select: Option<Callback<MouseEvent>>,
+) -> Element {
+    rsx! {
+        div {
+            class: "group relative aspect-square rounded-lg",
+            class: if is_selected { "ring-2 ring-primary" },
+            if folder_item.is_file {
+                Link {
+                    class: "btn flex-col gap-2 p-2 h-full w-full",
+                    to: Routes::file(folder_item.id),
+                    img {
+                        class: "rounded-lg m-auto min-h-0",
+                        src: folder_item.variant_url(THUMBNAIL_SIZE, THUMBNAIL_SIZE, false).unwrap().to_string(),
+                    }
+                    div { class: "normal-case truncate w-full shrink-0", {folder_item.name.clone()} }
+                }
+            } else {
+                Link {
+                    class: "btn flex-col gap-2 normal-case p-2 h-full w-full",
+                    to: Routes::folder(folder_item.id),
+                    FolderOutline { class: "size-[90%] text-gray-400 hover:text-gray-200" }
+
+                    div { class: "normal-case truncate w-full shrink-0", {folder_item.name.clone()} }
+
+                    if let Some(stats) = &folder_item.stats {
+                        div { class: "text-xs font-normal text-gray-400 shrink-0",
+                            {format!("{} items · {}", stats.files_count + stats.folders_count, stats.byte_size)}
+                        }
+                    }
+                }
+            }
+
+            if let Some(on_select) = on_select {
+                if is_selecting {
+                    div {
+                        class: "absolute inset-0 cursor-pointer",
+                        onclick: move |event| on_select.call(event),
+                    }
+                }
+
+                input {
+                    checked: is_selected,
+                    class: "checkbox checkbox-sm absolute top-2 left-2",
+                    class: if !is_selecting { "opacity-0 group-hover:opacity-100" },
+                    r#type: "checkbox",
+                    onclick: move |event| on_select.call(event),
+                }
+            }
+
+            div { class: "absolute top-0.5 right-0.5",
+                FolderItemMenu {
+                    folder_item: folder_item.clone(),
+                    on_update,
+                    on_star_change: on_update,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn FolderItemMenu(
+    #[props(into)] folder_item: FolderItemPresenter,
+    #[props(into)] on_update: Callback,
+    #[props(default, into)] on_star_change: Callback,
+) -> Element {
     let mut show_rename_modal = use_signal(|| false);
     let mut show_trash_confirmation = use_signal(|| false);
+    let mut show_conflict_modal = use_signal(|| false);
+    let mut conflicting_names = use_signal(Vec::new);
+    let mut is_starred = use_signal(|| folder_item.is_starred);
+    let current_user = use_current_user();
+    let is_owner = current_user
+        .read()
+        .clone()
+        .flatten()
+        .is_some_and(|user| user.id() == folder_item.user_id);
+
+    use_effect(use_reactive!(|folder_item| {
+        *is_starred.write() = folder_item.is_starred;
+    }));
 
     rsx! {
         div { class: "dropdown dropdown-end",
@@ -117,86 +326,176 @@ pub fn FolderItemMenu(#[props(into)] folder_item: FolderItemPresenter, #[props(i
                         DownloadLink { file: folder_item.clone() }
                     }
 
-                    div { class: "divider m-1" }
-                }
-
-                li {
-                    a {
-                        onclick: move |_| {
-                            *show_rename_modal.write() = true;
-                        },
-                        PencilOutline {}
-                        "Rename"
-                    }
-                }
-
-                li {
-                    a {
-                        onclick: move |_| {
-                            *MOVE_FOLDER_ITEM.write() = Some(folder_item.clone());
-                        },
-                        MoveOutline {}
-                        "Move"
+                    if is_owner {
+                        div { class: "divider m-1" }
                     }
                 }
 
-                if !folder_item.is_file && let Some(move_folder_item) = &*MOVE_FOLDER_ITEM.read()
-                    && can_be_moved(move_folder_item, Some(&(&folder_item).into()))
-                {
+                if is_owner {
                     li {
                         a {
                             onclick: {
-                                let move_folder_item_id = move_folder_item.id;
-                                let move_folder_item_is_file = move_folder_item.is_file;
-                                let target_folder_id = folder_item.id;
-                                move |_| {
-                                    async move {
-                                        let result = if move_folder_item_is_file {
+                                let folder_item_id = folder_item.id;
+                                let folder_item_is_file = folder_item.is_file;
+                                move |_| async move {
+                                    let result = match (folder_item_is_file, is_starred()) {
+                                        (true, true) => {
                                             run_with_loader(
-                                                    "move-file",
-                                                    move || attempt_to_move_file(
-                                                        move_folder_item_id,
-                                                        Some(target_folder_id),
-                                                    ),
+                                                    "unstar-file",
+                                                    move || attempt_to_unstar_file(folder_item_id),
                                                 )
                                                 .await
-                                        } else {
+                                        }
+                                        (true, false) => {
+                                            run_with_loader("star-file", move || attempt_to_star_file(folder_item_id))
+                                                .await
+                                        }
+                                        (false, true) => {
                                             run_with_loader(
-                                                    "move-folder",
-                                                    move || attempt_to_move_folder(
-                                                        move_folder_item_id,
-                                                        Some(target_folder_id),
-                                                    ),
+                                                    "unstar-folder",
+                                                    move || attempt_to_unstar_folder(folder_item_id),
+                                                )
+                                                .await
+                                        }
+                                        (false, false) => {
+                                            run_with_loader(
+                                                    "star-folder",
+                                                    move || attempt_to_star_folder(folder_item_id),
                                                 )
                                                 .await
-                                        };
-                                        if result.is_ok() {
-                                            *MOVE_FOLDER_ITEM.write() = None;
-                                            on_update.call(());
                                         }
+                                    };
+                                    if result.is_ok() {
+                                        is_starred.toggle();
+                                        on_star_change.call(());
                                     }
                                 }
                             },
-                            ClipboardDocumentListOutline {}
-                            "Paste here"
+                            if is_starred() {
+                                StarSolid {}
+                                "Remove star"
+                            } else {
+                                StarOutline {}
+                                "Add star"
+                            }
                         }
                     }
-                }
 
-                div { class: "divider m-1" }
+                    li {
+                        a {
+                            onclick: move |_| {
+                                *show_rename_modal.write() = true;
+                            },
+                            PencilOutline {}
+                            "Rename"
+                        }
+                    }
 
-                li {
-                    a {
-                        onclick: move |_| {
-                            *show_trash_confirmation.write() = true;
-                        },
-                        TrashOutline {}
-                        "Move to trash"
+                    li {
+                        a {
+                            onclick: move |_| {
+                                *COPY_FOLDER_ITEM.write() = None;
+                                MOVE_FOLDER_ITEMS.write().clear();
+                                *MOVE_FOLDER_ITEM.write() = Some(folder_item.clone());
+                            },
+                            MoveOutline {}
+                            "Move"
+                        }
+                    }
+
+                    li {
+                        a {
+                            onclick: move |_| {
+                                *MOVE_FOLDER_ITEM.write() = None;
+                                MOVE_FOLDER_ITEMS.write().clear();
+                                *COPY_FOLDER_ITEM.write() = Some(folder_item.clone());
+                            },
+                            DocumentDuplicateOutline {}
+                            "Copy to…"
+                        }
+                    }
+
+                    li {
+                        a {
+                            onclick: {
+                                let folder_item_id = folder_item.id;
+                                let folder_item_is_file = folder_item.is_file;
+                                let parent_folder_id = folder_item.parent_folder_id;
+                                move |_| async move {
+                                    let result = if folder_item_is_file {
+                                        run_with_loader(
+                                                "copy-file",
+                                                move || attempt_to_copy_file(folder_item_id, parent_folder_id),
+                                            )
+                                            .await
+                                    } else {
+                                        run_with_loader(
+                                                "copy-folder",
+                                                move || attempt_to_copy_folder(folder_item_id, parent_folder_id),
+                                            )
+                                            .await
+                                    };
+                                    if result.is_ok() {
+                                        on_update.call(());
+                                    }
+                                }
+                            },
+                            DocumentDuplicateOutline {}
+                            "Make a copy"
+                        }
+                    }
+
+                    if !folder_item.is_file && can_paste_folder_item(Some(&(&folder_item).into())) {
+                        li {
+                            a {
+                                onclick: {
+                                    let target_folder_id = folder_item.id;
+                                    move |_| async move {
+                                        let names = paste_conflicting_names(Some(target_folder_id)).await;
+                                        if !names.is_empty() {
+                                            conflicting_names.set(names);
+                                            *show_conflict_modal.write() = true;
+                                        } else if paste_folder_item(Some(target_folder_id), ConflictPolicy::Fail).await {
+                                            on_update.call(());
+                                        }
+                                    }
+                                },
+                                ClipboardDocumentListOutline {}
+                                "Paste here"
+                            }
+                        }
+                    }
+
+                    div { class: "divider m-1" }
+
+                    li {
+                        a {
+                            onclick: move |_| {
+                                *show_trash_confirmation.write() = true;
+                            },
+                            TrashOutline {}
+                            "Move to trash"
+                        }
                     }
                 }
             }
         }
 
+        ConflictPolicyModal {
+            is_open: show_conflict_modal,
+            names: conflicting_names,
+            can_merge: MOVE_FOLDER_ITEM.read().as_ref().is_some_and(|folder_item| !folder_item.is_file),
+            can_skip: !MOVE_FOLDER_ITEMS.read().is_empty(),
+            on_select: {
+                let target_folder_id = folder_item.id;
+                move |policy| async move {
+                    if paste_folder_item(Some(target_folder_id), policy).await {
+                        on_update.call(());
+                    }
+                }
+            },
+        }
+
         RenameModal {
             is_open: show_rename_modal,
             folder_item: folder_item.clone(),
@@ -1,16 +1,80 @@
+use chrono::NaiveDate;
 use dioxus::prelude::*;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
 
-use sdk::components::{Brand, Form, Modal, TextField};
+use sdk::components::{Brand, Form, Modal, SelectField, TextField};
 use sdk::constants::{COPYRIGHT, PRIVACY_URL, TERMS_URL};
 use sdk::hooks::{use_form_provider, use_resource_with_loader};
 use sdk::run_with_loader;
 
+use drive_core::enums::{ConflictPolicy, FileVisibility};
+use drive_core::inputs::{FolderItemsFilter, FolderItemsQuery, SmartFolderInput};
+
+use crate::components::FILE_VISIBILITY_OPTIONS;
 use crate::constants::SOURCE_CODE_URL;
-use crate::presenters::FolderItemPresenter;
+use crate::hooks::use_current_user;
+use crate::icons::{FolderOutline, PlusOutline};
+use crate::presenters::{AlbumPresenter, BillingPresenter, FolderItemPresenter, SmartFolderPresenter};
 use crate::server_fns::{
-    attempt_to_create_plan_checkout, attempt_to_rename_file, attempt_to_rename_folder, get_all_available_plans,
+    attempt_to_add_files_to_album, attempt_to_change_plan, attempt_to_create_album, attempt_to_create_plan_checkout,
+    attempt_to_create_smart_folder, attempt_to_rename_file, attempt_to_rename_folder, attempt_to_update_album,
+    attempt_to_update_smart_folder, get_all_albums, get_all_available_plans, get_all_folder_items,
+    get_promotion_code_discount,
 };
 
+const MEDIA_TYPE_OPTIONS: [(&str, &str); 6] = [
+    ("Any type", ""),
+    ("Images", "image/"),
+    ("JPEG", "image/jpeg"),
+    ("PNG", "image/png"),
+    ("GIF", "image/gif"),
+    ("WebP", "image/webp"),
+];
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct SmartFolderFormInput {
+    name: String,
+    name_pattern: String,
+    media_type: String,
+    created_after: String,
+    created_before: String,
+    min_size_mib: String,
+    max_size_mib: String,
+    visibility: String,
+    metadata_pattern: String,
+    tags: String,
+}
+
+impl From<SmartFolderFormInput> for SmartFolderInput {
+    fn from(input: SmartFolderFormInput) -> Self {
+        let non_blank = |value: String| Some(value.trim().to_owned()).filter(|value| !value.is_empty());
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+        let byte_size = |value: &str| value.parse::<f64>().ok().map(|mib| (mib * 1024.0 * 1024.0) as u64);
+
+        SmartFolderInput {
+            name: input.name,
+            filter: FolderItemsFilter {
+                name_pattern: non_blank(input.name_pattern),
+                media_type: non_blank(input.media_type),
+                created_after: date(&input.created_after),
+                created_before: date(&input.created_before),
+                min_byte_size: byte_size(&input.min_size_mib),
+                max_byte_size: byte_size(&input.max_size_mib),
+                visibility: serde_json::from_value(Value::String(input.visibility)).ok(),
+                metadata_pattern: non_blank(input.metadata_pattern),
+                tags: input
+                    .tags
+                    .split(',')
+                    .filter_map(|tag| non_blank(tag.to_owned()))
+                    .collect(),
+            },
+        }
+    }
+}
+
 #[component]
 pub fn AboutModal(is_open: Signal<bool>) -> Element {
     rsx! {
@@ -54,6 +118,243 @@ pub fn AboutModal(is_open: Signal<bool>) -> Element {
     }
 }
 
+#[component]
+pub fn AddToAlbumModal(is_open: Signal<bool>, file_ids: Vec<Uuid>, on_success: Callback) -> Element {
+    let albums = use_resource_with_loader("albums", get_all_albums);
+    let mut show_new_album = use_signal(|| false);
+
+    rsx! {
+        Modal { is_open,
+            h2 { class: "h2", "Add to album" }
+
+            if let Some(Ok(albums)) = &*albums.read() {
+                ul { class: "menu w-full",
+                    for album in albums.clone() {
+                        li { key: "{album.id}",
+                            button {
+                                onclick: {
+                                    let file_ids = file_ids.clone();
+                                    move |_| {
+                                        let file_ids = file_ids.clone();
+                                        async move {
+                                            if run_with_loader(
+                                                    "add-files-to-album",
+                                                    move || attempt_to_add_files_to_album(album.id, file_ids.clone()),
+                                                )
+                                                .await
+                                                .is_ok()
+                                            {
+                                                on_success.call(());
+                                                *is_open.write() = false;
+                                            }
+                                        }
+                                    }
+                                },
+                                {album.name.clone()}
+                                span { class: "badge badge-sm", "{album.files_count}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            button {
+                class: "btn btn-outline w-full mt-4",
+                onclick: move |_| {
+                    *show_new_album.write() = true;
+                },
+                PlusOutline {}
+                "New album"
+            }
+        }
+
+        AlbumModal {
+            is_open: show_new_album,
+            on_success: move |_| albums.restart(),
+        }
+    }
+}
+
+#[component]
+pub fn AlbumModal(is_open: Signal<bool>, album: Option<AlbumPresenter>, on_success: Callback) -> Element {
+    let album_id = album.as_ref().map(|album| album.id);
+    let mut form_provider = use_form_provider("album", move |input| async move {
+        if let Some(album_id) = album_id {
+            attempt_to_update_album(album_id, input).await
+        } else {
+            attempt_to_create_album(input).await
+        }
+    });
+
+    let mut name_value = use_signal(String::new);
+    let visibility = album
+        .as_ref()
+        .map(|album| album.visibility)
+        .unwrap_or(FileVisibility::Private);
+
+    use_effect({
+        let name = album.as_ref().map(|album| album.name.clone()).unwrap_or_default();
+
+        move || {
+            if *is_open.read() {
+                form_provider.reset();
+                *name_value.write() = name.clone();
+            }
+        }
+    });
+
+    rsx! {
+        Modal { is_open,
+            h2 { class: "h2",
+                if album_id.is_some() {
+                    "Edit album"
+                } else {
+                    "New album"
+                }
+            }
+
+            Form {
+                on_success: move |_| {
+                    on_success.call(());
+                    *is_open.write() = false;
+                },
+                TextField {
+                    id: "name",
+                    label: "Name",
+                    name: "name",
+                    value: name_value,
+                }
+
+                SelectField { id: "visibility", label: "Visibility", name: "visibility",
+                    for (label , value) in FILE_VISIBILITY_OPTIONS {
+                        option {
+                            selected: value == visibility,
+                            value: value.to_string(),
+                            {label}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn ConflictPolicyModal(
+    is_open: Signal<bool>,
+    names: ReadSignal<Vec<String>>,
+    #[props(default = false)] can_merge: bool,
+    #[props(default = false)] can_skip: bool,
+    #[props(into)] on_select: Callback<ConflictPolicy>,
+) -> Element {
+    let select = move |policy| {
+        *is_open.write() = false;
+        on_select.call(policy);
+    };
+
+    rsx! {
+        Modal { is_open, is_closable: !can_skip,
+            h2 { class: "h2", "Name conflict" }
+
+            if names().len() == 1 {
+                p {
+                    "An item named \""
+                    {names()[0].clone()}
+                    "\" already exists in this folder."
+                }
+            } else {
+                p { {format!("{} items with the same names already exist in this folder:", names().len())} }
+
+                ul { class: "list-disc list-inside my-2",
+                    for name in names() {
+                        li { key: "{name}", {name.clone()} }
+                    }
+                }
+            }
+
+            div { class: "modal-action",
+                if can_skip {
+                    button { class: "btn", onclick: move |_| select(ConflictPolicy::Fail), "Skip" }
+                } else {
+                    button { class: "btn", onclick: move |_| *is_open.write() = false, "Cancel" }
+                }
+                if can_merge {
+                    button { class: "btn", onclick: move |_| select(ConflictPolicy::Merge), "Merge" }
+                }
+                button { class: "btn", onclick: move |_| select(ConflictPolicy::Replace), "Replace" }
+                button {
+                    class: "btn btn-primary",
+                    onclick: move |_| select(ConflictPolicy::AutoRename),
+                    "Keep both"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn FolderPickerModal(
+    is_open: Signal<bool>,
+    #[props(into)] title: String,
+    #[props(into)] on_select: Callback<Option<Uuid>>,
+) -> Element {
+    let mut current_folders = use_signal(Vec::<(Uuid, String)>::new);
+    let current_folder_id = use_memo(move || current_folders.read().last().map(|(id, _)| *id));
+    let folder_items = use_resource_with_loader("folder-picker-items", move || async move {
+        get_all_folder_items(current_folder_id(), FolderItemsQuery::default()).await
+    });
+
+    use_effect(move || {
+        if *is_open.read() {
+            current_folders.write().clear();
+        }
+    });
+
+    rsx! {
+        Modal { is_open,
+            h2 { class: "h2", {title} }
+
+            div { class: "breadcrumbs text-sm",
+                ul {
+                    li {
+                        a { onclick: move |_| current_folders.write().clear(), "Home" }
+                    }
+                    for (index , (id , name)) in current_folders().into_iter().enumerate() {
+                        li { key: "{id}",
+                            a { onclick: move |_| current_folders.write().truncate(index + 1), {name} }
+                        }
+                    }
+                }
+            }
+
+            if let Some(Ok(page)) = &*folder_items.read() {
+                ul { class: "menu w-full",
+                    for folder in page.items.iter().filter(|folder_item| !folder_item.is_file).cloned() {
+                        li { key: "{folder.id}",
+                            a {
+                                onclick: move |_| current_folders.write().push((folder.id, folder.name.clone())),
+                                FolderOutline {}
+                                {folder.name.clone()}
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "modal-action",
+                button {
+                    class: "btn btn-primary",
+                    onclick: move |_| {
+                        on_select.call(current_folder_id());
+                        *is_open.write() = false;
+                    },
+                    "Select this folder"
+                }
+            }
+        }
+    }
+}
+
 #[component]
 pub fn RenameModal(
     is_open: Signal<bool>,
@@ -112,14 +413,208 @@ pub fn RenameModal(
 }
 
 #[component]
-pub fn SubscriptionModal(is_open: Signal<bool>, on_success: Callback<()>) -> Element {
+pub fn SmartFolderModal(
+    is_open: Signal<bool>,
+    smart_folder: Option<SmartFolderPresenter>,
+    on_success: Callback,
+) -> Element {
+    let smart_folder_id = smart_folder.as_ref().map(|smart_folder| smart_folder.id);
+    let mut form_provider = use_form_provider("smart-folder", move |input: SmartFolderFormInput| async move {
+        if let Some(smart_folder_id) = smart_folder_id {
+            attempt_to_update_smart_folder(smart_folder_id, input.into()).await
+        } else {
+            attempt_to_create_smart_folder(input.into()).await
+        }
+    });
+
+    let mut name_value = use_signal(String::new);
+    let mut name_pattern_value = use_signal(String::new);
+    let mut metadata_pattern_value = use_signal(String::new);
+    let mut tags_value = use_signal(String::new);
+    let name = smart_folder
+        .as_ref()
+        .map(|smart_folder| smart_folder.name.clone())
+        .unwrap_or_default();
+    let filter = smart_folder
+        .as_ref()
+        .map(|smart_folder| smart_folder.filter.clone())
+        .unwrap_or_default();
+    let date_value = |date: Option<NaiveDate>| date.map(|date| date.to_string()).unwrap_or_default();
+    let size_value = |byte_size: Option<u64>| {
+        byte_size
+            .map(|byte_size| (byte_size as f64 / (1024.0 * 1024.0)).to_string())
+            .unwrap_or_default()
+    };
+
+    use_effect({
+        let filter = filter.clone();
+
+        move || {
+            if *is_open.read() {
+                form_provider.reset();
+                *name_value.write() = name.clone();
+                *name_pattern_value.write() = filter.name_pattern.clone().unwrap_or_default();
+                *metadata_pattern_value.write() = filter.metadata_pattern.clone().unwrap_or_default();
+                *tags_value.write() = filter.tags.join(", ");
+            }
+        }
+    });
+
+    rsx! {
+        Modal { is_open,
+            h2 { class: "h2",
+                if smart_folder_id.is_some() {
+                    "Edit smart folder"
+                } else {
+                    "New smart folder"
+                }
+            }
+
+            Form {
+                on_success: move |_| {
+                    on_success.call(());
+                    *is_open.write() = false;
+                },
+                TextField {
+                    id: "name",
+                    label: "Name",
+                    name: "name",
+                    value: name_value,
+                }
+
+                TextField {
+                    id: "name_pattern",
+                    label: "Name contains (use * and ? as wildcards)",
+                    name: "name_pattern",
+                    value: name_pattern_value,
+                }
+
+                TextField {
+                    id: "metadata_pattern",
+                    label: "Description or property contains",
+                    name: "metadata_pattern",
+                    value: metadata_pattern_value,
+                }
+
+                TextField {
+                    id: "tags",
+                    label: "Tags (property names, separated by commas)",
+                    name: "tags",
+                    value: tags_value,
+                }
+
+                SelectField { id: "media_type", label: "Type", name: "media_type",
+                    for (label , value) in MEDIA_TYPE_OPTIONS {
+                        option {
+                            selected: filter.media_type.as_deref().unwrap_or_default() == value,
+                            value,
+                            {label}
+                        }
+                    }
+                }
+
+                fieldset { class: "fieldset",
+                    legend { class: "fieldset-legend", "Created between" }
+
+                    div { class: "join",
+                        input {
+                            class: "input join-item",
+                            name: "created_after",
+                            r#type: "date",
+                            value: date_value(filter.created_after),
+                        }
+                        input {
+                            class: "input join-item",
+                            name: "created_before",
+                            r#type: "date",
+                            value: date_value(filter.created_before),
+                        }
+                    }
+                }
+
+                fieldset { class: "fieldset",
+                    legend { class: "fieldset-legend", "Size in MiB" }
+
+                    div { class: "join",
+                        input {
+                            class: "input join-item",
+                            min: "0",
+                            name: "min_size_mib",
+                            placeholder: "Min",
+                            r#type: "number",
+                            value: size_value(filter.min_byte_size),
+                        }
+                        input {
+                            class: "input join-item",
+                            min: "0",
+                            name: "max_size_mib",
+                            placeholder: "Max",
+                            r#type: "number",
+                            value: size_value(filter.max_byte_size),
+                        }
+                    }
+                }
+
+                SelectField { id: "visibility", label: "Visibility", name: "visibility",
+                    option { selected: filter.visibility.is_none(), value: "", "Any visibility" }
+                    for (label , value) in FILE_VISIBILITY_OPTIONS {
+                        option {
+                            selected: filter.visibility == Some(value),
+                            value: value.to_string(),
+                            {label}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn SubscriptionModal(
+    mut is_open: Signal<bool>,
+    billing: Option<BillingPresenter>,
+    on_success: Callback<()>,
+) -> Element {
+    let current_user = use_current_user();
     let plans = use_resource_with_loader("available-plans", get_all_available_plans);
     let mut selected_plan_id = use_signal(|| None);
-    let mut is_yearly = use_signal(|| false);
+    let mut is_yearly = use_signal(|| {
+        billing
+            .as_ref()
+            .and_then(|billing| billing.is_yearly)
+            .unwrap_or_default()
+    });
+    let mut promotion_code_value = use_signal(String::new);
+    let mut promotion_code_discount = use_signal(|| None::<String>);
+    let mut is_promotion_code_invalid = use_signal(|| false);
+    let current_plan = billing
+        .as_ref()
+        .filter(|billing| !billing.is_canceled)
+        .map(|billing| (billing.plan.id, billing.is_yearly));
+    let is_switching = current_plan.is_some();
+    let used_space_bytes = current_user
+        .read()
+        .as_ref()
+        .and_then(|user| user.as_ref())
+        .map(|user| user.used_space_bytes)
+        .unwrap_or_default();
 
     rsx! {
         Modal { class: "max-w-300", is_open,
-            h2 { class: "h2", "Select plan" }
+            h2 { class: "h2",
+                if is_switching {
+                    "Change plan"
+                } else {
+                    "Select plan"
+                }
+            }
+
+            if is_switching {
+                p { class: "mb-4 text-center",
+                    "The price difference is prorated over the rest of the current billing period."
+                }
+            }
 
             div { class: "mb-4 font-bold flex justify-center gap-2",
                 a {
@@ -146,6 +641,50 @@ pub fn SubscriptionModal(is_open: Signal<bool>, on_success: Callback<()>) -> Ele
                 }
             }
 
+            if !is_switching {
+                div { class: "mb-4 flex flex-col items-center gap-1",
+                    div { class: "join",
+                        input {
+                            class: "input input-sm join-item",
+                            placeholder: "Promotion code",
+                            value: promotion_code_value,
+                            oninput: move |event| {
+                                promotion_code_value.set(event.value());
+                                promotion_code_discount.set(None);
+                                is_promotion_code_invalid.set(false);
+                            },
+                        }
+                        button {
+                            class: "btn btn-sm join-item",
+                            disabled: promotion_code_value.read().trim().is_empty(),
+                            onclick: move |event| {
+                                event.prevent_default();
+
+                                async move {
+                                    let result = run_with_loader(
+                                            "promotion-code-discount",
+                                            move || get_promotion_code_discount(promotion_code_value()),
+                                        )
+                                        .await;
+
+                                    let discount = result.ok().flatten();
+
+                                    is_promotion_code_invalid.set(discount.is_none());
+                                    promotion_code_discount.set(discount);
+                                }
+                            },
+                            "Apply"
+                        }
+                    }
+
+                    if let Some(discount) = promotion_code_discount() {
+                        span { class: "text-sm text-success", "{discount} on the first payment" }
+                    } else if is_promotion_code_invalid() {
+                        span { class: "text-sm text-error", "This code is invalid or expired" }
+                    }
+                }
+            }
+
             div { class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-2",
                 if let Some(Ok(plans)) = &*plans.read() {
                     for plan in plans {
@@ -172,9 +711,20 @@ pub fn SubscriptionModal(is_open: Signal<bool>, on_success: Callback<()>) -> Ele
                                     " of space"
                                 }
 
+                                p {
+                                    "Files up to "
+                                    span { class: "font-bold", {plan.max_file_size.clone()} }
+                                }
+
+                                if !is_switching && plan.trial_days > 0 {
+                                    p { class: "text-success", "{plan.trial_days} days free for first-time subscribers" }
+                                }
+
                                 button {
                                     class: "btn btn-block",
-                                    disabled: selected_plan_id().is_some() && selected_plan_id() != Some(plan.id),
+                                    disabled: (selected_plan_id().is_some() && selected_plan_id() != Some(plan.id))
+                                        || current_plan == Some((plan.id, Some(is_yearly())))
+                                        || (is_switching && plan.quota_bytes < used_space_bytes),
                                     onclick: {
                                         let plan_id = plan.id;
                                         move |event| {
@@ -187,9 +737,31 @@ pub fn SubscriptionModal(is_open: Signal<bool>, on_success: Callback<()>) -> Ele
 
                                                 *selected_plan_id.write() = Some(plan_id);
 
+                                                if is_switching {
+                                                    let result = run_with_loader(
+                                                            "change-plan",
+                                                            move || attempt_to_change_plan(plan_id, is_yearly()),
+                                                        )
+                                                        .await;
+
+                                                    *selected_plan_id.write() = None;
+
+                                                    if result.is_ok() {
+                                                        *is_open.write() = false;
+                                                        on_success.call(());
+                                                    }
+
+                                                    return;
+                                                }
+
+                                                let promotion_code = promotion_code_discount().map(|_| promotion_code_value());
                                                 let result = run_with_loader(
                                                         "create-plan-checkout",
-                                                        move || attempt_to_create_plan_checkout(plan_id, is_yearly()),
+                                                        move || attempt_to_create_plan_checkout(
+                                                            plan_id,
+                                                            is_yearly(),
+                                                            promotion_code.clone(),
+                                                        ),
                                                     )
                                                     .await;
 
@@ -203,6 +775,12 @@ pub fn SubscriptionModal(is_open: Signal<bool>, on_success: Callback<()>) -> Ele
                                     },
                                     if selected_plan_id() == Some(plan.id) {
                                         span { class: "loading loading-spinner" }
+                                    } else if current_plan == Some((plan.id, Some(is_yearly()))) {
+                                        "Current plan"
+                                    } else if is_switching && plan.quota_bytes < used_space_bytes {
+                                        "Not enough space"
+                                    } else if is_switching {
+                                        "Switch"
                                     } else {
                                         "Select"
                                     }
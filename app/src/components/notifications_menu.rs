@@ -0,0 +1,76 @@
+use dioxus::prelude::*;
+
+use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
+
+use crate::icons::BellOutline;
+use crate::routes::Routes;
+use crate::server_fns::{attempt_to_mark_notifications_as_read, get_all_notifications, get_unread_notifications_count};
+
+#[component]
+pub fn NotificationsMenu() -> Element {
+    let mut unread_count = use_resource_with_loader("unread-notifications-count", get_unread_notifications_count);
+    let mut notifications = use_resource_with_loader("notifications", get_all_notifications);
+
+    rsx! {
+        div { class: "dropdown dropdown-end",
+            button {
+                class: "btn btn-ghost btn-circle",
+                tabindex: 0,
+                onfocus: move |_| async move {
+                    notifications.restart();
+
+                    if !matches!(unread_count(), Some(Ok(count)) if count > 0) {
+                        return;
+                    }
+
+                    if run_with_loader("mark-notifications-as-read", attempt_to_mark_notifications_as_read)
+                        .await
+                        .is_ok()
+                    {
+                        unread_count.restart();
+                    }
+                },
+                div { class: "indicator",
+                    BellOutline {}
+
+                    if let Some(Ok(count)) = unread_count() && count > 0 {
+                        span { class: "badge badge-xs badge-primary indicator-item", "{count}" }
+                    }
+                }
+            }
+
+            ul {
+                class: "dropdown-content menu bg-base-200 rounded-box z-1 w-80 p-2 shadow-sm",
+                tabindex: 0,
+                if let Some(Ok(notifications)) = &*notifications.read() {
+                    if notifications.is_empty() {
+                        li { class: "menu-disabled",
+                            span { "No notifications" }
+                        }
+                    }
+
+                    for notification in notifications.clone() {
+                        li { key: "{notification.id}",
+                            if let Some(file_id) = notification.file_id {
+                                Link {
+                                    class: if !notification.is_read { "font-bold" },
+                                    to: Routes::file(file_id),
+                                    {notification.message()}
+                                }
+                            } else if notification.plan_name.is_some() {
+                                Link {
+                                    class: if !notification.is_read { "font-bold" },
+                                    to: Routes::storage(),
+                                    {notification.message()}
+                                }
+                            } else {
+                                span { class: if !notification.is_read { "font-bold" }, {notification.message()} }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::sync::LazyLock;
 pub static SOURCE_CODE_URL: LazyLock<String> =
     LazyLock::new(|| format!("{}/tree/{}", env!("CARGO_PKG_REPOSITORY"), env!("GIT_REV_SHORT")));
 
+pub const FOLDER_ITEMS_PAGE_SIZE: u8 = 60;
 pub const HEADER_AUTHORIZATION: &str = "Authorization";
 pub const KEY_REDIRECT_TO: &str = "_redirect_to";
 pub const KEY_SESSION_TOKEN: &str = "_session_token";
+pub const THUMBNAIL_SIZE: u16 = 200;
@@ -1,7 +1,13 @@
 use dioxus::prelude::*;
 
-use crate::presenters::UserPresenter;
+use sdk::serv_fn::ServFnResult;
+
+use crate::presenters::{SmartFolderPresenter, UserPresenter};
 
 pub fn use_current_user() -> Resource<Option<UserPresenter>> {
     use_context()
 }
+
+pub fn use_smart_folders() -> Resource<ServFnResult<Vec<SmartFolderPresenter>>> {
+    use_context()
+}
@@ -3,17 +3,27 @@ use dioxus::prelude::*;
 use sdk::auth_client::{auth_client_authorize_url, auth_client_provider_url};
 use sdk::components::{Brand, Navbar, NavbarEnd, NavbarStart};
 
-use crate::components::AboutModal;
+use sdk::hooks::use_resource_with_loader;
+
+use crate::components::{AboutModal, NotificationsMenu, SmartFolderModal};
 use crate::hooks::use_current_user;
-use crate::icons::{ChevronDownMini, CloudOutline, HomeOutline, InformationCircleOutline, TrashOutline};
+use crate::icons::{
+    ChevronDownMini, ClockOutline, CloudOutline, FunnelOutline, HomeOutline, InformationCircleOutline, PhotoOutline,
+    PlusOutline, RectangleStackOutline, StarOutline, TrashOutline,
+};
 use crate::local_data::set_redirect_to;
 use crate::routes::Routes;
+use crate::server_fns::get_all_smart_folders;
 
 #[component]
 pub fn UserLayout() -> Element {
     let router = router();
     let mut show_about = use_signal(|| false);
+    let mut show_new_smart_folder = use_signal(|| false);
     let current_user = use_current_user();
+    let mut smart_folders = use_resource_with_loader("smart-folders", get_all_smart_folders);
+
+    use_context_provider(|| smart_folders);
 
     use_effect(move || {
         if let Some(None) = *current_user.read() {
@@ -33,6 +43,8 @@ pub fn UserLayout() -> Element {
                 }
 
                 NavbarEnd {
+                    NotificationsMenu {}
+
                     div { class: "dropdown dropdown-end",
                         button { class: "btn btn-ghost btn-lg px-2", tabindex: 0,
                             div { class: "text-left text-xs",
@@ -72,6 +84,75 @@ pub fn UserLayout() -> Element {
                             }
                         }
 
+                        li {
+                            class: "max-md:tooltip max-md:tooltip-right",
+                            "data-tip": "Recent",
+                            Link { to: Routes::recent(),
+                                ClockOutline {}
+
+                                span { class: "max-md:hidden", "Recent" }
+                            }
+                        }
+
+                        li {
+                            class: "max-md:tooltip max-md:tooltip-right",
+                            "data-tip": "Starred",
+                            Link { to: Routes::starred(),
+                                StarOutline {}
+
+                                span { class: "max-md:hidden", "Starred" }
+                            }
+                        }
+
+                        li {
+                            class: "max-md:tooltip max-md:tooltip-right",
+                            "data-tip": "Timeline",
+                            Link { to: Routes::timeline(),
+                                PhotoOutline {}
+
+                                span { class: "max-md:hidden", "Timeline" }
+                            }
+                        }
+
+                        li {
+                            class: "max-md:tooltip max-md:tooltip-right",
+                            "data-tip": "Albums",
+                            Link { to: Routes::albums(),
+                                RectangleStackOutline {}
+
+                                span { class: "max-md:hidden", "Albums" }
+                            }
+                        }
+
+                        div { class: "divider m-1" }
+
+                        if let Some(Ok(smart_folders)) = &*smart_folders.read() {
+                            for smart_folder in smart_folders.clone() {
+                                li {
+                                    class: "max-md:tooltip max-md:tooltip-right",
+                                    "data-tip": smart_folder.name.clone(),
+                                    Link { to: Routes::smart_folder(smart_folder.id),
+                                        FunnelOutline {}
+
+                                        span { class: "max-md:hidden truncate", {smart_folder.name.clone()} }
+                                    }
+                                }
+                            }
+                        }
+
+                        li {
+                            class: "max-md:tooltip max-md:tooltip-right",
+                            "data-tip": "New smart folder",
+                            a {
+                                onclick: move |_| {
+                                    *show_new_smart_folder.write() = true;
+                                },
+                                PlusOutline {}
+
+                                span { class: "max-md:hidden", "New smart folder" }
+                            }
+                        }
+
                         div { class: "divider m-1" }
 
                         li {
@@ -130,10 +211,31 @@ pub fn UserLayout() -> Element {
                 }
 
                 main { class: "main grow max-w-[calc(100%-48px)] md:max-w-[calc(100%-208px)]",
+                    if user.is_over_quota() {
+                        div { role: "alert", class: "alert alert-warning mx-4 mb-4",
+                            div {
+                                div { class: "font-bold", "Your storage is over quota" }
+                                div { class: "text-sm",
+                                    "Your account is read-only: you can still download, delete and trash files, but uploading, copying and restoring are blocked until you free up space or upgrade your plan."
+                                }
+                                if let Some(notice) = user.over_quota_notice() {
+                                    div { class: "text-sm", {notice} }
+                                }
+                            }
+
+                            Link { class: "btn btn-sm", to: Routes::storage(), "Manage storage" }
+                        }
+                    }
+
                     Outlet::<Routes> {}
                 }
 
                 AboutModal { is_open: show_about }
+
+                SmartFolderModal {
+                    is_open: show_new_smart_folder,
+                    on_success: move |_| smart_folders.restart(),
+                }
             }
         }
     }
@@ -1,8 +1,12 @@
 use dioxus::prelude::*;
 
+#[cfg(feature = "server")]
+use axum::body::Bytes;
 #[cfg(feature = "server")]
 use axum::extract::{Path, Query};
 #[cfg(feature = "server")]
+use axum::http::{HeaderMap, StatusCode};
+#[cfg(feature = "server")]
 use axum::response::IntoResponse;
 #[cfg(feature = "server")]
 use uuid::Uuid;
@@ -44,12 +48,17 @@ struct FileQuery {
 #[cfg(feature = "server")]
 #[tokio::main]
 async fn main() {
-    use axum::routing::get;
+    use axum::routing::{get, post};
 
     dioxus::logger::initialize_default();
 
+    drive_core::server::jobs::spawn_over_quota_job();
+    drive_core::server::jobs::spawn_plan_expiry_job();
+    drive_core::server::jobs::spawn_trash_purge_job();
+
     let app = axum::Router::new()
         .route("/storage/files/{key_id}", get(get_storage_file))
+        .route("/webhooks/polar", post(post_polar_webhook))
         .serve_dioxus_application(ServeConfig::new().unwrap(), App);
 
     let addr = dioxus::cli_config::fullstack_address_or_localhost();
@@ -76,7 +85,6 @@ fn main() {
 #[cfg(feature = "server")]
 async fn get_storage_file(Path(key_id): Path<Uuid>, Query(query): Query<FileQuery>) -> impl IntoResponse {
     use axum::body::Body;
-    use axum::http::StatusCode;
     use axum::http::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE};
 
     let file_key = drive_core::server::commands::get_file_key_by_id(key_id)
@@ -111,6 +119,33 @@ async fn get_storage_file(Path(key_id): Path<Uuid>, Query(query): Query<FileQuer
     Ok((headers, body))
 }
 
+#[cfg(feature = "server")]
+async fn post_polar_webhook(headers: HeaderMap, body: Bytes) -> StatusCode {
+    use drive_core::server::commands::{PolarWebhookHeaders, process_polar_webhook, verify_polar_webhook};
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+    let webhook_headers = PolarWebhookHeaders {
+        id: header("webhook-id"),
+        timestamp: header("webhook-timestamp"),
+        signature: header("webhook-signature"),
+    };
+
+    if verify_polar_webhook(&webhook_headers, &body).is_err() {
+        return StatusCode::FORBIDDEN;
+    }
+
+    // Polar retries failed deliveries later.
+    match process_polar_webhook(webhook_headers.id, &body).await {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 #[component]
 fn App() -> Element {
     let mut is_starting = use_signal(|| true);
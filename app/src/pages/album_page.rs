@@ -0,0 +1,183 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::components::{ConfirmationModal, PageTitle};
+use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
+
+use crate::components::AlbumModal;
+use crate::constants::THUMBNAIL_SIZE;
+use crate::hooks::use_current_user;
+use crate::icons::{PencilOutline, StarOutline, TrashOutline};
+use crate::routes::Routes;
+use crate::server_fns::{
+    attempt_to_delete_album, attempt_to_remove_files_from_album, attempt_to_reorder_album_files,
+    attempt_to_set_album_cover, get_album, get_all_album_files,
+};
+
+#[component]
+pub fn AlbumPage(id: ReadSignal<Uuid>) -> Element {
+    let navigator = use_navigator();
+    let mut album = use_resource_with_loader("album", move || async move { get_album(id()).await.ok().flatten() });
+    let mut album_files = use_resource_with_loader("album-files", move || get_all_album_files(id()));
+    let current_user = use_current_user();
+    let current_user_id = use_memo(move || current_user.read().clone().flatten().map(|user| user.id()));
+    let mut show_edit_modal = use_signal(|| false);
+    let mut show_delete_confirmation = use_signal(|| false);
+    let mut reload = move || {
+        album.restart();
+        album_files.restart();
+    };
+    let mut move_album_file = move |index: usize, offset: isize| {
+        let Some(Ok(files)) = &*album_files.peek() else {
+            return;
+        };
+        let Some(target_index) = index.checked_add_signed(offset).filter(|index| *index < files.len()) else {
+            return;
+        };
+        let mut file_ids = files.iter().map(|file| file.id).collect::<Vec<_>>();
+
+        file_ids.swap(index, target_index);
+
+        spawn(async move {
+            if run_with_loader("reorder-album-files", move || {
+                attempt_to_reorder_album_files(id(), file_ids.clone())
+            })
+            .await
+            .is_ok()
+            {
+                reload();
+            }
+        });
+    };
+
+    rsx! {
+        if let Some(Some(current_album)) = &*album.read() {
+            PageTitle { {current_album.name.clone()} }
+
+            div { class: "flex gap-2 justify-between items-center",
+                div {
+                    h1 { class: "h2", {current_album.name.clone()} }
+                    div { class: "text-sm text-gray-400", "{current_album.files_count} files" }
+                }
+
+                if current_user_id() == Some(current_album.user_id) {
+                    div { class: "flex gap-2",
+                        button {
+                            class: "btn btn-outline",
+                            onclick: move |_| {
+                                *show_edit_modal.write() = true;
+                            },
+                            PencilOutline {}
+                            "Edit"
+                        }
+
+                        button {
+                            class: "btn btn-outline",
+                            onclick: move |_| {
+                                *show_delete_confirmation.write() = true;
+                            },
+                            TrashOutline {}
+                            "Delete"
+                        }
+                    }
+                }
+            }
+
+            if let Some(Ok(files)) = &*album_files.read() {
+                if !files.is_empty() {
+                    div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                        for (index , file) in files.clone().into_iter().enumerate() {
+                            div { key: "{file.id}", class: "flex flex-col gap-1",
+                                Link {
+                                    class: "btn flex-col gap-2 p-2 h-full w-full",
+                                    to: Routes::file(file.id),
+                                    img {
+                                        class: "rounded-lg m-auto min-h-0",
+                                        src: file.variant_url(THUMBNAIL_SIZE, THUMBNAIL_SIZE, false).map(|url| url.to_string()),
+                                    }
+                                    div { class: "normal-case truncate w-full shrink-0",
+                                        if current_album.cover_file_id == Some(file.id) {
+                                            StarOutline {}
+                                        }
+                                        {file.name.clone()}
+                                    }
+                                }
+
+                                if current_user_id() == Some(current_album.user_id) {
+                                    div { class: "join justify-center",
+                                        button {
+                                            class: "btn btn-xs join-item",
+                                            disabled: index == 0,
+                                            onclick: move |_| move_album_file(index, -1),
+                                            "←"
+                                        }
+                                        button {
+                                            class: "btn btn-xs join-item",
+                                            onclick: move |_| async move {
+                                                if run_with_loader(
+                                                        "set-album-cover",
+                                                        move || attempt_to_set_album_cover(id(), Some(file.id)),
+                                                    )
+                                                    .await
+                                                    .is_ok()
+                                                {
+                                                    album.restart();
+                                                }
+                                            },
+                                            "Cover"
+                                        }
+                                        button {
+                                            class: "btn btn-xs join-item",
+                                            onclick: move |_| async move {
+                                                if run_with_loader(
+                                                        "remove-files-from-album",
+                                                        move || attempt_to_remove_files_from_album(id(), vec![file.id]),
+                                                    )
+                                                    .await
+                                                    .is_ok()
+                                                {
+                                                    reload();
+                                                }
+                                            },
+                                            "Remove"
+                                        }
+                                        button {
+                                            class: "btn btn-xs join-item",
+                                            disabled: index + 1 == files.len(),
+                                            onclick: move |_| move_album_file(index, 1),
+                                            "→"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    div { class: "text-center mt-6",
+                        "This album is empty. Select files in a folder and use \"Add to album\"."
+                    }
+                }
+            }
+
+            AlbumModal {
+                is_open: show_edit_modal,
+                album: current_album.clone(),
+                on_success: move |_| album.restart(),
+            }
+
+            ConfirmationModal {
+                is_open: show_delete_confirmation,
+                on_accept: move |_| async move {
+                    if run_with_loader("delete-album", move || attempt_to_delete_album(id()))
+                        .await
+                        .is_ok()
+                    {
+                        navigator.push(Routes::albums());
+                    }
+                },
+                "Are you sure you want to delete this album? Its files are not affected."
+            }
+        }
+    }
+}
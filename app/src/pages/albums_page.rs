@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+
+use crate::components::AlbumModal;
+use crate::constants::THUMBNAIL_SIZE;
+use crate::icons::{PlusOutline, RectangleStackOutline};
+use crate::routes::Routes;
+use crate::server_fns::get_all_albums;
+
+#[component]
+pub fn AlbumsPage() -> Element {
+    let mut albums = use_resource_with_loader("albums", get_all_albums);
+    let mut show_new_album = use_signal(|| false);
+
+    rsx! {
+        PageTitle { "Albums" }
+
+        div { class: "flex gap-2 justify-between items-center",
+            h1 { class: "h2", "Albums" }
+
+            button {
+                class: "btn btn-outline",
+                onclick: move |_| {
+                    *show_new_album.write() = true;
+                },
+                PlusOutline {}
+                "New album"
+            }
+        }
+
+        if let Some(Ok(albums)) = &*albums.read() {
+            if !albums.is_empty() {
+                div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                    for album in albums.clone() {
+                        Link {
+                            key: "{album.id}",
+                            class: "btn flex-col gap-2 normal-case p-2 h-full w-full",
+                            to: Routes::album(album.id),
+                            if let Some(cover_url) = album.cover_variant_url(THUMBNAIL_SIZE, THUMBNAIL_SIZE, true) {
+                                img {
+                                    class: "rounded-lg m-auto min-h-0",
+                                    alt: album.name.clone(),
+                                    src: cover_url.to_string(),
+                                }
+                            } else {
+                                div { class: "size-[90%] text-gray-400 flex items-center justify-center",
+                                    RectangleStackOutline {}
+                                }
+                            }
+                            div { class: "truncate w-full shrink-0", {album.name.clone()} }
+                            div { class: "text-xs text-gray-400", "{album.files_count} files" }
+                        }
+                    }
+                }
+            } else {
+                div { class: "text-center mt-6", "No albums yet" }
+            }
+        }
+
+        AlbumModal {
+            is_open: show_new_album,
+            on_success: move |_| albums.restart(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
+
+use crate::icons::ArrowDownTrayOutline;
+use crate::server_fns::{get_all_invoices, get_invoice_url};
+
+#[component]
+pub fn BillingHistoryPage() -> Element {
+    let invoices = use_resource_with_loader("invoices", get_all_invoices);
+    let mut has_error = use_signal(|| false);
+
+    rsx! {
+        PageTitle { "Billing history" }
+
+        h1 { class: "h1", "Billing history" }
+
+        if let Some(Ok(invoices)) = &*invoices.read() {
+            if !invoices.is_empty() {
+                ul { class: "list mt-6",
+                    for invoice in invoices.iter().cloned() {
+                        li { class: "list-row items-center",
+                            div { class: "text-xs", {invoice.ordered_at.format("%Y-%m-%d").to_string()} }
+
+                            div { class: "list-col-grow",
+                                {invoice.plan_name.clone().unwrap_or_else(|| "Subscription".to_owned())}
+                                if !invoice.is_paid {
+                                    span { class: "badge badge-sm badge-warning ml-2", "Unpaid" }
+                                }
+                            }
+
+                            div { class: "font-bold", {invoice.amount.clone()} }
+
+                            button {
+                                class: "btn btn-sm btn-ghost",
+                                disabled: !invoice.is_paid,
+                                title: "Download invoice",
+                                onclick: move |_| async move {
+                                    let result = run_with_loader("invoice-url", move || get_invoice_url(invoice.id)).await;
+
+                                    has_error.set(result.is_err());
+
+                                    if let Ok(invoice_url) = result {
+                                        sdk::open_external_url(invoice_url);
+                                    }
+                                },
+                                ArrowDownTrayOutline {}
+                            }
+                        }
+                    }
+                }
+
+                if has_error() {
+                    div { class: "text-error text-sm mt-2",
+                        "The invoice could not be downloaded. It may still be generating, try again shortly."
+                    }
+                }
+            } else {
+                div { class: "text-center mt-6", "There are no payments yet" }
+            }
+        }
+    }
+}
@@ -1,17 +1,52 @@
+use std::rc::Rc;
+
 use dioxus::prelude::*;
 use uuid::Uuid;
 
 use sdk::components::PageTitle;
 use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
+
+use drive_core::inputs::{FileCommentInput, ImageRegion};
 
-use crate::components::FolderItemMenu;
+use crate::components::{FileCommentThread, FolderItemMenu, ItemDetails};
+use crate::hooks::use_current_user;
 use crate::routes::Routes;
-use crate::server_fns::get_file;
+use crate::server_fns::{
+    attempt_to_create_file_comment, attempt_to_record_file_access, get_all_file_comments, get_file,
+};
+
+fn region_style(region: &ImageRegion) -> String {
+    format!(
+        "left: {}%; top: {}%; width: {}%; height: {}%;",
+        region.x * 100.0,
+        region.y * 100.0,
+        region.width * 100.0,
+        region.height * 100.0
+    )
+}
 
 #[component]
 pub fn FilePage(id: ReadSignal<Uuid>) -> Element {
     let navigator = use_navigator();
-    let file = use_resource_with_loader("file", move || async move { get_file(id()).await.ok().flatten() });
+    let mut file = use_resource_with_loader("file", move || async move { get_file(id()).await.ok().flatten() });
+    let current_user = use_current_user();
+    let mut file_comments = use_resource_with_loader("file-comments", move || get_all_file_comments(id()));
+    let all_file_comments = use_memo(move || {
+        if let Some(Ok(file_comments)) = &*file_comments.read() {
+            file_comments.clone()
+        } else {
+            vec![]
+        }
+    });
+    let current_user_id = use_memo(move || current_user.read().clone().flatten().map(|user| user.id()));
+    let mut image_element = use_signal(|| None::<Rc<MountedData>>);
+    let mut is_annotating = use_signal(|| false);
+    let mut region_start = use_signal(|| None::<(f64, f64)>);
+    let mut new_region = use_signal(|| None::<ImageRegion>);
+    let mut new_body = use_signal(String::new);
+    let mut reply_to = use_signal(|| None::<Uuid>);
+    let highlighted_id = use_signal(|| None::<Uuid>);
     let page_title = use_memo(move || {
         if let Some(Some(file)) = &*file.read() {
             let mut title = "Home > ".to_owned();
@@ -33,6 +68,16 @@ pub fn FilePage(id: ReadSignal<Uuid>) -> Element {
         }
     });
 
+    let reload = move |_| file.restart();
+
+    use_effect(move || {
+        let id = id();
+
+        spawn(async move {
+            let _ = attempt_to_record_file_access(id).await;
+        });
+    });
+
     rsx! {
         if let Some(Some(file)) = &*file.read() {
             PageTitle { {page_title()} }
@@ -51,6 +96,8 @@ pub fn FilePage(id: ReadSignal<Uuid>) -> Element {
                 }
             }
 
+            div { class: "text-xs text-gray-400 truncate", {file.path()} }
+
             div { class: "flex justify-end",
                 FolderItemMenu {
                     folder_item: file,
@@ -60,11 +107,149 @@ pub fn FilePage(id: ReadSignal<Uuid>) -> Element {
                 }
             }
 
-            div { class: "my-4",
-                img {
-                    class: "m-auto max-h-[calc(100vh-2rem)]",
-                    src: file.variant_url(800, 800, false).to_string(),
-                    alt: file.name.clone(),
+            div { class: "my-4 flex justify-center",
+                div {
+                    class: "relative select-none",
+                    class: if is_annotating() { "cursor-crosshair" },
+                    onmounted: move |event| image_element.set(Some(event.data())),
+                    onmousedown: move |event| {
+                        if is_annotating() {
+                            event.prevent_default();
+                            let point = event.element_coordinates();
+                            region_start.set(Some((point.x, point.y)));
+                        }
+                    },
+                    onmouseup: move |event| async move {
+                        let Some((start_x, start_y)) = region_start.take() else {
+                            return;
+                        };
+                        let Some(element) = image_element() else {
+                            return;
+                        };
+                        let Ok(rect) = element.get_client_rect().await else {
+                            return;
+                        };
+                        let point = event.element_coordinates();
+                        let normalize = |value: f64, size: f64| (value / size).clamp(0.0, 1.0);
+                        let (x1, x2) = (normalize(start_x, rect.width()), normalize(point.x, rect.width()));
+                        let (y1, y2) = (normalize(start_y, rect.height()), normalize(point.y, rect.height()));
+
+                        new_region.set(Some(ImageRegion {
+                            x: x1.min(x2),
+                            y: y1.min(y2),
+                            width: (x2 - x1).abs(),
+                            height: (y2 - y1).abs(),
+                        }));
+                        is_annotating.set(false);
+                    },
+                    img {
+                        class: "max-h-[calc(100vh-2rem)]",
+                        draggable: false,
+                        src: file.variant_url(800, 800, false).to_string(),
+                        alt: file.name.clone(),
+                    }
+
+                    for file_comment in all_file_comments() {
+                        if let Some(region) = file_comment.region {
+                            div {
+                                key: "{file_comment.id}",
+                                class: "absolute border-2 pointer-events-none",
+                                class: if highlighted_id() == Some(file_comment.id) { "border-primary" } else { "border-white/60" },
+                                style: region_style(&region),
+                            }
+                        }
+                    }
+
+                    if let Some(region) = new_region() {
+                        div {
+                            class: "absolute border-2 border-dashed border-accent pointer-events-none",
+                            style: region_style(&region),
+                        }
+                    }
+                }
+            }
+
+            ItemDetails {
+                id: file.id,
+                is_file: true,
+                description: file.description.clone(),
+                properties: file.properties.clone(),
+                is_editable: current_user_id() == Some(file.user_id),
+                on_update: reload,
+            }
+
+            div { class: "max-w-2xl m-auto",
+                h2 { class: "h4 mb-2", "Comments" }
+
+                for file_comment in all_file_comments().into_iter().filter(|file_comment| file_comment.parent_comment_id.is_none()) {
+                    FileCommentThread {
+                        key: "{file_comment.id}",
+                        file_comment: file_comment.clone(),
+                        file_comments: all_file_comments,
+                        current_user_id: current_user_id(),
+                        highlighted_id,
+                        on_reply: move |file_comment_id| reply_to.set(Some(file_comment_id)),
+                        on_update: move |_| file_comments.restart(),
+                    }
+                }
+
+                div { class: "mt-4",
+                    if let Some(parent_comment) = reply_to().and_then(|reply_to| all_file_comments().into_iter().find(|file_comment| file_comment.id == reply_to)) {
+                        div { class: "text-sm mb-1",
+                            "Replying to "
+                            {parent_comment.author.display_name.clone()}
+                            " "
+                            a { class: "link", onclick: move |_| reply_to.set(None), "Cancel" }
+                        }
+                    }
+
+                    textarea {
+                        class: "textarea w-full",
+                        placeholder: "Write a comment",
+                        value: new_body,
+                        oninput: move |event| new_body.set(event.value()),
+                    }
+
+                    div { class: "flex gap-2 mt-2",
+                        button {
+                            class: "btn btn-sm btn-primary",
+                            disabled: new_body.read().trim().is_empty(),
+                            onclick: move |_| async move {
+                                let input = FileCommentInput {
+                                    file_id: id(),
+                                    parent_comment_id: reply_to(),
+                                    body: new_body(),
+                                    region: new_region(),
+                                };
+
+                                if run_with_loader("create-file-comment", move || attempt_to_create_file_comment(input.clone()))
+                                    .await
+                                    .is_ok()
+                                {
+                                    new_body.set(String::new());
+                                    new_region.set(None);
+                                    reply_to.set(None);
+                                    file_comments.restart();
+                                }
+                            },
+                            "Comment"
+                        }
+
+                        if new_region().is_some() {
+                            button {
+                                class: "btn btn-sm",
+                                onclick: move |_| new_region.set(None),
+                                "Remove region"
+                            }
+                        } else {
+                            button {
+                                class: "btn btn-sm",
+                                class: if is_annotating() { "btn-active" },
+                                onclick: move |_| is_annotating.toggle(),
+                                "Mark a region"
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -3,14 +3,14 @@ use uuid::Uuid;
 
 use sdk::components::PageTitle;
 
-use crate::components::FileManager;
+use crate::components::{FileManager, ItemDetails};
 use crate::routes::Routes;
 use crate::server_fns::get_folder;
 use crate::use_resource_with_loader;
 
 #[component]
 pub fn FolderPage(id: ReadSignal<Uuid>) -> Element {
-    let folder = use_resource_with_loader("folder", move || async move { get_folder(id()).await.ok().flatten() });
+    let mut folder = use_resource_with_loader("folder", move || async move { get_folder(id()).await.ok().flatten() });
     let page_title = use_memo(move || {
         if let Some(Some(folder)) = &*folder.read() {
             let mut title = "Home > ".to_owned();
@@ -32,6 +32,8 @@ pub fn FolderPage(id: ReadSignal<Uuid>) -> Element {
         }
     });
 
+    let reload = move |_| folder.restart();
+
     rsx! {
         if let Some(Some(folder)) = &*folder.read() {
             PageTitle { {page_title()} }
@@ -50,6 +52,16 @@ pub fn FolderPage(id: ReadSignal<Uuid>) -> Element {
                 }
             }
 
+            div { class: "text-xs text-gray-400 truncate", {folder.path()} }
+
+            ItemDetails {
+                id: folder.id,
+                is_file: false,
+                description: folder.description.clone(),
+                properties: folder.properties.clone(),
+                on_update: reload,
+            }
+
             FileManager {
                 min_visibility: folder.visibility,
                 folder: Some(folder.clone()),
@@ -1,15 +1,31 @@
+mod album_page;
+mod albums_page;
 mod authorized_page;
+mod billing_history_page;
 mod confirm_checkout_page;
 mod file_page;
 mod folder_page;
 mod home_page;
+mod path_page;
+mod recent_page;
+mod smart_folder_page;
+mod starred_page;
 mod storage_page;
+mod timeline_page;
 mod trash_page;
 
+pub use album_page::AlbumPage;
+pub use albums_page::AlbumsPage;
 pub use authorized_page::AuthorizedPage;
+pub use billing_history_page::BillingHistoryPage;
 pub use confirm_checkout_page::ConfirmCheckoutPage;
 pub use file_page::FilePage;
 pub use folder_page::FolderPage;
 pub use home_page::HomePage;
+pub use path_page::PathPage;
+pub use recent_page::RecentPage;
+pub use smart_folder_page::SmartFolderPage;
+pub use starred_page::StarredPage;
 pub use storage_page::StoragePage;
+pub use timeline_page::TimelinePage;
 pub use trash_page::TrashPage;
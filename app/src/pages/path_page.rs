@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+
+use crate::routes::Routes;
+use crate::server_fns::get_folder_item_by_path;
+
+#[component]
+pub fn PathPage(segments: ReadSignal<Vec<String>>) -> Element {
+    let navigator = use_navigator();
+    let folder_item = use_resource_with_loader("folder-item", move || async move {
+        get_folder_item_by_path(segments().join("/")).await.ok().flatten()
+    });
+
+    use_effect(move || {
+        if segments().is_empty() {
+            navigator.replace(Routes::home());
+            return;
+        }
+
+        if let Some(Some(folder_item)) = &*folder_item.read() {
+            if folder_item.is_file {
+                navigator.replace(Routes::file(folder_item.id));
+            } else {
+                navigator.replace(Routes::folder(folder_item.id));
+            }
+        }
+    });
+
+    rsx! {
+        if let Some(None) = &*folder_item.read() {
+            PageTitle { "Not found" }
+
+            h1 { class: "h2", "Not found" }
+
+            p { "Nothing was found at " {format!("/{}", segments().join("/"))} }
+
+            Link { class: "btn btn-primary mt-4", to: Routes::home(), "Go home" }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+
+use crate::components::FolderItemCard;
+use crate::server_fns::get_all_recent_files;
+
+#[component]
+pub fn RecentPage() -> Element {
+    let mut all_recent_files = use_resource_with_loader("recent-files", get_all_recent_files);
+
+    rsx! {
+        PageTitle { "Recent" }
+
+        h1 { class: "h1", "Recent" }
+
+        if let Some(Ok(recent_files)) = &*all_recent_files.read() {
+            if !recent_files.is_empty() {
+                div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                    for recent_file in recent_files {
+                        FolderItemCard {
+                            folder_item: recent_file.clone(),
+                            on_update: move |_| all_recent_files.restart(),
+                        }
+                    }
+                }
+            } else {
+                div { class: "text-center mt-6", "There are no recent files" }
+            }
+        }
+    }
+}
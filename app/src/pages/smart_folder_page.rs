@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::components::{ConfirmationModal, PageTitle};
+use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
+
+use drive_core::inputs::FolderItemsQuery;
+
+use crate::components::{FolderItemCard, SmartFolderModal};
+use crate::constants::FOLDER_ITEMS_PAGE_SIZE;
+use crate::hooks::use_smart_folders;
+use crate::icons::{PencilOutline, TrashOutline};
+use crate::routes::Routes;
+use crate::server_fns::{attempt_to_delete_smart_folder, get_all_smart_folder_items, get_smart_folder};
+
+#[component]
+pub fn SmartFolderPage(id: ReadSignal<Uuid>) -> Element {
+    let navigator = use_navigator();
+    let mut smart_folders = use_smart_folders();
+    let mut smart_folder = use_resource_with_loader("smart-folder", move || async move {
+        get_smart_folder(id()).await.ok().flatten()
+    });
+    let mut folder_items_query = use_signal(|| FolderItemsQuery {
+        limit: Some(FOLDER_ITEMS_PAGE_SIZE),
+        ..Default::default()
+    });
+    let mut folder_items = use_signal(Vec::new);
+    let mut next_cursor = use_signal(|| None);
+    let mut show_edit_modal = use_signal(|| false);
+    let mut show_delete_confirmation = use_signal(|| false);
+    let folder_items_page = use_resource_with_loader("smart-folder-items", move || {
+        get_all_smart_folder_items(id(), folder_items_query())
+    });
+
+    use_effect(move || {
+        if let Some(Ok(page)) = &*folder_items_page.read() {
+            if folder_items_query.peek().cursor.is_some() {
+                folder_items.write().extend(page.items.clone());
+            } else {
+                *folder_items.write() = page.items.clone();
+            }
+
+            *next_cursor.write() = page.next_cursor;
+        }
+    });
+
+    use_effect(move || {
+        let _ = id();
+
+        if folder_items_query.peek().cursor.is_some() {
+            folder_items_query.write().cursor = None;
+        }
+    });
+
+    rsx! {
+        if let Some(Some(current_smart_folder)) = &*smart_folder.read() {
+            PageTitle { {current_smart_folder.name.clone()} }
+
+            div { class: "flex gap-2 justify-between items-center",
+                h1 { class: "h2", {current_smart_folder.name.clone()} }
+
+                div { class: "flex gap-2",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| {
+                            *show_edit_modal.write() = true;
+                        },
+                        PencilOutline {}
+                        "Edit"
+                    }
+
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| {
+                            *show_delete_confirmation.write() = true;
+                        },
+                        TrashOutline {}
+                        "Delete"
+                    }
+                }
+            }
+
+            if !folder_items.read().is_empty() {
+                div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                    for folder_item in folder_items() {
+                        FolderItemCard {
+                            key: "{folder_item.id}",
+                            folder_item: folder_item.clone(),
+                            on_update: move |_| folder_items_query.write().cursor = None,
+                        }
+                    }
+                }
+
+                if let Some(cursor) = next_cursor() {
+                    div {
+                        class: "h-1",
+                        onvisible: move |event| {
+                            if event.data().is_intersecting().unwrap_or(false) {
+                                folder_items_query.write().cursor = Some(cursor);
+                            }
+                        },
+                    }
+                }
+            } else if let Some(Ok(_)) = &*folder_items_page.read() {
+                div { class: "text-center mt-6", "No items match this smart folder" }
+            }
+
+            SmartFolderModal {
+                is_open: show_edit_modal,
+                smart_folder: current_smart_folder.clone(),
+                on_success: move |_| {
+                    smart_folder.restart();
+                    smart_folders.restart();
+                    folder_items_query.write().cursor = None;
+                },
+            }
+
+            ConfirmationModal {
+                is_open: show_delete_confirmation,
+                on_accept: move |_| async move {
+                    if run_with_loader("delete-smart-folder", move || attempt_to_delete_smart_folder(id()))
+                        .await
+                        .is_ok()
+                    {
+                        smart_folders.restart();
+                        navigator.push(Routes::home());
+                    }
+                },
+                "Are you sure you want to delete this smart folder? The matching items are not affected."
+            }
+        }
+    }
+}
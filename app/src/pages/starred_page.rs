@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+
+use crate::components::FolderItemCard;
+use crate::server_fns::get_all_starred_items;
+
+#[component]
+pub fn StarredPage() -> Element {
+    let mut all_starred_items = use_resource_with_loader("starred-items", get_all_starred_items);
+
+    rsx! {
+        PageTitle { "Starred" }
+
+        h1 { class: "h1", "Starred" }
+
+        if let Some(Ok(starred_items)) = &*all_starred_items.read() {
+            if !starred_items.is_empty() {
+                div { class: "grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
+                    for starred_item in starred_items {
+                        FolderItemCard {
+                            folder_item: starred_item.clone(),
+                            on_update: move |_| all_starred_items.restart(),
+                        }
+                    }
+                }
+            } else {
+                div { class: "text-center mt-6", "There are no starred items" }
+            }
+        }
+    }
+}
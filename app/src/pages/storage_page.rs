@@ -1,14 +1,23 @@
 use dioxus::prelude::*;
 
-use sdk::components::PageTitle;
+use sdk::components::{ConfirmationModal, PageTitle};
+use sdk::hooks::use_resource_with_loader;
+use sdk::run_with_loader;
 
 use crate::components::SubscriptionModal;
 use crate::hooks::use_current_user;
+use crate::icons::{DocumentOutline, FolderOutline, TrashOutline};
+use crate::routes::Routes;
+use crate::server_fns::{attempt_to_cancel_subscription, get_current_billing, get_storage_usage};
 
 #[component]
 pub fn StoragePage() -> Element {
     let mut current_user = use_current_user();
     let mut show_modal = use_signal(|| false);
+    let mut show_cancel_confirmation = use_signal(|| false);
+    let mut billing = use_resource_with_loader("current-billing", get_current_billing);
+    let storage_usage = use_resource_with_loader("storage-usage", get_storage_usage);
+    let current_billing = billing.read().clone().and_then(|result| result.ok()).flatten();
 
     rsx! {
         PageTitle { "Storage" }
@@ -36,12 +45,110 @@ pub fn StoragePage() -> Element {
                     },
                     "Get more space"
                 }
+            }
 
-                SubscriptionModal {
-                    is_open: show_modal,
-                    on_success: move |_| {
-                        current_user.restart();
-                    },
+            if let Some(billing) = current_billing.clone() {
+                h2 { class: "h2 mt-6", "Billing" }
+
+                div { class: "card card-border card-sm",
+                    div { class: "card-body",
+                        h3 { class: "card-title", {billing.plan.name.clone()} }
+
+                        p {
+                            span { class: "font-bold", {billing.plan.quota.clone()} }
+                            " of space"
+                        }
+
+                        p { class: "opacity-70", {billing.status()} }
+
+                        if !billing.is_canceled && !user.plan_is_cancelable {
+                            p { class: "text-xs opacity-70",
+                                "To cancel your subscription, first free up space until your files fit in the free plan."
+                            }
+                        }
+
+                        div { class: "card-actions justify-end",
+                            if !billing.is_canceled {
+                                button {
+                                    class: "btn btn-sm",
+                                    onclick: move |_| {
+                                        *show_modal.write() = true;
+                                    },
+                                    "Change plan"
+                                }
+                            }
+
+                            if user.plan_is_cancelable {
+                                button {
+                                    class: "btn btn-sm btn-error btn-outline",
+                                    onclick: move |_| {
+                                        *show_cancel_confirmation.write() = true;
+                                    },
+                                    "Cancel subscription"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "text-right mt-2",
+                Link { class: "link text-sm", to: Routes::billing_history(), "Billing history" }
+            }
+
+            SubscriptionModal {
+                is_open: show_modal,
+                billing: current_billing.clone(),
+                on_success: move |_| {
+                    current_user.restart();
+                    billing.restart();
+                },
+            }
+
+            ConfirmationModal {
+                is_open: show_cancel_confirmation,
+                on_accept: move |_| async move {
+                    let _ = run_with_loader("cancel-subscription", attempt_to_cancel_subscription).await;
+
+                    current_user.restart();
+                    billing.restart();
+                },
+                "Are you sure you want to cancel your subscription? You keep your plan until the end of the current billing period."
+            }
+
+            if let Some(Ok(storage_usage)) = &*storage_usage.read() {
+                h2 { class: "h2 mt-6", "What's using your space" }
+
+                ul { class: "list",
+                    for usage in storage_usage.iter().cloned() {
+                        li { class: "list-row items-center",
+                            div {
+                                if usage.folder_id.is_some() {
+                                    FolderOutline {}
+                                } else if usage.is_trash {
+                                    TrashOutline {}
+                                } else {
+                                    DocumentOutline {}
+                                }
+                            }
+
+                            div { class: "list-col-grow",
+                                if let Some(folder_id) = usage.folder_id {
+                                    Link { class: "link", to: Routes::folder(folder_id), {usage.name.clone()} }
+                                } else {
+                                    {usage.name.clone()}
+                                }
+
+                                progress {
+                                    class: "progress w-full",
+                                    value: usage.byte_size_bytes,
+                                    max: user.used_space_bytes,
+                                }
+                            }
+
+                            div { class: "text-xs", {usage.byte_size.clone()} }
+                        }
+                    }
                 }
             }
         }
@@ -0,0 +1,195 @@
+use chrono::{Datelike, Months, NaiveDate};
+use dioxus::prelude::*;
+
+use sdk::components::PageTitle;
+use sdk::hooks::use_resource_with_loader;
+
+use drive_core::enums::TimelineGrouping;
+use drive_core::inputs::TimelineQuery;
+
+use crate::constants::{FOLDER_ITEMS_PAGE_SIZE, THUMBNAIL_SIZE};
+use crate::presenters::{TimelineGroupPresenter, TimelineItemPresenter};
+use crate::routes::Routes;
+use crate::server_fns::{get_all_timeline_items, get_timeline_groups};
+
+const GROUPING_OPTIONS: [(&str, TimelineGrouping); 2] =
+    [("Day", TimelineGrouping::Day), ("Month", TimelineGrouping::Month)];
+
+fn group_period(timeline_item: &TimelineItemPresenter, grouping: TimelineGrouping) -> NaiveDate {
+    let date = timeline_item.captured_at.date_naive();
+
+    match grouping {
+        TimelineGrouping::Day => date,
+        TimelineGrouping::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn group_label(period: NaiveDate, grouping: TimelineGrouping) -> String {
+    match grouping {
+        TimelineGrouping::Day => period.format("%B %-d, %Y").to_string(),
+        TimelineGrouping::Month => period.format("%B %Y").to_string(),
+    }
+}
+
+fn group_last_day(period: NaiveDate, grouping: TimelineGrouping) -> NaiveDate {
+    match grouping {
+        TimelineGrouping::Day => period,
+        TimelineGrouping::Month => period
+            .checked_add_months(Months::new(1))
+            .and_then(|next_month| next_month.pred_opt())
+            .unwrap_or(period),
+    }
+}
+
+#[component]
+pub fn TimelinePage() -> Element {
+    let mut grouping = use_signal(TimelineGrouping::default);
+    let mut timeline_query = use_signal(|| TimelineQuery {
+        limit: Some(FOLDER_ITEMS_PAGE_SIZE),
+        ..Default::default()
+    });
+    let mut timeline_items = use_signal(Vec::<TimelineItemPresenter>::new);
+    let mut next_cursor = use_signal(|| None);
+    let timeline_groups = use_resource_with_loader("timeline-groups", move || get_timeline_groups(grouping()));
+    let timeline_page = use_resource_with_loader("timeline-items", move || get_all_timeline_items(timeline_query()));
+
+    use_effect(move || {
+        if let Some(Ok(page)) = &*timeline_page.read() {
+            if timeline_query.peek().cursor.is_some() {
+                timeline_items.write().extend(page.items.clone());
+            } else {
+                *timeline_items.write() = page.items.clone();
+            }
+
+            *next_cursor.write() = page.next_cursor;
+        }
+    });
+
+    let sections = use_memo(move || {
+        let grouping = grouping();
+        let mut sections: Vec<(NaiveDate, Vec<TimelineItemPresenter>)> = Vec::new();
+
+        for timeline_item in timeline_items() {
+            let period = group_period(&timeline_item, grouping);
+
+            match sections.last_mut() {
+                Some((last_period, items)) if *last_period == period => items.push(timeline_item),
+                _ => sections.push((period, vec![timeline_item])),
+            }
+        }
+
+        sections
+    });
+
+    let files_count = move |period: NaiveDate| {
+        if let Some(Ok(timeline_groups)) = &*timeline_groups.read() {
+            timeline_groups
+                .iter()
+                .find(|timeline_group| timeline_group.period == period)
+                .map(|timeline_group| timeline_group.files_count)
+        } else {
+            None
+        }
+    };
+
+    rsx! {
+        PageTitle { "Timeline" }
+
+        div { class: "flex gap-2 justify-between items-center",
+            h1 { class: "h2", "Timeline" }
+
+            div { class: "join",
+                for (label , value) in GROUPING_OPTIONS {
+                    button {
+                        class: "btn btn-outline join-item",
+                        class: if grouping() == value { "btn-active" },
+                        onclick: move |_| {
+                            *grouping.write() = value;
+                        },
+                        {label}
+                    }
+                }
+            }
+        }
+
+        div { class: "flex gap-6 mt-6",
+            div { class: "grow min-w-0",
+                if !sections.read().is_empty() {
+                    for (period , items) in sections() {
+                        section { key: "{period}", class: "mb-6",
+                            h2 { class: "h4 mb-3",
+                                {group_label(period, grouping())}
+
+                                if let Some(files_count) = files_count(period) {
+                                    span { class: "text-sm text-gray-400 ml-2", "{files_count} photos" }
+                                }
+                            }
+
+                            div { class: "grid grid-cols-3 sm:grid-cols-4 md:grid-cols-5 lg:grid-cols-6 gap-2",
+                                for timeline_item in items {
+                                    Link {
+                                        key: "{timeline_item.folder_item.id}",
+                                        class: "btn p-1 h-full w-full",
+                                        to: Routes::file(timeline_item.folder_item.id),
+                                        img {
+                                            class: "rounded-lg m-auto min-h-0",
+                                            alt: timeline_item.folder_item.name.clone(),
+                                            src: timeline_item
+                                                .folder_item
+                                                .variant_url(THUMBNAIL_SIZE, THUMBNAIL_SIZE, true)
+                                                .map(|url| url.to_string()),
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(cursor) = next_cursor() {
+                        div {
+                            class: "h-1",
+                            onvisible: move |event| {
+                                if event.data().is_intersecting().unwrap_or(false) {
+                                    timeline_query.write().cursor = Some(cursor);
+                                }
+                            },
+                        }
+                    }
+                } else if let Some(Ok(_)) = &*timeline_page.read() {
+                    div { class: "text-center", "No photos yet" }
+                }
+            }
+
+            if let Some(Ok(timeline_groups)) = &*timeline_groups.read() {
+                ul { class: "menu menu-sm max-md:hidden shrink-0 sticky top-4 self-start max-h-[calc(100vh-2rem)] overflow-y-auto flex-nowrap",
+                    li {
+                        button {
+                            class: if timeline_query.read().before.is_none() { "menu-active" },
+                            onclick: move |_| {
+                                let mut query = timeline_query.write();
+                                query.before = None;
+                                query.cursor = None;
+                            },
+                            "Latest"
+                        }
+                    }
+
+                    for TimelineGroupPresenter { period , files_count } in timeline_groups.clone() {
+                        li { key: "{period}",
+                            button {
+                                class: if timeline_query.read().before == Some(group_last_day(period, grouping())) { "menu-active" },
+                                onclick: move |_| {
+                                    let mut query = timeline_query.write();
+                                    query.before = Some(group_last_day(period, grouping()));
+                                    query.cursor = None;
+                                },
+                                {group_label(period, grouping())}
+                                span { class: "badge badge-sm", "{files_count}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,78 @@
 use dioxus::prelude::*;
+use uuid::Uuid;
 
 use sdk::components::{ConfirmationModal, PageTitle};
 use sdk::hooks::use_resource_with_loader;
 use sdk::{loader_is_active, run_with_loader};
 
+use drive_core::enums::ConflictPolicy;
+
+use crate::components::{ConflictPolicyModal, FolderPickerModal, folder_item_ids};
 use crate::hooks::use_current_user;
-use crate::icons::{EllipsisVerticalOutline, FolderOutline};
-use crate::presenters::FolderItemPresenter;
+use crate::icons::{EllipsisVerticalOutline, FolderOutline, TrashOutline};
+use crate::presenters::{BatchItemResultPresenter, TrashItemPresenter};
 use crate::server_fns::{
-    attempt_to_empty_trash, attempt_to_restore_file, attempt_to_restore_folder, get_all_trash_items,
+    attempt_to_delete_file, attempt_to_delete_folder, attempt_to_delete_items, attempt_to_empty_trash,
+    attempt_to_restore_file, attempt_to_restore_file_to_folder, attempt_to_restore_folder,
+    attempt_to_restore_folder_to_folder, attempt_to_restore_items, get_all_trash_items, get_conflicting_item_names,
 };
 
+async fn restore_trash_item(id: Uuid, is_file: bool, target_folder_id: Option<Option<Uuid>>, policy: ConflictPolicy) {
+    let _ = match (is_file, target_folder_id) {
+        (true, None) => run_with_loader("restore-file", move || attempt_to_restore_file(id, policy)).await,
+        (false, None) => run_with_loader("restore-folder", move || attempt_to_restore_folder(id, policy)).await,
+        (true, Some(target_folder_id)) => {
+            run_with_loader("restore-file-to-folder", move || {
+                attempt_to_restore_file_to_folder(id, target_folder_id, policy)
+            })
+            .await
+        }
+        (false, Some(target_folder_id)) => {
+            run_with_loader("restore-folder-to-folder", move || {
+                attempt_to_restore_folder_to_folder(id, target_folder_id, policy)
+            })
+            .await
+        }
+    };
+}
+
 #[component]
-pub fn TrashItemMenu(trash_item: FolderItemPresenter, #[props(into)] on_restore: Callback) -> Element {
+pub fn TrashItemMenu(trash_item: TrashItemPresenter, #[props(into)] on_update: Callback) -> Element {
+    let mut show_folder_picker = use_signal(|| false);
+    let mut show_delete_confirmation = use_signal(|| false);
+    let mut show_conflict_modal = use_signal(|| false);
+    let mut conflicting_names = use_signal(Vec::new);
+    let mut restore_target = use_signal(|| None::<Option<Uuid>>);
+    let id = trash_item.folder_item.id;
+    let is_file = trash_item.folder_item.is_file;
+    let parent_folder_id = trash_item.folder_item.parent_folder_id;
+    let name = trash_item.folder_item.name.clone();
+    let folder_picker_title = if trash_item.can_be_restored_in_place {
+        "Restore to"
+    } else {
+        "The original folder is unavailable, restore to"
+    };
+
+    let restore = move |target_folder_id: Option<Option<Uuid>>| {
+        let name = name.clone();
+
+        async move {
+            let names = get_conflicting_item_names(target_folder_id.unwrap_or(parent_folder_id), vec![name], Some(id))
+                .await
+                .unwrap_or_default();
+
+            if names.is_empty() {
+                restore_trash_item(id, is_file, target_folder_id, ConflictPolicy::Fail).await;
+                on_update.call(());
+            } else {
+                restore_target.set(target_folder_id);
+                conflicting_names.set(names);
+                *show_conflict_modal.write() = true;
+            }
+        }
+    };
+    let restore_to_folder = restore.clone();
+
     rsx! {
         div { class: "absolute top-0.5 right-0.5",
             div { class: "dropdown dropdown-end absolute top-1 right-1",
@@ -26,33 +86,65 @@ pub fn TrashItemMenu(trash_item: FolderItemPresenter, #[props(into)] on_restore:
                 ul {
                     class: "menu menu-sm dropdown-content bg-base-200 rounded-box shadow mt-3 p-2 w-max z-1",
                     tabindex: 0,
+                    if trash_item.can_be_restored_in_place {
+                        li {
+                            a { onclick: move |_| restore(None), "Restore" }
+                        }
+                    }
+
                     li {
                         a {
-                            onclick: {
-                                let id = trash_item.id;
-
-                                move |_| async move {
-                                    if trash_item.is_file {
-                                        let _ = run_with_loader(
-                                                "restore-file",
-                                                move || attempt_to_restore_file(id),
-                                            )
-                                            .await;
-                                    } else {
-                                        let _ = run_with_loader(
-                                                "restore-folder",
-                                                move || attempt_to_restore_folder(id),
-                                            )
-                                            .await;
-                                    }
-                                    on_restore.call(());
-                                }
-                            },
-                            "Restore"
+                            onclick: move |_| *show_folder_picker.write() = true,
+                            "Restore to…"
                         }
                     }
+
+                    div { class: "divider m-1" }
+
+                    li {
+                        a {
+                            onclick: move |_| *show_delete_confirmation.write() = true,
+                            TrashOutline {}
+                            "Delete forever"
+                        }
+                    }
+                }
+            }
+        }
+
+        FolderPickerModal {
+            is_open: show_folder_picker,
+            title: folder_picker_title,
+            on_select: move |target_folder_id| restore_to_folder(Some(target_folder_id)),
+        }
+
+        ConflictPolicyModal {
+            is_open: show_conflict_modal,
+            names: conflicting_names,
+            can_merge: !is_file,
+            on_select: move |policy| async move {
+                restore_trash_item(id, is_file, restore_target(), policy).await;
+                on_update.call(());
+            },
+        }
+
+        ConfirmationModal {
+            is_open: show_delete_confirmation,
+            on_accept: move |_| async move {
+                if is_file {
+                    let _ = run_with_loader("delete-file", move || attempt_to_delete_file(id)).await;
+                } else {
+                    let _ = run_with_loader("delete-folder", move || attempt_to_delete_folder(id)).await;
                 }
+                on_update.call(());
+            },
+            "Are you sure you want to delete this "
+            if is_file {
+                "file"
+            } else {
+                "folder"
             }
+            " forever? This can't be undone."
         }
     }
 }
@@ -69,6 +161,77 @@ pub fn TrashPage() -> Element {
             .map_or(0, |trash_items| trash_items.len())
     });
     let mut show_empty_confirmation = use_signal(|| false);
+    let mut show_delete_items_confirmation = use_signal(|| false);
+    let mut show_conflict_modal = use_signal(|| false);
+    let mut conflicting_names = use_signal(Vec::new);
+    let mut selected_ids = use_signal(Vec::<Uuid>::new);
+    let mut failed_items_count = use_signal(|| 0);
+    let selected_items = use_memo(move || {
+        all_trash_items
+            .read()
+            .as_ref()
+            .and_then(|result| result.as_ref().ok())
+            .map(|trash_items| {
+                trash_items
+                    .iter()
+                    .filter(|trash_item| selected_ids.read().contains(&trash_item.folder_item.id))
+                    .map(|trash_item| trash_item.folder_item.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+    let mut toggle_trash_item = move |id: Uuid| {
+        if selected_ids.peek().contains(&id) {
+            selected_ids.write().retain(|selected_id| *selected_id != id);
+        } else {
+            selected_ids.write().push(id);
+        }
+    };
+    let mut finish_batch = move |results: Vec<BatchItemResultPresenter>| -> Vec<Uuid> {
+        let failed_ids = results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .map(|result| result.id)
+            .collect::<Vec<_>>();
+
+        failed_items_count.set(failed_ids.len());
+        *selected_ids.write() = failed_ids.clone();
+        current_user.restart();
+        all_trash_items.restart();
+
+        failed_ids
+    };
+    let restore_items = move |policy: ConflictPolicy| {
+        let selected_items = selected_items();
+        let ids = folder_item_ids(&selected_items);
+
+        async move {
+            let result = run_with_loader("restore-items", move || attempt_to_restore_items(ids.clone(), policy)).await;
+
+            if let Ok(results) = result {
+                let conflicting_ids = results
+                    .iter()
+                    .filter(|result| policy == ConflictPolicy::Fail && result.is_name_conflict())
+                    .map(|result| result.id)
+                    .collect::<Vec<_>>();
+                let failed_ids = finish_batch(results);
+
+                if !conflicting_ids.is_empty() {
+                    // Other failures are only counted, so picking a policy retries the name conflicts alone.
+                    failed_items_count.set(failed_ids.len() - conflicting_ids.len());
+                    *selected_ids.write() = conflicting_ids.clone();
+                    conflicting_names.set(
+                        selected_items
+                            .iter()
+                            .filter(|folder_item| conflicting_ids.contains(&folder_item.id))
+                            .map(|folder_item| folder_item.name.clone())
+                            .collect(),
+                    );
+                    *show_conflict_modal.write() = true;
+                }
+            }
+        }
+    };
 
     rsx! {
         PageTitle { "Trash" }
@@ -92,30 +255,59 @@ pub fn TrashPage() -> Element {
             if !trash_items.is_empty() {
                 div { class: "grid grid-cols-3 sm:grid-cols-4 lg:grid-cols-5 gap-3 mt-6",
                     for trash_item in trash_items {
-                        div { class: "relative",
-                            if trash_item.is_file {
+                        div {
+                            class: "group relative rounded-lg",
+                            class: if selected_ids.read().contains(&trash_item.folder_item.id) { "ring-2 ring-primary" },
+                            if trash_item.folder_item.is_file {
                                 div { class: "btn flex-col gap-2 p-2 h-full w-full",
                                     img {
                                         class: "rounded-lg m-auto",
-                                        src: trash_item.variant_url(200, 200, false).unwrap().to_string(),
+                                        src: trash_item.folder_item.variant_url(200, 200, false).unwrap().to_string(),
                                     }
 
                                     div { class: "normal-case truncate w-full",
-                                        {trash_item.name.clone()}
+                                        {trash_item.folder_item.name.clone()}
+                                    }
+
+                                    div { class: "text-xs font-normal text-gray-400",
+                                        {trash_item.deletion_message()}
+                                    }
+
+                                    div { class: "text-xs font-normal text-gray-400 truncate w-full",
+                                        {trash_item.folder_item.path()}
                                     }
                                 }
                             } else {
                                 div { class: "btn flex-col gap-2 normal-case p-2 h-full w-full",
                                     FolderOutline { class: "size-[90%]" }
                                     div { class: "normal-case truncate w-full",
-                                        {trash_item.name.clone()}
+                                        {trash_item.folder_item.name.clone()}
+                                    }
+
+                                    div { class: "text-xs font-normal text-gray-400",
+                                        {trash_item.deletion_message()}
+                                    }
+
+                                    div { class: "text-xs font-normal text-gray-400 truncate w-full",
+                                        {trash_item.folder_item.path()}
                                     }
                                 }
                             }
 
+                            input {
+                                checked: selected_ids.read().contains(&trash_item.folder_item.id),
+                                class: "checkbox checkbox-sm absolute top-2 left-2",
+                                class: if selected_ids.read().is_empty() { "opacity-0 group-hover:opacity-100" },
+                                r#type: "checkbox",
+                                onclick: {
+                                    let id = trash_item.folder_item.id;
+                                    move |_| toggle_trash_item(id)
+                                },
+                            }
+
                             TrashItemMenu {
                                 trash_item: trash_item.clone(),
-                                on_restore: move |_| {
+                                on_update: move |_| {
                                     current_user.restart();
                                     all_trash_items.restart();
                                 },
@@ -124,6 +316,66 @@ pub fn TrashPage() -> Element {
                     }
                 }
 
+                if !selected_ids.read().is_empty() {
+                    div { class: "fixed bottom-4 flex",
+                        div { role: "alert", class: "alert",
+                            {format!("{} selected", selected_ids.read().len())}
+
+                            if failed_items_count() > 0 {
+                                span { class: "text-error",
+                                    {format!("{} could not be updated", failed_items_count())}
+                                }
+                            }
+
+                            button {
+                                class: "btn btn-sm btn-outline",
+                                onclick: move |_| {
+                                    selected_ids.write().clear();
+                                    failed_items_count.set(0);
+                                },
+                                "Clear"
+                            }
+
+                            button {
+                                class: "btn btn-sm btn-outline",
+                                onclick: move |_| restore_items(ConflictPolicy::Fail),
+                                "Restore"
+                            }
+
+                            button {
+                                class: "btn btn-sm btn-error",
+                                onclick: move |_| *show_delete_items_confirmation.write() = true,
+                                TrashOutline {}
+                                "Delete forever"
+                            }
+                        }
+                    }
+                }
+
+                ConflictPolicyModal {
+                    is_open: show_conflict_modal,
+                    names: conflicting_names,
+                    can_skip: true,
+                    on_select: move |policy| async move {
+                        if policy != ConflictPolicy::Fail {
+                            restore_items(policy).await;
+                        }
+                    },
+                }
+
+                ConfirmationModal {
+                    is_open: show_delete_items_confirmation,
+                    on_accept: move |_| async move {
+                        let ids = folder_item_ids(&selected_items());
+                        let result = run_with_loader("delete-items", move || attempt_to_delete_items(ids.clone())).await;
+
+                        if let Ok(results) = result {
+                            finish_batch(results);
+                        }
+                    },
+                    {format!("Are you sure you want to delete {} items forever? This can't be undone.", selected_ids.read().len())}
+                }
+
                 ConfirmationModal {
                     is_open: show_empty_confirmation,
                     on_accept: move |_| async move {
@@ -1,11 +1,19 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
-use drive_core::enums::FileVisibility;
+use drive_core::constants::ERROR_NAME_CONFLICT;
+use drive_core::enums::{FileVisibility, NotificationKind, OverQuotaPolicy};
+use drive_core::inputs::{FolderItemsFilter, ImageRegion, ItemProperties};
 
 #[cfg(feature = "server")]
-use drive_core::server::models::{File, Folder, FolderItem, Plan, User};
+use bytesize::ByteSize;
+#[cfg(feature = "server")]
+use drive_core::server::models::{
+    Album, BatchItemResult, File, FileComment, Folder, FolderItem, FolderStats, Invoice, Notification, Plan,
+    SmartFolder, TimelineGroup, TimelineItem, TrashItem, User,
+};
 
 #[cfg(feature = "server")]
 pub trait AsyncInto<T> {
@@ -20,17 +28,150 @@ fn file_variant_url(file_url: &Url, width: u16, height: u16, fill: bool) -> Url
     variant_url
 }
 
+fn folder_item_path(parent_folders: &[FolderPresenter], name: &str) -> String {
+    parent_folders
+        .iter()
+        .map(|parent_folder| parent_folder.name.as_str())
+        .chain([name])
+        .fold(String::new(), |path, name| path + "/" + name)
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct AlbumPresenter {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub cover_file_id: Option<Uuid>,
+    pub name: String,
+    pub visibility: FileVisibility,
+    pub cover_url: Option<Url>,
+    pub files_count: i64,
+}
+
+impl AlbumPresenter {
+    pub fn cover_variant_url(&self, width: u16, height: u16, fill: bool) -> Option<Url> {
+        self.cover_url
+            .as_ref()
+            .map(|url| file_variant_url(url, width, height, fill))
+    }
+}
+
+#[cfg(feature = "server")]
+impl AsyncInto<AlbumPresenter> for Album<'_> {
+    async fn async_into(&self) -> AlbumPresenter {
+        let cover_url = if let Some(cover_file) = self.cover_file().await {
+            Some(cover_file.url().await)
+        } else {
+            None
+        };
+
+        AlbumPresenter {
+            id: self.id,
+            user_id: self.user_id,
+            cover_file_id: self.cover_file_id,
+            name: self.name.to_string(),
+            visibility: self.visibility,
+            cover_url,
+            files_count: self.files_count().await,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct BatchItemResultPresenter {
+    pub id: Uuid,
+    pub is_file: bool,
+    pub error: Option<String>,
+}
+
+impl BatchItemResultPresenter {
+    pub fn is_name_conflict(&self) -> bool {
+        self.error.as_deref() == Some(ERROR_NAME_CONFLICT)
+    }
+}
+
+#[cfg(feature = "server")]
+impl From<BatchItemResult> for BatchItemResultPresenter {
+    fn from(result: BatchItemResult) -> Self {
+        BatchItemResultPresenter {
+            id: result.id,
+            is_file: result.is_file,
+            error: result.error,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct BillingPresenter {
+    pub plan: PlanPresenter,
+    pub is_yearly: Option<bool>,
+    pub is_canceled: bool,
+    pub period_ends_at: Option<DateTime<Utc>>,
+}
+
+impl BillingPresenter {
+    pub fn status(&self) -> String {
+        let interval = match self.is_yearly {
+            Some(true) => "Billed yearly",
+            Some(false) => "Billed monthly",
+            None => "Active",
+        };
+        let Some(period_ends_at) = self.period_ends_at.map(|ends_at| ends_at.format("%B %-d, %Y")) else {
+            return interval.to_owned();
+        };
+
+        if self.is_canceled {
+            format!("Canceled, ends on {period_ends_at}")
+        } else {
+            format!("{interval}, renews on {period_ends_at}")
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct FileCommentPresenter {
+    pub id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub body: String,
+    pub region: Option<ImageRegion>,
+    pub author: UserPresenter,
+    pub is_edited: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl AsyncInto<FileCommentPresenter> for FileComment<'_> {
+    async fn async_into(&self) -> FileCommentPresenter {
+        FileCommentPresenter {
+            id: self.id,
+            parent_comment_id: self.parent_comment_id,
+            body: self.body.to_string(),
+            region: self.region(),
+            author: self.user().await.async_into().await,
+            is_edited: self.updated_at.is_some(),
+            created_at: self.created_at,
+        }
+    }
+}
+
 #[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct FilePresenter {
     pub id: Uuid,
+    pub user_id: Uuid,
     pub parent_folder_id: Option<Uuid>,
     pub name: String,
     pub visibility: FileVisibility,
+    pub is_starred: bool,
+    pub description: Option<String>,
+    pub properties: ItemProperties,
     pub parent_folders: Vec<FolderPresenter>,
     pub url: Url,
 }
 
 impl FilePresenter {
+    pub fn path(&self) -> String {
+        folder_item_path(&self.parent_folders, &self.name)
+    }
+
     pub fn variant_url(&self, width: u16, height: u16, fill: bool) -> Url {
         file_variant_url(&self.url, width, height, fill)
     }
@@ -41,9 +182,13 @@ impl AsyncInto<FilePresenter> for File<'_> {
     async fn async_into(&self) -> FilePresenter {
         FilePresenter {
             id: self.id,
+            user_id: self.user_id,
             parent_folder_id: self.parent_folder_id,
             name: self.name.to_string(),
             visibility: self.visibility,
+            is_starred: self.is_starred().await,
+            description: self.description.as_ref().map(|description| description.to_string()),
+            properties: self.properties.0.clone(),
             parent_folders: futures::future::join_all(
                 self.parent_folders().await.iter().map(|folder| folder.async_into()),
             )
@@ -57,9 +202,13 @@ impl From<FolderItemPresenter> for FilePresenter {
     fn from(folder_item: FolderItemPresenter) -> Self {
         Self {
             id: folder_item.id,
+            user_id: folder_item.user_id,
             parent_folder_id: folder_item.parent_folder_id,
             name: folder_item.name.clone(),
             visibility: folder_item.visibility,
+            is_starred: folder_item.is_starred,
+            description: None,
+            properties: ItemProperties::new(),
             parent_folders: vec![],
             url: folder_item.url.clone().expect("Could not get file url"),
         }
@@ -69,15 +218,22 @@ impl From<FolderItemPresenter> for FilePresenter {
 #[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct FolderItemPresenter {
     pub id: Uuid,
+    pub user_id: Uuid,
     pub parent_folder_id: Option<Uuid>,
     pub is_file: bool,
     pub name: String,
     pub visibility: FileVisibility,
+    pub is_starred: bool,
     pub url: Option<Url>,
+    pub stats: Option<FolderStatsPresenter>,
     pub parent_folders: Vec<FolderPresenter>,
 }
 
 impl FolderItemPresenter {
+    pub fn path(&self) -> String {
+        folder_item_path(&self.parent_folders, &self.name)
+    }
+
     pub fn variant_url(&self, width: u16, height: u16, fill: bool) -> Option<Url> {
         self.url.as_ref().map(|url| file_variant_url(url, width, height, fill))
     }
@@ -88,11 +244,18 @@ impl AsyncInto<FolderItemPresenter> for FolderItem<'_> {
     async fn async_into(&self) -> FolderItemPresenter {
         FolderItemPresenter {
             id: self.id,
+            user_id: self.user_id,
             parent_folder_id: self.parent_folder_id,
             is_file: self.is_file,
             name: self.name.to_string(),
             visibility: self.visibility,
+            is_starred: self.is_starred().await,
             url: self.url().await,
+            stats: if self.is_file {
+                None
+            } else {
+                Some((&Folder::from(self).stats().await).into())
+            },
             parent_folders: futures::future::join_all(
                 self.parent_folders().await.iter().map(|folder| folder.async_into()),
             )
@@ -105,11 +268,14 @@ impl From<&FilePresenter> for FolderItemPresenter {
     fn from(file: &FilePresenter) -> Self {
         FolderItemPresenter {
             id: file.id,
+            user_id: file.user_id,
             parent_folder_id: file.parent_folder_id,
             is_file: true,
             name: file.name.to_string(),
             visibility: file.visibility,
+            is_starred: file.is_starred,
             url: Some(file.url.clone()),
+            stats: None,
             parent_folders: file.parent_folders.clone(),
         }
     }
@@ -125,11 +291,14 @@ impl From<FolderPresenter> for FolderItemPresenter {
     fn from(folder: FolderPresenter) -> Self {
         Self {
             id: folder.id,
+            user_id: folder.user_id,
             parent_folder_id: folder.parent_folder_id,
             is_file: false,
             name: folder.name,
             visibility: folder.visibility,
+            is_starred: folder.is_starred,
             url: None,
+            stats: folder.stats,
             parent_folders: folder.parent_folders,
         }
     }
@@ -138,20 +307,36 @@ impl From<FolderPresenter> for FolderItemPresenter {
 #[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct FolderPresenter {
     pub id: Uuid,
+    pub user_id: Uuid,
     pub parent_folder_id: Option<Uuid>,
     pub name: String,
     pub visibility: FileVisibility,
+    pub is_starred: bool,
+    pub description: Option<String>,
+    pub properties: ItemProperties,
+    pub stats: Option<FolderStatsPresenter>,
     pub parent_folders: Vec<FolderPresenter>,
 }
 
+impl FolderPresenter {
+    pub fn path(&self) -> String {
+        folder_item_path(&self.parent_folders, &self.name)
+    }
+}
+
 #[cfg(feature = "server")]
 impl AsyncInto<FolderPresenter> for Folder<'_> {
     async fn async_into(&self) -> FolderPresenter {
         FolderPresenter {
             id: self.id,
+            user_id: self.user_id,
             parent_folder_id: self.parent_folder_id,
             name: self.name.to_string(),
             visibility: self.visibility,
+            is_starred: self.is_starred().await,
+            description: self.description.as_ref().map(|description| description.to_string()),
+            properties: self.properties.0.clone(),
+            stats: Some((&self.stats().await).into()),
             parent_folders: futures::future::join_all(
                 self.parent_folders().await.iter().map(|folder| folder.async_into()),
             )
@@ -164,22 +349,130 @@ impl From<&FolderItemPresenter> for FolderPresenter {
     fn from(folder_item: &FolderItemPresenter) -> Self {
         Self {
             id: folder_item.id,
+            user_id: folder_item.user_id,
             parent_folder_id: folder_item.parent_folder_id,
             name: folder_item.name.to_string(),
             visibility: folder_item.visibility,
+            is_starred: folder_item.is_starred,
+            description: None,
+            properties: ItemProperties::new(),
+            stats: folder_item.stats.clone(),
             parent_folders: vec![],
         }
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct FolderStatsPresenter {
+    pub byte_size_bytes: u64,
+    pub byte_size: String,
+    pub files_count: u32,
+    pub folders_count: u32,
+}
+
+#[cfg(feature = "server")]
+impl From<&FolderStats> for FolderStatsPresenter {
+    fn from(stats: &FolderStats) -> Self {
+        FolderStatsPresenter {
+            byte_size_bytes: stats.byte_size().as_u64(),
+            byte_size: stats.byte_size().to_string(),
+            files_count: stats.files_count as u32,
+            folders_count: stats.folders_count as u32,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct InvoicePresenter {
+    pub id: Uuid,
+    pub plan_name: Option<String>,
+    pub amount: String,
+    pub is_paid: bool,
+    pub ordered_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl AsyncInto<InvoicePresenter> for Invoice {
+    async fn async_into(&self) -> InvoicePresenter {
+        InvoicePresenter {
+            id: self.id,
+            plan_name: self.plan().await.map(|plan| plan.name.to_string()),
+            amount: self.amount(),
+            is_paid: self.is_paid,
+            ordered_at: self.ordered_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct NotificationPresenter {
+    pub id: Uuid,
+    pub kind: NotificationKind,
+    pub actor: Option<UserPresenter>,
+    pub file_id: Option<Uuid>,
+    pub file_name: Option<String>,
+    pub plan_name: Option<String>,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NotificationPresenter {
+    pub fn message(&self) -> String {
+        let actor_name = self
+            .actor
+            .as_ref()
+            .map(|actor| actor.display_name.as_str())
+            .unwrap_or("Someone");
+        let file_name = self.file_name.as_deref().unwrap_or("a file");
+        let plan_name = self.plan_name.as_deref().unwrap_or("current");
+
+        match self.kind {
+            NotificationKind::FileComment => format!("{actor_name} commented on {file_name}"),
+            NotificationKind::PlanExpiring => format!("Your {plan_name} plan is about to expire"),
+            NotificationKind::PlanExpired => format!("Your {plan_name} plan has expired"),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl AsyncInto<NotificationPresenter> for Notification {
+    async fn async_into(&self) -> NotificationPresenter {
+        let actor = if let Some(actor_user) = self.actor_user().await {
+            Some(actor_user.async_into().await)
+        } else {
+            None
+        };
+
+        NotificationPresenter {
+            id: self.id,
+            kind: self.kind,
+            actor,
+            file_id: self.file_id,
+            file_name: self.file().await.map(|file| file.name.to_string()),
+            plan_name: self.plan().await.map(|plan| plan.name.to_string()),
+            is_read: self.read_at.is_some(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct PagePresenter<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<Uuid>,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct PlanPresenter {
     pub id: Uuid,
     pub name: String,
     pub description: String,
+    pub quota_bytes: u64,
     pub quota: String,
+    pub max_file_size: String,
     pub monthly_price: String,
     pub yearly_price: String,
+    pub trial_days: u16,
 }
 
 #[cfg(feature = "server")]
@@ -189,9 +482,12 @@ impl From<&Plan<'_>> for PlanPresenter {
             id: plan.id,
             name: plan.name.to_string(),
             description: plan.description.to_string(),
+            quota_bytes: plan.quota().as_u64(),
             quota: plan.quota().to_string(),
+            max_file_size: plan.entitlements().max_file_size().to_string(),
             monthly_price: plan.monthly_price(),
             yearly_price: plan.yearly_price(),
+            trial_days: plan.trial_days as u16,
         }
     }
 }
@@ -203,7 +499,116 @@ impl From<Plan<'_>> for PlanPresenter {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct SmartFolderPresenter {
+    pub id: Uuid,
+    pub name: String,
+    pub filter: FolderItemsFilter,
+}
+
+#[cfg(feature = "server")]
+impl From<&SmartFolder<'_>> for SmartFolderPresenter {
+    fn from(smart_folder: &SmartFolder<'_>) -> Self {
+        SmartFolderPresenter {
+            id: smart_folder.id,
+            name: smart_folder.name.to_string(),
+            filter: smart_folder.filter(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct StorageUsagePresenter {
+    pub folder_id: Option<Uuid>,
+    pub is_trash: bool,
+    pub name: String,
+    pub byte_size_bytes: u64,
+    pub byte_size: String,
+}
+
+#[cfg(feature = "server")]
+impl StorageUsagePresenter {
+    pub fn new(folder_id: Option<Uuid>, name: &str, byte_size: ByteSize) -> Self {
+        StorageUsagePresenter {
+            folder_id,
+            is_trash: false,
+            name: name.to_owned(),
+            byte_size_bytes: byte_size.as_u64(),
+            byte_size: byte_size.to_string(),
+        }
+    }
+
+    pub fn trash(byte_size: ByteSize) -> Self {
+        StorageUsagePresenter {
+            is_trash: true,
+            ..Self::new(None, "Trash", byte_size)
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct TimelineGroupPresenter {
+    pub period: NaiveDate,
+    pub files_count: i64,
+}
+
+#[cfg(feature = "server")]
+impl From<&TimelineGroup> for TimelineGroupPresenter {
+    fn from(timeline_group: &TimelineGroup) -> Self {
+        TimelineGroupPresenter {
+            period: timeline_group.period,
+            files_count: timeline_group.files_count,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct TimelineItemPresenter {
+    pub folder_item: FolderItemPresenter,
+    pub captured_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "server")]
+impl AsyncInto<TimelineItemPresenter> for TimelineItem<'_> {
+    async fn async_into(&self) -> TimelineItemPresenter {
+        TimelineItemPresenter {
+            folder_item: FolderItem::from(self).async_into().await,
+            captured_at: self.captured_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+pub struct TrashItemPresenter {
+    pub folder_item: FolderItemPresenter,
+    pub days_until_deletion: u32,
+    pub can_be_restored_in_place: bool,
+}
+
+#[cfg(feature = "server")]
+impl TrashItemPresenter {
+    pub async fn new(trash_item: &TrashItem<'_>, trash_retention_days: u16) -> Self {
+        let deleted_at = trash_item.trashed_at + chrono::Duration::days(trash_retention_days as i64);
+
+        Self {
+            folder_item: FolderItem::from(trash_item).async_into().await,
+            days_until_deletion: ((deleted_at - Utc::now()).num_hours().max(0) as u32).div_ceil(24),
+            can_be_restored_in_place: trash_item.can_be_restored_in_place().await,
+        }
+    }
+}
+
+impl TrashItemPresenter {
+    pub fn deletion_message(&self) -> String {
+        match self.days_until_deletion {
+            0 => "Deleted today".to_owned(),
+            1 => "Deleted in 1 day".to_owned(),
+            days => format!("Deleted in {days} days"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub struct UserPresenter {
     id: Uuid,
     pub username: String,
@@ -215,6 +620,32 @@ pub struct UserPresenter {
     pub used_space: String,
     pub plan: Option<PlanPresenter>,
     pub plan_is_cancelable: bool,
+    pub over_quota_grace_ends_at: Option<DateTime<Utc>>,
+    pub over_quota_policy: OverQuotaPolicy,
+}
+
+impl UserPresenter {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn is_over_quota(&self) -> bool {
+        self.used_space_bytes > self.total_space_bytes
+    }
+
+    pub fn over_quota_notice(&self) -> Option<String> {
+        let grace_ends_at = self.over_quota_grace_ends_at?.format("%B %-d, %Y");
+
+        match self.over_quota_policy {
+            OverQuotaPolicy::ReadOnly => None,
+            OverQuotaPolicy::PurgeTrash => Some(format!(
+                "If you are still over quota on {grace_ends_at}, your trash will be emptied."
+            )),
+            OverQuotaPolicy::Disable => Some(format!(
+                "If you are still over quota on {grace_ends_at}, your account will be disabled."
+            )),
+        }
+    }
 }
 
 #[cfg(feature = "server")]
@@ -239,6 +670,8 @@ impl AsyncInto<UserPresenter> for User<'_> {
             used_space: used_space.to_string(),
             plan,
             plan_is_cancelable,
+            over_quota_grace_ends_at: self.over_quota_grace_ends_at(),
+            over_quota_policy: self.over_quota_policy(),
         }
     }
 }
@@ -12,12 +12,28 @@ pub enum Routes {
     #[layout(UserLayout)]
         #[route("/")]
         HomePage {},
+        #[route("/albums")]
+        AlbumsPage {},
+        #[route("/albums/:id")]
+        AlbumPage { id: Uuid },
+        #[route("/billing-history")]
+        BillingHistoryPage {},
         #[route("/files/:id")]
         FilePage { id: Uuid },
         #[route("/folders/:id")]
         FolderPage { id: Uuid },
+        #[route("/path/:..segments")]
+        PathPage { segments: Vec<String> },
+        #[route("/recent")]
+        RecentPage {},
+        #[route("/smart-folders/:id")]
+        SmartFolderPage { id: Uuid },
+        #[route("/starred")]
+        StarredPage {},
         #[route("/storage")]
         StoragePage {},
+        #[route("/timeline")]
+        TimelinePage {},
         #[route("/trash")]
         TrashPage {},
     #[end_layout]
@@ -33,6 +49,18 @@ impl Routes {
         Self::HomePage {}
     }
 
+    pub fn album(id: Uuid) -> Self {
+        Self::AlbumPage { id }
+    }
+
+    pub fn albums() -> Self {
+        Self::AlbumsPage {}
+    }
+
+    pub fn billing_history() -> Self {
+        Self::BillingHistoryPage {}
+    }
+
     pub fn file(id: Uuid) -> Self {
         Self::FilePage { id }
     }
@@ -41,10 +69,36 @@ impl Routes {
         Self::FolderPage { id }
     }
 
+    pub fn path(path: &str) -> Self {
+        Self::PathPage {
+            segments: path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| segment.to_owned())
+                .collect(),
+        }
+    }
+
+    pub fn recent() -> Self {
+        Self::RecentPage {}
+    }
+
+    pub fn smart_folder(id: Uuid) -> Self {
+        Self::SmartFolderPage { id }
+    }
+
+    pub fn starred() -> Self {
+        Self::StarredPage {}
+    }
+
     pub fn storage() -> Self {
         Self::StoragePage {}
     }
 
+    pub fn timeline() -> Self {
+        Self::TimelinePage {}
+    }
+
     pub fn trash() -> Self {
         Self::TrashPage {}
     }
@@ -0,0 +1,200 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::serv_fn::{FormResult, ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use serde_json::Value;
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::{FormError, FormSuccess, ServFnError};
+
+use drive_core::inputs::AlbumInput;
+
+#[cfg(feature = "server")]
+use drive_core::enums::FileVisibility;
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::{AlbumPresenter, FolderItemPresenter};
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_add_files_to_album(album_id: Uuid, file_ids: Vec<Uuid>) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(album_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let files = futures::future::try_join_all(file_ids.iter().map(|id| commands::get_file_by_id(*id, Some(&user))))
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    commands::add_files_to_album(&album, &files)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_create_album(input: AlbumInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+
+    let result = commands::insert_album(&user, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Album created successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to create album", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_album(id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::delete_album(&album)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_remove_files_from_album(album_id: Uuid, file_ids: Vec<Uuid>) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(album_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::remove_files_from_album(&album, &file_ids)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_reorder_album_files(album_id: Uuid, file_ids: Vec<Uuid>) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(album_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::reorder_album_files(&album, &file_ids)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_set_album_cover(album_id: Uuid, file_id: Option<Uuid>) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(album_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let file = if let Some(file_id) = file_id {
+        Some(
+            commands::get_file_by_id(file_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
+
+    commands::set_album_cover(&album, file.as_ref())
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_album(id: Uuid, input: AlbumInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let album = commands::get_album_by_id(id, Some(&user))
+        .await
+        .map_err(|_| FormError::new("Failed to update album", None))?;
+
+    let result = commands::update_album(&album, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Album updated successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to update album", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_album(id: Uuid) -> ServFnResult<Option<AlbumPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    let result = commands::get_album_by_id(id, None).await;
+
+    Ok(
+        if let Ok(album) = result
+            && album.is_visible_to(&user)
+        {
+            Some(album.async_into().await)
+        } else {
+            None
+        },
+    )
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_album_files(id: Uuid) -> ServFnResult<Vec<FolderItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let album = commands::get_album_by_id(id, None)
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    if !album.is_visible_to(&user) {
+        return Err(ServFnError::not_found().into());
+    }
+
+    let album_files = commands::get_all_album_files(&album)
+        .await
+        .expect("Could not get album files")
+        .into_iter()
+        .filter(|album_file| album_file.user_id == user.id || album_file.visibility >= FileVisibility::Users)
+        .collect::<Vec<_>>();
+
+    Ok(futures::future::join_all(album_files.iter().map(|album_file| album_file.async_into())).await)
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_albums() -> ServFnResult<Vec<AlbumPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let albums = commands::get_all_albums(&user).await.expect("Could not get albums");
+
+    Ok(futures::future::join_all(albums.iter().map(|album| album.async_into())).await)
+}
@@ -0,0 +1,104 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::serv_fn::{ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::ServFnError;
+
+use drive_core::enums::{ConflictPolicy, FileVisibility};
+use drive_core::inputs::FolderItemIds;
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+#[cfg(feature = "server")]
+use drive_core::server::models::BatchItemResult;
+
+use crate::presenters::BatchItemResultPresenter;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[cfg(feature = "server")]
+fn into_presenters(results: Vec<BatchItemResult>) -> Vec<BatchItemResultPresenter> {
+    results.into_iter().map(BatchItemResultPresenter::from).collect()
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_items(ids: FolderItemIds) -> ServFnResult<Vec<BatchItemResultPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    commands::batch_delete(&user, &ids)
+        .await
+        .map(into_presenters)
+        .map_err(|_| ServFnError::bad_request().into())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_move_items(
+    ids: FolderItemIds,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> ServFnResult<Vec<BatchItemResultPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let target_folder = if let Some(target_folder_id) = target_folder_id {
+        Some(
+            commands::get_folder_by_id(target_folder_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::not_found())?,
+        )
+    } else {
+        None
+    };
+
+    commands::batch_move(&user, &ids, target_folder.as_ref(), policy)
+        .await
+        .map(into_presenters)
+        .map_err(|_| ServFnError::bad_request().into())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_move_items_to_trash(ids: FolderItemIds) -> ServFnResult<Vec<BatchItemResultPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    commands::batch_move_to_trash(&user, &ids)
+        .await
+        .map(into_presenters)
+        .map_err(|_| ServFnError::bad_request().into())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_restore_items(
+    ids: FolderItemIds,
+    policy: ConflictPolicy,
+) -> ServFnResult<Vec<BatchItemResultPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    commands::batch_restore(&user, &ids, policy)
+        .await
+        .map(into_presenters)
+        .map_err(|_| ServFnError::bad_request().into())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_items_visibility(
+    ids: FolderItemIds,
+    visibility: FileVisibility,
+) -> ServFnResult<Vec<BatchItemResultPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    commands::batch_update_visibility(&user, &ids, visibility)
+        .await
+        .map(into_presenters)
+        .map_err(|_| ServFnError::bad_request().into())
+}
@@ -0,0 +1,91 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::serv_fn::{FormResult, ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use serde_json::Value;
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::{FormError, FormSuccess, ServFnError};
+
+use drive_core::inputs::{FileCommentInput, FileCommentUpdateInput};
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::FileCommentPresenter;
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_create_file_comment(input: FileCommentInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let file = commands::get_file_by_id(input.file_id, None)
+        .await
+        .map_err(|_| FormError::new("Failed to add comment", None))?;
+
+    let result = commands::insert_file_comment(&user, &file, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Comment added successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to add comment", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_file_comment(id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file_comment = commands::get_file_comment_by_id(id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::delete_file_comment(&file_comment)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_file_comment(input: FileCommentUpdateInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let file_comment = commands::get_file_comment_by_id(input.id, Some(&user))
+        .await
+        .map_err(|_| FormError::new("Failed to update comment", None))?;
+
+    let result = commands::update_file_comment(&file_comment, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Comment updated successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to update comment", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_file_comments(file_id: Uuid) -> ServFnResult<Vec<FileCommentPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, None)
+        .await
+        .ok()
+        .filter(|file| file.is_visible_to(&user))
+        .ok_or_else(ServFnError::not_found)?;
+
+    let file_comments = commands::get_all_file_comments(&file)
+        .await
+        .expect("Could not get file comments");
+
+    Ok(futures::future::join_all(file_comments.iter().map(|file_comment| file_comment.async_into())).await)
+}
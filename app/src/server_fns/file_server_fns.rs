@@ -5,7 +5,8 @@ use uuid::Uuid;
 #[cfg(feature = "server")]
 use serde_json::Value;
 
-use drive_core::inputs::RenameInput;
+use drive_core::enums::ConflictPolicy;
+use drive_core::inputs::{ItemMetadataInput, RenameInput};
 
 #[cfg(feature = "server")]
 use drive_core::server::commands;
@@ -15,11 +16,16 @@ use sdk::serv_fn::{FormResult, ServFnClient, ServFnResult};
 #[cfg(feature = "server")]
 use sdk::serv_fn::{FormError, FormSuccess, ServFnError};
 
+use crate::presenters::FolderItemPresenter;
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
 #[cfg(feature = "server")]
 use super::{extract_user, require_login};
 
 #[server(client = ServFnClient)]
-pub async fn attempt_to_move_file(file_id: Uuid, target_folder_id: Option<Uuid>) -> ServFnResult {
+pub async fn attempt_to_copy_file(file_id: Uuid, target_folder_id: Option<Uuid>) -> ServFnResult {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
@@ -36,7 +42,56 @@ pub async fn attempt_to_move_file(file_id: Uuid, target_folder_id: Option<Uuid>)
         None
     };
 
-    commands::move_file(&file, target_folder.as_ref())
+    commands::copy_file(&file, target_folder.as_ref())
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_move_file(
+    file_id: Uuid,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let target_folder = if let Some(target_folder_id) = target_folder_id {
+        Some(
+            commands::get_folder_by_id(target_folder_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
+
+    commands::move_file(&file, target_folder.as_ref(), policy)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_record_file_access(file_id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, None)
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    if !file.is_visible_to(&user) {
+        return Err(ServFnError::not_found().into());
+    }
+
+    commands::insert_or_update_file_access(&user, &file)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -60,6 +115,35 @@ pub async fn attempt_to_rename_file(input: RenameInput) -> FormResult {
     }
 }
 
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_file_metadata(input: ItemMetadataInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let file = commands::get_file_by_id(input.id, Some(&user))
+        .await
+        .map_err(|_| FormError::new("Failed to update file details", None))?;
+
+    let result = commands::update_file_metadata(&file, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("File details updated successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to update file details", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_recent_files() -> ServFnResult<Vec<FolderItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let recent_files = commands::get_all_recent_files(&user)
+        .await
+        .expect("Could not get recent files");
+
+    Ok(futures::future::join_all(recent_files.iter().map(|recent_file| recent_file.async_into())).await)
+}
+
 #[server(client = ServFnClient)]
 pub async fn get_file_url(id: Uuid) -> ServFnResult<Url> {
     require_login().await?;
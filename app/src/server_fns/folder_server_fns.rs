@@ -1,7 +1,8 @@
 use dioxus::prelude::*;
 use uuid::Uuid;
 
-use drive_core::inputs::RenameInput;
+use drive_core::enums::ConflictPolicy;
+use drive_core::inputs::{ItemMetadataInput, RenameInput};
 
 #[cfg(feature = "server")]
 use drive_core::server::commands;
@@ -18,7 +19,7 @@ use sdk::serv_fn::{FormError, FormSuccess, ServFnError};
 use super::{extract_user, require_login};
 
 #[server(client = ServFnClient)]
-pub async fn attempt_to_move_folder(folder_id: Uuid, target_folder_id: Option<Uuid>) -> ServFnResult {
+pub async fn attempt_to_copy_folder(folder_id: Uuid, target_folder_id: Option<Uuid>) -> ServFnResult {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
@@ -35,7 +36,36 @@ pub async fn attempt_to_move_folder(folder_id: Uuid, target_folder_id: Option<Uu
         None
     };
 
-    drive_core::server::commands::move_folder(&folder, target_folder.as_ref())
+    commands::copy_folder(&folder, target_folder.as_ref())
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_move_folder(
+    folder_id: Uuid,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let target_folder = if let Some(target_folder_id) = target_folder_id {
+        Some(
+            commands::get_folder_by_id(target_folder_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
+
+    drive_core::server::commands::move_folder(&folder, target_folder.as_ref(), policy)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -58,3 +88,20 @@ pub async fn attempt_to_rename_folder(input: RenameInput) -> FormResult {
         Err(errors) => Err(FormError::new("Failed to rename folder", Some(errors)).into()),
     }
 }
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_folder_metadata(input: ItemMetadataInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let folder = commands::get_folder_by_id(input.id, Some(&user))
+        .await
+        .map_err(|_| FormError::new("Failed to update folder details", None))?;
+
+    let result = commands::update_folder_metadata(&folder, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Folder details updated successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to update folder details", Some(errors)).into()),
+    }
+}
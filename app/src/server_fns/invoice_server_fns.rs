@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+use url::Url;
+use uuid::Uuid;
+
+use sdk::serv_fn::{ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::ServFnError;
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::InvoicePresenter;
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn get_all_invoices() -> ServFnResult<Vec<InvoicePresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let invoices = commands::get_all_invoices(&user).await.expect("Could not get invoices");
+
+    Ok(futures::future::join_all(invoices.iter().map(|invoice| invoice.async_into())).await)
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_invoice_url(id: Uuid) -> ServFnResult<Url> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let invoice = commands::get_invoice_by_id(id, &user)
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::get_invoice_url(&invoice)
+        .await
+        .map_err(|_| ServFnError::bad_request().into())
+}
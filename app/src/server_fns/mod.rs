@@ -11,26 +11,82 @@ use sdk::serv_fn::{FormResult, ServFnClient, ServFnResult};
 #[cfg(feature = "server")]
 use sdk::serv_fn::{FormError, FormSuccess, ServFnError, extract_bearer, require_app_token};
 
-use drive_core::inputs::{FileInput, FolderInput};
+use drive_core::inputs::{FileInput, FolderInput, FolderItemsQuery};
 
+#[cfg(feature = "server")]
+use bytesize::ByteSize;
 #[cfg(feature = "server")]
 use drive_core::server::commands;
 #[cfg(feature = "server")]
-use drive_core::server::models::{Session, User};
+use drive_core::server::models::{Folder, Session, User};
 
-use crate::presenters::{FilePresenter, FolderItemPresenter, FolderPresenter, PlanPresenter, UserPresenter};
+use crate::presenters::{
+    BillingPresenter, FilePresenter, FolderItemPresenter, FolderPresenter, PagePresenter, PlanPresenter,
+    StorageUsagePresenter, UserPresenter,
+};
 
+#[cfg(feature = "server")]
+use crate::constants::FOLDER_ITEMS_PAGE_SIZE;
 #[cfg(feature = "server")]
 use crate::presenters::AsyncInto;
 
+mod album_server_fns;
+mod batch_server_fns;
+mod file_comment_server_fns;
 mod file_server_fns;
 mod folder_server_fns;
+mod invoice_server_fns;
+mod notification_server_fns;
+mod smart_folder_server_fns;
+mod star_server_fns;
+mod timeline_server_fns;
 mod trash_server_fns;
 
+pub use album_server_fns::*;
+pub use batch_server_fns::*;
+pub use file_comment_server_fns::*;
 pub use file_server_fns::*;
 pub use folder_server_fns::*;
+pub use invoice_server_fns::*;
+pub use notification_server_fns::*;
+pub use smart_folder_server_fns::*;
+pub use star_server_fns::*;
+pub use timeline_server_fns::*;
 pub use trash_server_fns::*;
 
+#[server(client = ServFnClient)]
+pub async fn attempt_to_cancel_subscription() -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    if !user.plan_is_cancellable().await {
+        return Err(ServFnError::bad_request().into());
+    }
+
+    commands::cancel_subscription(&user)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_change_plan(plan_id: Uuid, is_yearly: bool) -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let plan = commands::get_plan_by_id(plan_id)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    commands::change_subscription_plan(&user, &plan, is_yearly)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
 #[server(client = ServFnClient)]
 pub async fn attempt_to_confirm_authorization(token: String, expires_at: DateTime<Utc>) -> ServFnResult<String> {
     require_no_login().await?;
@@ -68,15 +124,28 @@ pub async fn attempt_to_create_folder(input: FolderInput) -> FormResult {
 }
 
 #[server(client = ServFnClient)]
-pub async fn attempt_to_create_plan_checkout(plan_id: Uuid, is_yearly: bool) -> ServFnResult<Url> {
+pub async fn attempt_to_create_plan_checkout(
+    plan_id: Uuid,
+    is_yearly: bool,
+    promotion_code: Option<String>,
+) -> ServFnResult<Url> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
     let plan = commands::get_plan_by_id(plan_id)
         .await
         .map_err(|_| ServFnError::bad_request())?;
+    let promotion_code = if let Some(code) = promotion_code {
+        Some(
+            commands::get_redeemable_promotion_code(&code)
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
 
-    let result = commands::create_user_plan_checkout(&user, &plan, is_yearly).await;
+    let result = commands::create_user_plan_checkout(&user, &plan, is_yearly, promotion_code.as_ref()).await;
 
     match result {
         Ok(checkout) => Ok(checkout.url),
@@ -114,9 +183,16 @@ async fn extract_user<'a>() -> ServFnResult<Option<User<'a>>> {
 }
 
 #[server(client = ServFnClient)]
-pub async fn get_all_folder_items(parent_folder_id: Option<Uuid>) -> ServFnResult<Vec<FolderItemPresenter>> {
+pub async fn get_all_folder_items(
+    parent_folder_id: Option<Uuid>,
+    mut query: FolderItemsQuery,
+) -> ServFnResult<PagePresenter<FolderItemPresenter>> {
     require_login().await?;
 
+    if parent_folder_id.is_some() && query.filter.is_some() {
+        return Err(ServFnError::bad_request().into());
+    }
+
     let user = extract_user().await?.unwrap();
     let parent_folder = if let Some(id) = parent_folder_id {
         Some(
@@ -127,11 +203,67 @@ pub async fn get_all_folder_items(parent_folder_id: Option<Uuid>) -> ServFnResul
     } else {
         None
     };
-    let folder_items = commands::get_all_folder_items(Some(&user), parent_folder.as_ref())
+    let limit = query
+        .limit
+        .unwrap_or(FOLDER_ITEMS_PAGE_SIZE)
+        .min(FOLDER_ITEMS_PAGE_SIZE);
+
+    query.limit = Some(limit);
+
+    let folder_items = commands::get_all_folder_items(Some(&user), parent_folder.as_ref(), &query)
         .await
         .expect("Could not get folder items");
+    let next_cursor = if folder_items.len() == limit as usize {
+        folder_items.last().map(|folder_item| folder_item.id)
+    } else {
+        None
+    };
 
-    Ok(futures::future::join_all(folder_items.iter().map(|folder_item| folder_item.async_into())).await)
+    Ok(PagePresenter {
+        items: futures::future::join_all(folder_items.iter().map(|folder_item| folder_item.async_into())).await,
+        next_cursor,
+    })
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_conflicting_item_names(
+    parent_folder_id: Option<Uuid>,
+    names: Vec<String>,
+    except_id: Option<Uuid>,
+) -> ServFnResult<Vec<String>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    let conflicting_names = commands::get_conflicting_item_names(&user, parent_folder_id, &names, except_id)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(conflicting_names)
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_current_billing() -> ServFnResult<Option<BillingPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    let Some(plan) = user.plan().await else {
+        return Ok(None);
+    };
+
+    let subscription = commands::get_user_subscription(&user).await.ok().flatten();
+
+    Ok(Some(BillingPresenter {
+        plan: (&plan).into(),
+        is_yearly: subscription
+            .as_ref()
+            .map(|subscription| subscription.product_id == plan.polar_yearly_product_id),
+        is_canceled: user.polar_subscription_id.is_none(),
+        period_ends_at: subscription
+            .and_then(|subscription| subscription.current_period_end)
+            .or(user.plan_expires_at),
+    }))
 }
 
 #[server(client = ServFnClient)]
@@ -151,13 +283,25 @@ pub async fn get_file(id: Uuid) -> ServFnResult<Option<FilePresenter>> {
 
     let user = extract_user().await?.unwrap();
 
-    let result = commands::get_file_by_id(id, Some(&user)).await;
-
-    Ok(if let Ok(file) = result {
-        Some(file.async_into().await)
-    } else {
-        None
-    })
+    let result = commands::get_file_by_id(id, None).await;
+
+    Ok(
+        if let Ok(file) = result
+            && file.is_visible_to(&user)
+        {
+            let mut file_presenter: FilePresenter = file.async_into().await;
+
+            // The folders and stars of the owner are not shared along with the file.
+            if file.user_id != user.id {
+                file_presenter.is_starred = false;
+                file_presenter.parent_folders.clear();
+            }
+
+            Some(file_presenter)
+        } else {
+            None
+        },
+    )
 }
 
 #[server(client = ServFnClient)]
@@ -175,11 +319,74 @@ pub async fn get_folder(id: Uuid) -> ServFnResult<Option<FolderPresenter>> {
     })
 }
 
+#[server(client = ServFnClient)]
+pub async fn get_folder_item_by_path(path: String) -> ServFnResult<Option<FolderItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    let result = commands::get_folder_item_by_path(&user, &path).await;
+
+    Ok(if let Ok(folder_item) = result {
+        Some(folder_item.async_into().await)
+    } else {
+        None
+    })
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_promotion_code_discount(code: String) -> ServFnResult<Option<String>> {
+    require_login().await?;
+
+    let result = commands::get_redeemable_promotion_code(&code).await;
+
+    Ok(result.ok().map(|promotion_code| promotion_code.discount()))
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_storage_usage() -> ServFnResult<Vec<StorageUsagePresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let root_items = commands::get_all_folder_items(Some(&user), None, &FolderItemsQuery::default())
+        .await
+        .expect("Could not get folder items");
+    let root_files_byte_size = commands::get_root_files_byte_size(&user)
+        .await
+        .expect("Could not get root files size");
+
+    let mut storage_usage = Vec::new();
+
+    for folder_item in root_items.iter().filter(|folder_item| !folder_item.is_file) {
+        let stats = Folder::from(folder_item).stats().await;
+
+        storage_usage.push(StorageUsagePresenter::new(
+            Some(folder_item.id),
+            &folder_item.name,
+            stats.byte_size(),
+        ));
+    }
+
+    storage_usage.push(StorageUsagePresenter::new(None, "Files", root_files_byte_size));
+
+    let listed_bytes = storage_usage.iter().map(|usage| usage.byte_size_bytes).sum::<u64>();
+
+    // Anything not reachable from the root folders is either trashed or nested inside a trashed folder.
+    storage_usage.push(StorageUsagePresenter::trash(ByteSize(
+        user.used_space().await.as_u64().saturating_sub(listed_bytes),
+    )));
+
+    storage_usage.retain(|usage| usage.byte_size_bytes > 0);
+    storage_usage.sort_by(|a, b| b.byte_size_bytes.cmp(&a.byte_size_bytes));
+
+    Ok(storage_usage)
+}
+
 #[server(client = ServFnClient)]
 pub async fn get_all_available_plans() -> ServFnResult<Vec<PlanPresenter>> {
     require_login().await?;
 
-    Ok(commands::get_all_plans()
+    Ok(commands::get_all_plans(false)
         .await
         .expect("Could not get plans")
         .iter()
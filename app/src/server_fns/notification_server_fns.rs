@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+
+use sdk::serv_fn::{ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::ServFnError;
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::NotificationPresenter;
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_mark_notifications_as_read() -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    commands::mark_notifications_as_read(&user)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_notifications() -> ServFnResult<Vec<NotificationPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let notifications = commands::get_all_notifications(&user)
+        .await
+        .expect("Could not get notifications");
+
+    Ok(futures::future::join_all(notifications.iter().map(|notification| notification.async_into())).await)
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_unread_notifications_count() -> ServFnResult<i64> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    Ok(commands::get_unread_notifications_count(&user)
+        .await
+        .unwrap_or_default())
+}
@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::serv_fn::{FormResult, ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use serde_json::Value;
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::{FormError, FormSuccess, ServFnError};
+
+use drive_core::inputs::{FolderItemsQuery, SmartFolderInput};
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::{FolderItemPresenter, PagePresenter, SmartFolderPresenter};
+
+#[cfg(feature = "server")]
+use crate::constants::FOLDER_ITEMS_PAGE_SIZE;
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_create_smart_folder(input: SmartFolderInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+
+    let result = commands::insert_smart_folder(&user, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Smart folder created successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to create smart folder", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_smart_folder(id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let smart_folder = commands::get_smart_folder_by_id(id, &user)
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::delete_smart_folder(&smart_folder)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_update_smart_folder(id: Uuid, input: SmartFolderInput) -> FormResult {
+    require_login().await.map_err(FormError::from)?;
+
+    let user = extract_user().await.map_err(FormError::from)?.unwrap();
+    let smart_folder = commands::get_smart_folder_by_id(id, &user)
+        .await
+        .map_err(|_| FormError::new("Failed to update smart folder", None))?;
+
+    let result = commands::update_smart_folder(&smart_folder, &input).await;
+
+    match result {
+        Ok(_) => Ok(FormSuccess::new("Smart folder updated successfully", Value::Null)),
+        Err(errors) => Err(FormError::new("Failed to update smart folder", Some(errors)).into()),
+    }
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_smart_folder_items(
+    id: Uuid,
+    mut query: FolderItemsQuery,
+) -> ServFnResult<PagePresenter<FolderItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let smart_folder = commands::get_smart_folder_by_id(id, &user)
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let limit = query
+        .limit
+        .unwrap_or(FOLDER_ITEMS_PAGE_SIZE)
+        .min(FOLDER_ITEMS_PAGE_SIZE);
+
+    query.limit = Some(limit);
+
+    let folder_items = commands::get_all_smart_folder_items(&smart_folder, &query)
+        .await
+        .expect("Could not get smart folder items");
+    let next_cursor = if folder_items.len() == limit as usize {
+        folder_items.last().map(|folder_item| folder_item.id)
+    } else {
+        None
+    };
+
+    Ok(PagePresenter {
+        items: futures::future::join_all(folder_items.iter().map(|folder_item| folder_item.async_into())).await,
+        next_cursor,
+    })
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_smart_folders() -> ServFnResult<Vec<SmartFolderPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    Ok(commands::get_all_smart_folders(&user)
+        .await
+        .expect("Could not get smart folders")
+        .iter()
+        .map(|smart_folder| smart_folder.into())
+        .collect())
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_smart_folder(id: Uuid) -> ServFnResult<Option<SmartFolderPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    let result = commands::get_smart_folder_by_id(id, &user).await;
+
+    Ok(result.ok().map(|smart_folder| (&smart_folder).into()))
+}
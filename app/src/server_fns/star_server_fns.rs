@@ -0,0 +1,94 @@
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use sdk::serv_fn::{ServFnClient, ServFnResult};
+
+#[cfg(feature = "server")]
+use sdk::serv_fn::ServFnError;
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::FolderItemPresenter;
+
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_star_file(file_id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::star_file(&user, &file)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_star_folder(folder_id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::star_folder(&user, &folder)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_unstar_file(file_id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::unstar_file(&user, &file)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_unstar_folder(folder_id: Uuid) -> ServFnResult {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    commands::unstar_folder(&user, &folder)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_all_starred_items() -> ServFnResult<Vec<FolderItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let starred_items = commands::get_all_starred_items(&user)
+        .await
+        .expect("Could not get starred items");
+
+    Ok(futures::future::join_all(starred_items.iter().map(|starred_item| starred_item.async_into())).await)
+}
@@ -0,0 +1,60 @@
+use dioxus::prelude::*;
+
+use sdk::serv_fn::{ServFnClient, ServFnResult};
+
+use drive_core::enums::TimelineGrouping;
+use drive_core::inputs::TimelineQuery;
+
+#[cfg(feature = "server")]
+use drive_core::server::commands;
+
+use crate::presenters::{PagePresenter, TimelineGroupPresenter, TimelineItemPresenter};
+
+#[cfg(feature = "server")]
+use crate::constants::FOLDER_ITEMS_PAGE_SIZE;
+#[cfg(feature = "server")]
+use crate::presenters::AsyncInto;
+
+#[cfg(feature = "server")]
+use super::{extract_user, require_login};
+
+#[server(client = ServFnClient)]
+pub async fn get_all_timeline_items(mut query: TimelineQuery) -> ServFnResult<PagePresenter<TimelineItemPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let limit = query
+        .limit
+        .unwrap_or(FOLDER_ITEMS_PAGE_SIZE)
+        .min(FOLDER_ITEMS_PAGE_SIZE);
+
+    query.limit = Some(limit);
+
+    let timeline_items = commands::get_all_timeline_items(&user, &query)
+        .await
+        .expect("Could not get timeline items");
+    let next_cursor = if timeline_items.len() == limit as usize {
+        timeline_items.last().map(|timeline_item| timeline_item.id)
+    } else {
+        None
+    };
+
+    Ok(PagePresenter {
+        items: futures::future::join_all(timeline_items.iter().map(|timeline_item| timeline_item.async_into())).await,
+        next_cursor,
+    })
+}
+
+#[server(client = ServFnClient)]
+pub async fn get_timeline_groups(grouping: TimelineGrouping) -> ServFnResult<Vec<TimelineGroupPresenter>> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+
+    Ok(commands::get_timeline_groups(&user, grouping)
+        .await
+        .expect("Could not get timeline groups")
+        .iter()
+        .map(|timeline_group| timeline_group.into())
+        .collect())
+}
@@ -3,27 +3,66 @@ use uuid::Uuid;
 
 use sdk::serv_fn::{ServFnClient, ServFnResult};
 
+use drive_core::enums::ConflictPolicy;
+
 #[cfg(feature = "server")]
 use sdk::serv_fn::ServFnError;
 
 #[cfg(feature = "server")]
 use drive_core::server::commands;
 
-use crate::presenters::FolderItemPresenter;
-
-#[cfg(feature = "server")]
-use crate::presenters::AsyncInto;
+use crate::presenters::TrashItemPresenter;
 
 #[cfg(feature = "server")]
 use super::{extract_user, require_login};
 
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_file(file_id: Uuid) -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    if file.trashed_at.is_none() {
+        return Err(ServFnError::bad_request().into());
+    }
+
+    commands::delete_file(&file)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_delete_folder(folder_id: Uuid) -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+
+    if folder.trashed_at.is_none() {
+        return Err(ServFnError::bad_request().into());
+    }
+
+    commands::delete_folder(&folder)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
 #[server(client = ServFnClient)]
 pub async fn attempt_to_empty_trash() -> ServFnResult<()> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
 
-    drive_core::server::commands::empty_trash(&user)
+    commands::empty_trash(&user)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -35,11 +74,11 @@ pub async fn attempt_to_move_file_to_trash(file_id: Uuid) -> ServFnResult<()> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
-    let file = drive_core::server::commands::get_file_by_id(file_id, Some(&user))
+    let file = commands::get_file_by_id(file_id, Some(&user))
         .await
         .map_err(|_| ServFnError::not_found())?;
 
-    drive_core::server::commands::move_file_to_trash(&file)
+    commands::move_file_to_trash(&file)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -51,11 +90,11 @@ pub async fn attempt_to_move_folder_to_trash(folder_id: Uuid) -> ServFnResult<()
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
-    let folder = drive_core::server::commands::get_folder_by_id(folder_id, Some(&user))
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
         .await
         .map_err(|_| ServFnError::not_found())?;
 
-    drive_core::server::commands::move_folder_to_trash(&folder)
+    commands::move_folder_to_trash(&folder)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -63,15 +102,44 @@ pub async fn attempt_to_move_folder_to_trash(folder_id: Uuid) -> ServFnResult<()
 }
 
 #[server(client = ServFnClient)]
-pub async fn attempt_to_restore_file(file_id: Uuid) -> ServFnResult<()> {
+pub async fn attempt_to_restore_file(file_id: Uuid, policy: ConflictPolicy) -> ServFnResult<()> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
-    let file = drive_core::server::commands::get_file_by_id(file_id, Some(&user))
+    let file = commands::get_file_by_id(file_id, Some(&user))
         .await
         .map_err(|_| ServFnError::not_found())?;
 
-    drive_core::server::commands::restore_file(&file)
+    commands::restore_file(&file, policy)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_restore_file_to_folder(
+    file_id: Uuid,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let file = commands::get_file_by_id(file_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let target_folder = if let Some(target_folder_id) = target_folder_id {
+        Some(
+            commands::get_folder_by_id(target_folder_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
+
+    commands::restore_file_to_folder(&file, target_folder.as_ref(), policy)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -79,15 +147,44 @@ pub async fn attempt_to_restore_file(file_id: Uuid) -> ServFnResult<()> {
 }
 
 #[server(client = ServFnClient)]
-pub async fn attempt_to_restore_folder(folder_id: Uuid) -> ServFnResult<()> {
+pub async fn attempt_to_restore_folder(folder_id: Uuid, policy: ConflictPolicy) -> ServFnResult<()> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
-    let folder = drive_core::server::commands::get_folder_by_id(folder_id, Some(&user))
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
         .await
         .map_err(|_| ServFnError::not_found())?;
 
-    drive_core::server::commands::restore_folder(&folder)
+    commands::restore_folder(&folder, policy)
+        .await
+        .map_err(|_| ServFnError::bad_request())?;
+
+    Ok(())
+}
+
+#[server(client = ServFnClient)]
+pub async fn attempt_to_restore_folder_to_folder(
+    folder_id: Uuid,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> ServFnResult<()> {
+    require_login().await?;
+
+    let user = extract_user().await?.unwrap();
+    let folder = commands::get_folder_by_id(folder_id, Some(&user))
+        .await
+        .map_err(|_| ServFnError::not_found())?;
+    let target_folder = if let Some(target_folder_id) = target_folder_id {
+        Some(
+            commands::get_folder_by_id(target_folder_id, Some(&user))
+                .await
+                .map_err(|_| ServFnError::bad_request())?,
+        )
+    } else {
+        None
+    };
+
+    commands::restore_folder_to_folder(&folder, target_folder.as_ref(), policy)
         .await
         .map_err(|_| ServFnError::bad_request())?;
 
@@ -95,13 +192,19 @@ pub async fn attempt_to_restore_folder(folder_id: Uuid) -> ServFnResult<()> {
 }
 
 #[server(client = ServFnClient)]
-pub async fn get_all_trash_items() -> ServFnResult<Vec<FolderItemPresenter>> {
+pub async fn get_all_trash_items() -> ServFnResult<Vec<TrashItemPresenter>> {
     require_login().await?;
 
     let user = extract_user().await?.unwrap();
     let trash_items = commands::get_all_trash_items(&user)
         .await
         .expect("Could not get trash items");
-
-    Ok(futures::future::join_all(trash_items.iter().map(|trash_item| trash_item.async_into())).await)
+    let trash_retention_days = user.entitlements().await.trash_retention_days;
+
+    Ok(futures::future::join_all(
+        trash_items
+            .iter()
+            .map(|trash_item| TrashItemPresenter::new(trash_item, trash_retention_days)),
+    )
+    .await)
 }
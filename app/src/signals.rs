@@ -2,4 +2,6 @@ use dioxus::signals::GlobalSignal;
 
 use crate::presenters::FolderItemPresenter;
 
+pub static COPY_FOLDER_ITEM: GlobalSignal<Option<FolderItemPresenter>> = GlobalSignal::new(|| None);
 pub static MOVE_FOLDER_ITEM: GlobalSignal<Option<FolderItemPresenter>> = GlobalSignal::new(|| None);
+pub static MOVE_FOLDER_ITEMS: GlobalSignal<Vec<FolderItemPresenter>> = GlobalSignal::new(Vec::new);
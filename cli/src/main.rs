@@ -1,6 +1,9 @@
+use bytesize::ByteSize;
+use chrono::{TimeDelta, Utc};
 use clap::{Arg, Command, value_parser};
 
 use drive_core::server::commands::*;
+use drive_core::server::models::Entitlements;
 
 #[cfg(feature = "test-utils")]
 use drive_core::test_utils::insert_test_session;
@@ -8,19 +11,45 @@ use drive_core::test_utils::insert_test_session;
 use serde_json::to_string_pretty;
 use uuid::Uuid;
 
+const ARG_ALLOWED_MEDIA_TYPES: &str = "allowed-media-types";
+const ARG_AMOUNT_OFF_CENTS: &str = "amount-off-cents";
+const ARG_CODE: &str = "code";
+const ARG_CURRENCY: &str = "currency";
 const ARG_DESCRIPTION: &str = "description";
+const ARG_EXPIRES_IN_DAYS: &str = "expires-in-days";
+const ARG_GRANT_ID: &str = "grant-id";
+const ARG_MAX_FILE_SIZE_GIB: &str = "max-file-size-gib";
 const ARG_MONTHLY_PRICE_CENTS: &str = "monthly-price-cents";
 const ARG_NAME: &str = "name";
+const ARG_PATH: &str = "path";
+const ARG_PERCENT_OFF: &str = "percent-off";
 const ARG_PLAN_ID: &str = "plan-id";
 const ARG_QUOTA_GIB: &str = "quota-gib";
+const ARG_REASON: &str = "reason";
+const ARG_SIZE_GIB: &str = "size-gib";
+const ARG_TRASH_RETENTION_DAYS: &str = "trash-retention-days";
+const ARG_TRIAL_DAYS: &str = "trial-days";
+const ARG_USAGE_LIMIT: &str = "usage-limit";
 const ARG_USERNAME: &str = "username";
 const ARG_YEARLY_PRICE_CENTS: &str = "yearly-price-cents";
 
+const COMMAND_ARCHIVE_PLAN: &str = "archive-plan";
 const COMMAND_CREATE_PLAN: &str = "create-plan";
+const COMMAND_CREATE_PROMOTION_CODE: &str = "create-promotion-code";
 const COMMAND_DISABLE_USER: &str = "disable-user";
 const COMMAND_ENABLE_USER: &str = "enable-user";
+const COMMAND_ENFORCE_OVER_QUOTA: &str = "enforce-over-quota";
+const COMMAND_EXPIRE_PLANS: &str = "expire-plans";
+const COMMAND_GRANT_STORAGE: &str = "grant-storage";
+const COMMAND_LIST_GRANTS: &str = "list-grants";
 const COMMAND_LIST_PLANS: &str = "list-plans";
+const COMMAND_LIST_PROMOTION_CODES: &str = "list-promotion-codes";
+const COMMAND_PURGE_TRASH: &str = "purge-trash";
+const COMMAND_RESOLVE_PATH: &str = "resolve-path";
+const COMMAND_REVOKE_GRANT: &str = "revoke-grant";
 const COMMAND_SET_USER_PLAN: &str = "set-user-plan";
+const COMMAND_SHOW_PLAN: &str = "show-plan";
+const COMMAND_UPDATE_PLAN: &str = "update-plan";
 
 #[cfg(feature = "test-utils")]
 const COMMAND_CREATE_TEST_SESSION: &str = "create-test-session";
@@ -31,40 +60,107 @@ async fn main() {
         .short('u')
         .long("username")
         .value_parser(value_parser!(String));
+    let arg_plan_id = Arg::new(ARG_PLAN_ID)
+        .short('p')
+        .long(ARG_PLAN_ID)
+        .value_parser(value_parser!(Uuid));
+    let arg_name = Arg::new(ARG_NAME)
+        .short('n')
+        .long(ARG_NAME)
+        .value_parser(value_parser!(String));
+    let arg_description = Arg::new(ARG_DESCRIPTION)
+        .short('d')
+        .long(ARG_DESCRIPTION)
+        .value_parser(value_parser!(String));
+    let arg_quota_gib = Arg::new(ARG_QUOTA_GIB)
+        .short('q')
+        .long(ARG_QUOTA_GIB)
+        .value_parser(value_parser!(u16));
+    let arg_max_file_size_gib = Arg::new(ARG_MAX_FILE_SIZE_GIB)
+        .short('s')
+        .long(ARG_MAX_FILE_SIZE_GIB)
+        .value_parser(value_parser!(u16));
+    let arg_allowed_media_types = Arg::new(ARG_ALLOWED_MEDIA_TYPES)
+        .short('a')
+        .long(ARG_ALLOWED_MEDIA_TYPES)
+        .value_delimiter(',')
+        .value_parser(value_parser!(String));
+    let arg_trash_retention_days = Arg::new(ARG_TRASH_RETENTION_DAYS)
+        .short('t')
+        .long(ARG_TRASH_RETENTION_DAYS)
+        .value_parser(value_parser!(u16));
+    let arg_trial_days = Arg::new(ARG_TRIAL_DAYS)
+        .short('r')
+        .long(ARG_TRIAL_DAYS)
+        .value_parser(value_parser!(u16));
+    let arg_currency = Arg::new(ARG_CURRENCY)
+        .short('c')
+        .long(ARG_CURRENCY)
+        .value_parser(value_parser!(String));
     let version = env!("CARGO_PKG_VERSION");
     let command = Command::new("Mango³ CLI")
         .version(version)
+        .subcommand(
+            Command::new(COMMAND_ARCHIVE_PLAN)
+                .version(version)
+                .arg(arg_plan_id.clone()),
+        )
         .subcommand(
             Command::new(COMMAND_CREATE_PLAN)
                 .version(version)
+                .arg(arg_name.clone())
+                .arg(arg_description.clone())
+                .arg(arg_quota_gib.clone())
                 .arg(
-                    Arg::new(ARG_NAME)
-                        .short('n')
-                        .long(ARG_NAME)
-                        .value_parser(value_parser!(String)),
+                    Arg::new(ARG_MONTHLY_PRICE_CENTS)
+                        .short('m')
+                        .long(ARG_MONTHLY_PRICE_CENTS)
+                        .value_parser(value_parser!(u32)),
                 )
                 .arg(
-                    Arg::new(ARG_DESCRIPTION)
-                        .short('d')
-                        .long(ARG_DESCRIPTION)
+                    Arg::new(ARG_YEARLY_PRICE_CENTS)
+                        .short('y')
+                        .long(ARG_YEARLY_PRICE_CENTS)
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(arg_currency.clone().default_value("usd"))
+                .arg(arg_max_file_size_gib.clone().default_value("1"))
+                .arg(arg_allowed_media_types.clone())
+                .arg(arg_trash_retention_days.clone().default_value("90"))
+                .arg(arg_trial_days.clone().default_value("0")),
+        )
+        .subcommand(
+            Command::new(COMMAND_CREATE_PROMOTION_CODE)
+                .version(version)
+                .arg(
+                    Arg::new(ARG_CODE)
+                        .short('o')
+                        .long(ARG_CODE)
                         .value_parser(value_parser!(String)),
                 )
                 .arg(
-                    Arg::new(ARG_QUOTA_GIB)
-                        .short('q')
-                        .long(ARG_QUOTA_GIB)
+                    Arg::new(ARG_PERCENT_OFF)
+                        .short('p')
+                        .long(ARG_PERCENT_OFF)
                         .value_parser(value_parser!(u8)),
                 )
                 .arg(
-                    Arg::new(ARG_MONTHLY_PRICE_CENTS)
+                    Arg::new(ARG_AMOUNT_OFF_CENTS)
                         .short('m')
-                        .long(ARG_MONTHLY_PRICE_CENTS)
-                        .value_parser(value_parser!(u8)),
+                        .long(ARG_AMOUNT_OFF_CENTS)
+                        .value_parser(value_parser!(u32)),
                 )
+                .arg(arg_currency.clone())
                 .arg(
-                    Arg::new(ARG_YEARLY_PRICE_CENTS)
-                        .short('y')
-                        .long(ARG_YEARLY_PRICE_CENTS)
+                    Arg::new(ARG_USAGE_LIMIT)
+                        .short('l')
+                        .long(ARG_USAGE_LIMIT)
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new(ARG_EXPIRES_IN_DAYS)
+                        .short('e')
+                        .long(ARG_EXPIRES_IN_DAYS)
                         .value_parser(value_parser!(u16)),
                 ),
         )
@@ -78,17 +174,81 @@ async fn main() {
                 .version(version)
                 .arg(arg_username.clone()),
         )
+        .subcommand(Command::new(COMMAND_ENFORCE_OVER_QUOTA).version(version))
+        .subcommand(Command::new(COMMAND_EXPIRE_PLANS).version(version))
+        .subcommand(
+            Command::new(COMMAND_GRANT_STORAGE)
+                .version(version)
+                .arg(arg_username.clone())
+                .arg(
+                    Arg::new(ARG_SIZE_GIB)
+                        .short('s')
+                        .long(ARG_SIZE_GIB)
+                        .value_parser(value_parser!(u32)),
+                )
+                .arg(
+                    Arg::new(ARG_REASON)
+                        .short('r')
+                        .long(ARG_REASON)
+                        .value_parser(value_parser!(String))
+                        .default_value(""),
+                )
+                .arg(
+                    Arg::new(ARG_EXPIRES_IN_DAYS)
+                        .short('e')
+                        .long(ARG_EXPIRES_IN_DAYS)
+                        .value_parser(value_parser!(u16)),
+                ),
+        )
+        .subcommand(
+            Command::new(COMMAND_LIST_GRANTS)
+                .version(version)
+                .arg(arg_username.clone()),
+        )
         .subcommand(Command::new(COMMAND_LIST_PLANS).version(version))
+        .subcommand(Command::new(COMMAND_LIST_PROMOTION_CODES).version(version))
+        .subcommand(Command::new(COMMAND_PURGE_TRASH).version(version))
         .subcommand(
-            Command::new(COMMAND_SET_USER_PLAN)
+            Command::new(COMMAND_RESOLVE_PATH)
                 .version(version)
                 .arg(arg_username.clone())
                 .arg(
-                    Arg::new(ARG_PLAN_ID)
+                    Arg::new(ARG_PATH)
                         .short('p')
-                        .long(ARG_PLAN_ID)
-                        .value_parser(value_parser!(Uuid)),
+                        .long(ARG_PATH)
+                        .value_parser(value_parser!(String)),
                 ),
+        )
+        .subcommand(
+            Command::new(COMMAND_REVOKE_GRANT).version(version).arg(
+                Arg::new(ARG_GRANT_ID)
+                    .short('g')
+                    .long(ARG_GRANT_ID)
+                    .value_parser(value_parser!(Uuid)),
+            ),
+        )
+        .subcommand(
+            Command::new(COMMAND_SET_USER_PLAN)
+                .version(version)
+                .arg(arg_username.clone())
+                .arg(arg_plan_id.clone()),
+        )
+        .subcommand(
+            Command::new(COMMAND_SHOW_PLAN)
+                .version(version)
+                .arg(arg_plan_id.clone()),
+        )
+        .subcommand(
+            Command::new(COMMAND_UPDATE_PLAN)
+                .version(version)
+                .arg(arg_plan_id.clone())
+                .arg(arg_name.clone())
+                .arg(arg_description.clone())
+                .arg(arg_quota_gib.clone())
+                .arg(arg_max_file_size_gib.clone())
+                .arg(arg_allowed_media_types.clone())
+                .arg(arg_trash_retention_days.clone())
+                .arg(arg_trial_days.clone()),
         );
 
     #[cfg(feature = "test-utils")]
@@ -97,6 +257,20 @@ async fn main() {
     let matches = command.get_matches();
 
     match matches.subcommand() {
+        Some((COMMAND_ARCHIVE_PLAN, matches)) => {
+            let plan_id = matches
+                .get_one::<Uuid>(ARG_PLAN_ID)
+                .expect("argument plan-id is missing");
+
+            let plan = get_plan_by_id(*plan_id).await.expect("Could not get plan");
+
+            let result = archive_plan(&plan).await;
+
+            match result {
+                Ok(_) => println!("Plan archived successfully."),
+                _ => println!("Failed to archive plan."),
+            }
+        }
         Some((COMMAND_CREATE_PLAN, matches)) => {
             let name = matches
                 .get_one::<String>(ARG_NAME)
@@ -107,25 +281,83 @@ async fn main() {
                 .cloned()
                 .expect("Could not get argument description");
             let quota_gib = matches
-                .get_one::<u8>(ARG_QUOTA_GIB)
+                .get_one::<u16>(ARG_QUOTA_GIB)
                 .cloned()
                 .expect("Could not get argument quota-gib");
             let monthly_price_cents = matches
-                .get_one::<u8>(ARG_MONTHLY_PRICE_CENTS)
+                .get_one::<u32>(ARG_MONTHLY_PRICE_CENTS)
                 .cloned()
                 .expect("Could not get argument monthly-price-cents");
             let yearly_price_cents = matches
-                .get_one::<u16>(ARG_YEARLY_PRICE_CENTS)
+                .get_one::<u32>(ARG_YEARLY_PRICE_CENTS)
                 .cloned()
                 .expect("Could not get argument yearly-price-cents");
+            let currency = matches
+                .get_one::<String>(ARG_CURRENCY)
+                .map(|currency| currency.to_lowercase())
+                .expect("Could not get argument currency");
+            let entitlements = Entitlements {
+                max_file_size_gib: matches
+                    .get_one::<u16>(ARG_MAX_FILE_SIZE_GIB)
+                    .cloned()
+                    .expect("Could not get argument max-file-size-gib"),
+                allowed_media_types: matches
+                    .get_many::<String>(ARG_ALLOWED_MEDIA_TYPES)
+                    .map(|media_types| media_types.cloned().collect())
+                    .unwrap_or_default(),
+                trash_retention_days: matches
+                    .get_one::<u16>(ARG_TRASH_RETENTION_DAYS)
+                    .cloned()
+                    .expect("Could not get argument trash-retention-days"),
+            };
+            let trial_days = matches
+                .get_one::<u16>(ARG_TRIAL_DAYS)
+                .cloned()
+                .expect("Could not get argument trial-days");
 
-            let result = insert_plan(&name, &description, quota_gib, monthly_price_cents, yearly_price_cents).await;
+            let result = insert_plan(
+                &name,
+                &description,
+                quota_gib,
+                monthly_price_cents,
+                yearly_price_cents,
+                &currency,
+                &entitlements,
+                trial_days,
+            )
+            .await;
 
             match result {
-                Ok(_) => println!("Plan created successfully."),
+                Ok(plan) => println!("Plan created successfully with ID {}.", plan.id),
                 Err(err) => println!("Failed to create plan.\n{err}"),
             }
         }
+        Some((COMMAND_CREATE_PROMOTION_CODE, matches)) => {
+            let code = matches
+                .get_one::<String>(ARG_CODE)
+                .expect("Could not get argument code");
+            let percent_off = matches.get_one::<u8>(ARG_PERCENT_OFF).cloned();
+            let currency = matches
+                .get_one::<String>(ARG_CURRENCY)
+                .map(|currency| currency.to_lowercase());
+            let amount_off = matches
+                .get_one::<u32>(ARG_AMOUNT_OFF_CENTS)
+                .map(|amount_off_cents| (*amount_off_cents, currency.as_deref().unwrap_or("usd")));
+            let usage_limit = matches.get_one::<u32>(ARG_USAGE_LIMIT).cloned();
+            let expires_at = matches
+                .get_one::<u16>(ARG_EXPIRES_IN_DAYS)
+                .map(|days| Utc::now() + TimeDelta::days(*days as i64));
+
+            let result = insert_promotion_code(code, percent_off, amount_off, usage_limit, expires_at).await;
+
+            match result {
+                Ok(promotion_code) => println!(
+                    "Promotion code {} created successfully with ID {}.",
+                    promotion_code.code, promotion_code.id
+                ),
+                Err(err) => println!("Failed to create promotion code.\n{err}"),
+            }
+        }
         Some((COMMAND_DISABLE_USER, matches)) => {
             let username = matches
                 .get_one::<String>(ARG_USERNAME)
@@ -150,14 +382,139 @@ async fn main() {
                 _ => println!("Failed to enable user."),
             }
         }
+        Some((COMMAND_ENFORCE_OVER_QUOTA, _)) => {
+            let result = enforce_over_quota_policy().await;
+
+            match result {
+                Ok(count) => println!("Applied over-quota policy to {count} users."),
+                Err(err) => println!("Failed to enforce over-quota policy.\n{err}"),
+            }
+        }
+        Some((COMMAND_EXPIRE_PLANS, _)) => {
+            match notify_expiring_plans().await {
+                Ok(count) => println!("Notified {count} users about expiring plans."),
+                Err(err) => println!("Failed to notify about expiring plans.\n{err}"),
+            }
+
+            match expire_plans().await {
+                Ok(count) => println!("Expired {count} plans."),
+                Err(err) => println!("Failed to expire plans.\n{err}"),
+            }
+        }
+        Some((COMMAND_GRANT_STORAGE, matches)) => {
+            let username = matches
+                .get_one::<String>(ARG_USERNAME)
+                .expect("argument username is missing");
+            let size_gib = matches
+                .get_one::<u32>(ARG_SIZE_GIB)
+                .cloned()
+                .expect("Could not get argument size-gib");
+            let reason = matches
+                .get_one::<String>(ARG_REASON)
+                .expect("Could not get argument reason");
+            let expires_at = matches
+                .get_one::<u16>(ARG_EXPIRES_IN_DAYS)
+                .map(|days| Utc::now() + TimeDelta::days(*days as i64));
+
+            let user = get_user_by_username(username).await.expect("Could not get user");
+
+            let result = insert_storage_grant(&user, ByteSize::gib(size_gib as u64), reason, expires_at).await;
+
+            match result {
+                Ok(storage_grant) => println!("Storage granted successfully with ID {}.", storage_grant.id),
+                Err(err) => println!("Failed to grant storage.\n{err}"),
+            }
+        }
+        Some((COMMAND_LIST_GRANTS, matches)) => {
+            let username = matches
+                .get_one::<String>(ARG_USERNAME)
+                .expect("argument username is missing");
+
+            let user = get_user_by_username(username).await.expect("Could not get user");
+
+            let result = get_all_storage_grants(&user).await;
+
+            match result {
+                Ok(storage_grants) => println!(
+                    "{}",
+                    to_string_pretty(&storage_grants).expect("Failed to serialize storage grants")
+                ),
+                _ => println!("Failed to get storage grants."),
+            }
+        }
         Some((COMMAND_LIST_PLANS, _)) => {
-            let result = get_all_plans().await;
+            let result = get_all_plans(true).await;
 
             match result {
                 Ok(plans) => println!("{}", to_string_pretty(&plans).expect("Failed to serialize plans")),
                 _ => println!("Failed to get plans."),
             }
         }
+        Some((COMMAND_LIST_PROMOTION_CODES, _)) => {
+            let result = get_all_promotion_codes().await;
+
+            match result {
+                Ok(promotion_codes) => {
+                    for promotion_code in promotion_codes {
+                        println!(
+                            "{} {} {} ({} redemptions)",
+                            promotion_code.id,
+                            promotion_code.code,
+                            promotion_code.discount(),
+                            promotion_code
+                                .redemptions_count()
+                                .await
+                                .expect("Could not get redemptions count")
+                        );
+                    }
+                }
+                _ => println!("Failed to get promotion codes."),
+            }
+        }
+        Some((COMMAND_PURGE_TRASH, _)) => {
+            let result = purge_expired_trash_items().await;
+
+            match result {
+                Ok(count) => println!("Purged {count} expired trash items."),
+                Err(err) => println!("Failed to purge trash.\n{err}"),
+            }
+        }
+        Some((COMMAND_RESOLVE_PATH, matches)) => {
+            let username = matches
+                .get_one::<String>(ARG_USERNAME)
+                .expect("argument username is missing");
+            let path = matches.get_one::<String>(ARG_PATH).expect("argument path is missing");
+
+            let user = get_user_by_username(username).await.expect("Could not get user");
+
+            match get_folder_item_by_path(&user, path).await {
+                Ok(folder_item) => {
+                    let canonical_path = get_folder_item_path(&folder_item)
+                        .await
+                        .expect("Could not get canonical path");
+                    let kind = if folder_item.is_file { "file" } else { "folder" };
+
+                    println!("{kind} {} {canonical_path}", folder_item.id);
+                }
+                _ => println!("Nothing found at path."),
+            }
+        }
+        Some((COMMAND_REVOKE_GRANT, matches)) => {
+            let grant_id = matches
+                .get_one::<Uuid>(ARG_GRANT_ID)
+                .expect("argument grant-id is missing");
+
+            let storage_grant = get_storage_grant_by_id(*grant_id)
+                .await
+                .expect("Could not get storage grant");
+
+            let result = revoke_storage_grant(&storage_grant).await;
+
+            match result {
+                Ok(_) => println!("Storage grant revoked successfully."),
+                _ => println!("Failed to revoke storage grant."),
+            }
+        }
         Some((COMMAND_SET_USER_PLAN, matches)) => {
             let username = matches
                 .get_one::<String>(ARG_USERNAME)
@@ -176,6 +533,56 @@ async fn main() {
                 _ => println!("Failed to update user plan."),
             }
         }
+        Some((COMMAND_SHOW_PLAN, matches)) => {
+            let plan_id = matches
+                .get_one::<Uuid>(ARG_PLAN_ID)
+                .expect("argument plan-id is missing");
+
+            let plan = get_plan_by_id(*plan_id).await.expect("Could not get plan");
+            let subscribers_count = get_plan_subscribers_count(&plan)
+                .await
+                .expect("Could not get subscribers count");
+
+            println!("{}", to_string_pretty(&plan).expect("Failed to serialize plan"));
+            println!("Monthly price: {}", plan.monthly_price());
+            println!("Yearly price: {}", plan.yearly_price());
+            println!("Active subscribers: {subscribers_count}");
+        }
+        Some((COMMAND_UPDATE_PLAN, matches)) => {
+            let plan_id = matches
+                .get_one::<Uuid>(ARG_PLAN_ID)
+                .expect("argument plan-id is missing");
+
+            let plan = get_plan_by_id(*plan_id).await.expect("Could not get plan");
+            let mut entitlements = plan.entitlements();
+
+            if let Some(max_file_size_gib) = matches.get_one::<u16>(ARG_MAX_FILE_SIZE_GIB) {
+                entitlements.max_file_size_gib = *max_file_size_gib;
+            }
+
+            if let Some(media_types) = matches.get_many::<String>(ARG_ALLOWED_MEDIA_TYPES) {
+                entitlements.allowed_media_types = media_types.cloned().collect();
+            }
+
+            if let Some(trash_retention_days) = matches.get_one::<u16>(ARG_TRASH_RETENTION_DAYS) {
+                entitlements.trash_retention_days = *trash_retention_days;
+            }
+
+            let result = update_plan(
+                &plan,
+                matches.get_one::<String>(ARG_NAME).map(String::as_str),
+                matches.get_one::<String>(ARG_DESCRIPTION).map(String::as_str),
+                matches.get_one::<u16>(ARG_QUOTA_GIB).cloned(),
+                Some(&entitlements),
+                matches.get_one::<u16>(ARG_TRIAL_DAYS).cloned(),
+            )
+            .await;
+
+            match result {
+                Ok(_) => println!("Plan updated successfully."),
+                Err(err) => println!("Failed to update plan.\n{err}"),
+            }
+        }
         #[cfg(feature = "test-utils")]
         Some((COMMAND_CREATE_TEST_SESSION, _)) => {
             let session = insert_test_session().await;
@@ -0,0 +1 @@
+pub const ERROR_NAME_CONFLICT: &str = "An item with the same name already exists";
@@ -1,8 +1,30 @@
 use std::fmt::Display;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    #[default]
+    Fail,
+    AutoRename,
+    Replace,
+    Merge,
+}
+
+impl Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictPolicy::Fail => write!(f, "fail"),
+            ConflictPolicy::AutoRename => write!(f, "auto_rename"),
+            ConflictPolicy::Replace => write!(f, "replace"),
+            ConflictPolicy::Merge => write!(f, "merge"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
 #[cfg_attr(feature = "server", derive(sqlx::Type, strum::EnumIter))]
 #[cfg_attr(feature = "server", sqlx(type_name = "file_visibility"))]
 #[cfg_attr(feature = "server", sqlx(rename_all = "lowercase"))]
@@ -24,3 +46,124 @@ impl Display for FileVisibility {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderItemsSortBy {
+    #[default]
+    Name,
+    Size,
+    CreatedAt,
+    UpdatedAt,
+    Type,
+}
+
+impl Display for FolderItemsSortBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FolderItemsSortBy::Name => write!(f, "name"),
+            FolderItemsSortBy::Size => write!(f, "size"),
+            FolderItemsSortBy::CreatedAt => write!(f, "created_at"),
+            FolderItemsSortBy::UpdatedAt => write!(f, "updated_at"),
+            FolderItemsSortBy::Type => write!(f, "type"),
+        }
+    }
+}
+
+impl FromStr for FolderItemsSortBy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "name" => Ok(FolderItemsSortBy::Name),
+            "size" => Ok(FolderItemsSortBy::Size),
+            "created_at" => Ok(FolderItemsSortBy::CreatedAt),
+            "updated_at" => Ok(FolderItemsSortBy::UpdatedAt),
+            "type" => Ok(FolderItemsSortBy::Type),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(type_name = "notification_kind"))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "snake_case"))]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    FileComment,
+    PlanExpiring,
+    PlanExpired,
+}
+
+impl Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationKind::FileComment => write!(f, "file_comment"),
+            NotificationKind::PlanExpiring => write!(f, "plan_expiring"),
+            NotificationKind::PlanExpired => write!(f, "plan_expired"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverQuotaPolicy {
+    #[default]
+    ReadOnly,
+    PurgeTrash,
+    Disable,
+}
+
+impl Display for OverQuotaPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverQuotaPolicy::ReadOnly => write!(f, "read_only"),
+            OverQuotaPolicy::PurgeTrash => write!(f, "purge_trash"),
+            OverQuotaPolicy::Disable => write!(f, "disable"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn reverse(&self) -> Self {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+}
+
+impl Display for SortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortDirection::Asc => write!(f, "asc"),
+            SortDirection::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineGrouping {
+    #[default]
+    Day,
+    Month,
+}
+
+impl Display for TimelineGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimelineGrouping::Day => write!(f, "day"),
+            TimelineGrouping::Month => write!(f, "month"),
+        }
+    }
+}
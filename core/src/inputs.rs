@@ -1,13 +1,55 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[cfg(feature = "server")]
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+#[cfg(feature = "server")]
+use sdk::constants::ERROR_IS_INVALID;
 
-use crate::enums::FileVisibility;
+use crate::enums::{ConflictPolicy, FileVisibility, FolderItemsSortBy, SortDirection};
 
 #[cfg(feature = "server")]
-use crate::server::constants::REGEX_FILE_NAME;
+use crate::server::constants::{
+    ITEM_PROPERTIES_LIMIT, ITEM_PROPERTY_KEY_MAX_LENGTH, ITEM_PROPERTY_VALUE_MAX_LENGTH, REGEX_FILE_NAME,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct AlbumInput {
+    #[cfg_attr(feature = "server", validate(length(min = 1, max = 256, message = "Can't be blank"),
+        regex(path = *REGEX_FILE_NAME, message = "Is invalid"),
+    ))]
+    pub name: String,
+    pub visibility: FileVisibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct FileCommentInput {
+    pub file_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    #[cfg_attr(
+        feature = "server",
+        validate(length(min = 1, max = 4096, message = "Can't be blank"))
+    )]
+    pub body: String,
+    pub region: Option<ImageRegion>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct FileCommentUpdateInput {
+    pub id: Uuid,
+    #[cfg_attr(
+        feature = "server",
+        validate(length(min = 1, max = 4096, message = "Can't be blank"))
+    )]
+    pub body: String,
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "server", derive(Validate))]
@@ -18,6 +60,8 @@ pub struct FileInput {
     ))]
     pub name: String,
     pub content: Vec<u8>,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -31,6 +75,91 @@ pub struct FolderInput {
     pub visibility: FileVisibility,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FolderItemIds {
+    pub file_ids: Vec<Uuid>,
+    pub folder_ids: Vec<Uuid>,
+}
+
+impl FolderItemIds {
+    pub fn is_empty(&self) -> bool {
+        self.file_ids.is_empty() && self.folder_ids.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.file_ids.len() + self.folder_ids.len()
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FolderItemsFilter {
+    pub name_pattern: Option<String>,
+    pub media_type: Option<String>,
+    pub created_after: Option<NaiveDate>,
+    pub created_before: Option<NaiveDate>,
+    pub min_byte_size: Option<u64>,
+    pub max_byte_size: Option<u64>,
+    pub visibility: Option<FileVisibility>,
+    pub metadata_pattern: Option<String>,
+    /// Property keys the item must all have, which is how items are tagged.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FolderItemsQuery {
+    pub sort_by: FolderItemsSortBy,
+    pub sort_direction: SortDirection,
+    pub folders_first: bool,
+    pub cursor: Option<Uuid>,
+    pub limit: Option<u8>,
+    pub filter: Option<FolderItemsFilter>,
+}
+
+impl Default for FolderItemsQuery {
+    fn default() -> Self {
+        Self {
+            sort_by: FolderItemsSortBy::default(),
+            sort_direction: SortDirection::default(),
+            folders_first: true,
+            cursor: None,
+            limit: None,
+            filter: None,
+        }
+    }
+}
+
+/// Coordinates are relative to the image size, so they don't depend on the displayed variant.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ImageRegion {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ImageRegion {
+    pub fn is_valid(&self) -> bool {
+        [self.x, self.y, self.width, self.height]
+            .iter()
+            .all(|value| (0.0..=1.0).contains(value))
+            && self.x + self.width <= 1.0
+            && self.y + self.height <= 1.0
+    }
+}
+
+pub type ItemProperties = BTreeMap<String, String>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct ItemMetadataInput {
+    pub id: Uuid,
+    #[cfg_attr(feature = "server", validate(length(max = 4096, message = "Is too long")))]
+    pub description: String,
+    #[cfg_attr(feature = "server", validate(custom(function = "validate_item_properties")))]
+    pub properties: ItemProperties,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "server", derive(Validate))]
 pub struct RenameInput {
@@ -40,3 +169,36 @@ pub struct RenameInput {
     ))]
     pub name: String,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct SmartFolderInput {
+    #[cfg_attr(feature = "server", validate(length(min = 1, max = 256, message = "Can't be blank"),
+        regex(path = *REGEX_FILE_NAME, message = "Is invalid"),
+    ))]
+    pub name: String,
+    pub filter: FolderItemsFilter,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TimelineQuery {
+    pub before: Option<NaiveDate>,
+    pub cursor: Option<Uuid>,
+    pub limit: Option<u8>,
+}
+
+#[cfg(feature = "server")]
+fn validate_item_properties(properties: &ItemProperties) -> Result<(), ValidationError> {
+    let is_valid = properties.len() <= ITEM_PROPERTIES_LIMIT
+        && properties.iter().all(|(key, value)| {
+            !key.trim().is_empty()
+                && key.chars().count() <= ITEM_PROPERTY_KEY_MAX_LENGTH
+                && value.chars().count() <= ITEM_PROPERTY_VALUE_MAX_LENGTH
+        });
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ERROR_IS_INVALID.clone())
+    }
+}
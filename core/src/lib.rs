@@ -1,3 +1,4 @@
+pub mod constants;
 pub mod enums;
 pub mod inputs;
 
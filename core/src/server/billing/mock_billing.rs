@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{TimeDelta, Utc};
+use futures::future::BoxFuture;
+use url::Url;
+use uuid::Uuid;
+
+use super::{
+    BillingCheckout, BillingCheckoutParams, BillingDiscountParams, BillingInterval, BillingOrder, BillingProductParams,
+    BillingProvider, BillingSubscription,
+};
+
+#[derive(Default)]
+pub struct MockBillingProvider {
+    checkouts: Mutex<HashMap<Uuid, BillingCheckout>>,
+    orders: Mutex<HashMap<String, Vec<BillingOrder>>>,
+    products: Mutex<HashMap<Uuid, MockProduct>>,
+    subscriptions: Mutex<HashMap<Uuid, BillingSubscription>>,
+}
+
+struct MockProduct {
+    interval: BillingInterval,
+    price_cents: u32,
+    currency: String,
+}
+
+impl MockBillingProvider {
+    fn period(&self, product_id: Uuid) -> TimeDelta {
+        // Products created by another process, like the CLI, are billed monthly.
+        match self.products.lock().unwrap().get(&product_id) {
+            Some(MockProduct {
+                interval: BillingInterval::Year,
+                ..
+            }) => TimeDelta::days(365),
+            _ => TimeDelta::days(30),
+        }
+    }
+
+    fn order(&self, product_id: Uuid) -> BillingOrder {
+        let products = self.products.lock().unwrap();
+        let product = products.get(&product_id);
+
+        BillingOrder {
+            id: Uuid::new_v4(),
+            product_id: Some(product_id),
+            amount_cents: product.map(|product| product.price_cents).unwrap_or_default(),
+            currency: product.map_or_else(|| "usd".to_owned(), |product| product.currency.clone()),
+            is_paid: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn subscription(&self, subscription_id: Uuid) -> anyhow::Result<BillingSubscription> {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .get(&subscription_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Subscription not found"))
+    }
+}
+
+impl BillingProvider for MockBillingProvider {
+    fn cancel_subscription_at_period_end(
+        &self,
+        subscription_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move { self.subscription(subscription_id) })
+    }
+
+    fn change_subscription_product(
+        &self,
+        subscription_id: Uuid,
+        product_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            let subscription = subscriptions
+                .get_mut(&subscription_id)
+                .ok_or_else(|| anyhow::anyhow!("Subscription not found"))?;
+
+            // Switching between monthly and yearly billing starts a new period.
+            if self.period(product_id) != self.period(subscription.product_id) {
+                subscription.current_period_end = Some(Utc::now() + self.period(product_id));
+            }
+
+            subscription.product_id = product_id;
+
+            Ok(subscription.clone())
+        })
+    }
+
+    fn create_checkout<'a>(
+        &'a self,
+        params: &'a BillingCheckoutParams<'a>,
+    ) -> BoxFuture<'a, anyhow::Result<BillingCheckout>> {
+        Box::pin(async move {
+            let id = Uuid::new_v4();
+            // A trial replaces the first period until the first payment.
+            let period = params
+                .trial_days
+                .map_or_else(|| self.period(params.product_id), |days| TimeDelta::days(days as i64));
+            let subscription = BillingSubscription {
+                id: Uuid::new_v4(),
+                product_id: params.product_id,
+                current_period_end: Some(Utc::now() + period),
+            };
+            let checkout = BillingCheckout {
+                id,
+                url: params
+                    .success_url
+                    .as_str()
+                    .replace("{CHECKOUT_ID}", &id.to_string())
+                    .parse()?,
+                is_succeeded: true,
+                external_customer_id: Some(params.external_customer_id.clone()),
+                product_id: params.product_id,
+                subscription_id: Some(subscription.id),
+                discount_id: params.discount_id,
+            };
+
+            // Trials are paid for at the end of the first period, so there's nothing to order yet.
+            if params.trial_days.is_none() {
+                let order = self.order(params.product_id);
+
+                self.orders
+                    .lock()
+                    .unwrap()
+                    .entry(params.external_customer_id.clone())
+                    .or_default()
+                    .insert(0, order);
+            }
+
+            self.subscriptions.lock().unwrap().insert(subscription.id, subscription);
+            self.checkouts.lock().unwrap().insert(id, checkout.clone());
+
+            Ok(checkout)
+        })
+    }
+
+    fn create_discount<'a>(&'a self, _params: &'a BillingDiscountParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>> {
+        Box::pin(async move { Ok(Uuid::new_v4()) })
+    }
+
+    fn create_product<'a>(&'a self, params: &'a BillingProductParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>> {
+        Box::pin(async move {
+            let id = Uuid::new_v4();
+
+            self.products.lock().unwrap().insert(
+                id,
+                MockProduct {
+                    interval: params.interval,
+                    price_cents: params.price_cents,
+                    currency: params.currency.to_owned(),
+                },
+            );
+
+            Ok(id)
+        })
+    }
+
+    fn get_checkout(&self, checkout_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingCheckout>> {
+        Box::pin(async move {
+            self.checkouts
+                .lock()
+                .unwrap()
+                .get(&checkout_id)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Checkout not found"))
+        })
+    }
+
+    fn get_customer_orders<'a>(
+        &'a self,
+        external_customer_id: &'a str,
+    ) -> BoxFuture<'a, anyhow::Result<Vec<BillingOrder>>> {
+        Box::pin(async move {
+            Ok(self
+                .orders
+                .lock()
+                .unwrap()
+                .get(external_customer_id)
+                .cloned()
+                .unwrap_or_default())
+        })
+    }
+
+    fn get_order_invoice_url(&self, order_id: Uuid) -> BoxFuture<'_, anyhow::Result<Url>> {
+        Box::pin(async move {
+            // There is no invoice to download, so the order ID is only kept to tell invoices apart.
+            Ok(format!("https://example.com/invoices/{order_id}.pdf").parse()?)
+        })
+    }
+
+    fn get_subscription(&self, subscription_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move { self.subscription(subscription_id) })
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use super::config::BILLING_CONFIG;
+
+mod mock_billing;
+mod polar_billing;
+
+pub use mock_billing::MockBillingProvider;
+pub use polar_billing::PolarBillingProvider;
+
+pub(crate) static BILLING_PROVIDER: LazyLock<Box<dyn BillingProvider>> =
+    LazyLock::new(|| match BILLING_CONFIG.provider {
+        BillingProviderKind::Mock => Box::new(MockBillingProvider::default()),
+        BillingProviderKind::Polar => Box::new(PolarBillingProvider::new()),
+    });
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BillingProviderKind {
+    Mock,
+    Polar,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BillingInterval {
+    Month,
+    Year,
+}
+
+#[derive(Clone, Debug)]
+pub struct BillingCheckout {
+    pub id: Uuid,
+    pub url: Url,
+    pub is_succeeded: bool,
+    pub external_customer_id: Option<String>,
+    pub product_id: Uuid,
+    pub subscription_id: Option<Uuid>,
+    pub discount_id: Option<Uuid>,
+}
+
+pub struct BillingCheckoutParams<'a> {
+    pub product_id: Uuid,
+    pub external_customer_id: String,
+    pub customer_name: &'a str,
+    pub customer_email: &'a str,
+    /// `{CHECKOUT_ID}` is replaced by the ID of the checkout.
+    pub success_url: Url,
+    pub trial_days: Option<u16>,
+    pub discount_id: Option<Uuid>,
+}
+
+pub struct BillingDiscountParams<'a> {
+    pub code: &'a str,
+    pub percent_off: Option<u8>,
+    pub amount_off_cents: Option<u32>,
+    pub currency: Option<&'a str>,
+    pub max_redemptions: Option<u32>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BillingOrder {
+    pub id: Uuid,
+    pub product_id: Option<Uuid>,
+    pub amount_cents: u32,
+    pub currency: String,
+    pub is_paid: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct BillingProductParams<'a> {
+    pub name: String,
+    pub description: &'a str,
+    pub interval: BillingInterval,
+    pub price_cents: u32,
+    pub currency: &'a str,
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BillingSubscription {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub current_period_end: Option<DateTime<Utc>>,
+}
+
+pub trait BillingProvider: Send + Sync {
+    fn cancel_subscription_at_period_end(
+        &self,
+        subscription_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>>;
+
+    fn change_subscription_product(
+        &self,
+        subscription_id: Uuid,
+        product_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>>;
+
+    fn create_checkout<'a>(
+        &'a self,
+        params: &'a BillingCheckoutParams<'a>,
+    ) -> BoxFuture<'a, anyhow::Result<BillingCheckout>>;
+
+    fn create_discount<'a>(&'a self, params: &'a BillingDiscountParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>>;
+
+    fn create_product<'a>(&'a self, params: &'a BillingProductParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>>;
+
+    fn get_checkout(&self, checkout_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingCheckout>>;
+
+    fn get_customer_orders<'a>(
+        &'a self,
+        external_customer_id: &'a str,
+    ) -> BoxFuture<'a, anyhow::Result<Vec<BillingOrder>>>;
+
+    fn get_order_invoice_url(&self, order_id: Uuid) -> BoxFuture<'_, anyhow::Result<Url>>;
+
+    fn get_subscription(&self, subscription_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingSubscription>>;
+}
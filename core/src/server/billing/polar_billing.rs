@@ -0,0 +1,250 @@
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use polar_rs::{
+    AmountType, CheckoutSession, CheckoutSessionParams, DiscountDuration, DiscountParams, DiscountType,
+    ListOrdersParams, Order, OrderSorting, Polar, PriceParams, ProductParams, ProrationBehavior, RecurringInterval,
+    Subscription, SubscriptionParams, TrialInterval,
+};
+use url::Url;
+use uuid::Uuid;
+
+use crate::server::config::POLAR_CONFIG;
+
+use super::{
+    BillingCheckout, BillingCheckoutParams, BillingDiscountParams, BillingInterval, BillingOrder, BillingProductParams,
+    BillingProvider, BillingSubscription,
+};
+
+pub struct PolarBillingProvider {
+    client: Polar,
+}
+
+impl PolarBillingProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Polar::new(POLAR_CONFIG.base_url.clone(), POLAR_CONFIG.access_token.clone())
+                .expect("Could not get Polar client."),
+        }
+    }
+}
+
+impl Default for PolarBillingProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<CheckoutSession> for BillingCheckout {
+    fn from(checkout: CheckoutSession) -> Self {
+        Self {
+            id: checkout.id,
+            url: checkout.url,
+            is_succeeded: checkout.status.is_succeeded(),
+            external_customer_id: checkout.external_customer_id,
+            product_id: checkout.product_id,
+            subscription_id: checkout.subscription_id,
+            discount_id: checkout.discount_id,
+        }
+    }
+}
+
+impl From<Order> for BillingOrder {
+    fn from(order: Order) -> Self {
+        Self {
+            id: order.id,
+            product_id: order.product_id,
+            amount_cents: order.total_amount,
+            currency: order.currency,
+            is_paid: order.paid,
+            created_at: order.created_at,
+        }
+    }
+}
+
+impl From<Subscription> for BillingSubscription {
+    fn from(subscription: Subscription) -> Self {
+        let current_period_end: Option<DateTime<Utc>> = subscription.current_period_end.into();
+
+        Self {
+            id: subscription.id,
+            product_id: subscription.product_id,
+            current_period_end,
+        }
+    }
+}
+
+impl BillingProvider for PolarBillingProvider {
+    fn cancel_subscription_at_period_end(
+        &self,
+        subscription_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move {
+            let subscription = self
+                .client
+                .update_subscription(
+                    subscription_id,
+                    &SubscriptionParams {
+                        cancel_at_period_end: Some(true),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            Ok(subscription.into())
+        })
+    }
+
+    fn change_subscription_product(
+        &self,
+        subscription_id: Uuid,
+        product_id: Uuid,
+    ) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move {
+            let subscription = self
+                .client
+                .update_subscription(
+                    subscription_id,
+                    &SubscriptionParams {
+                        product_id: Some(product_id),
+                        proration_behavior: Some(ProrationBehavior::Prorate),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+            Ok(subscription.into())
+        })
+    }
+
+    fn create_checkout<'a>(
+        &'a self,
+        params: &'a BillingCheckoutParams<'a>,
+    ) -> BoxFuture<'a, anyhow::Result<BillingCheckout>> {
+        Box::pin(async move {
+            let checkout = self
+                .client
+                .create_checkout_session(&CheckoutSessionParams {
+                    external_customer_id: Some(params.external_customer_id.clone()),
+                    customer_name: Some(params.customer_name.to_owned()),
+                    customer_email: Some(params.customer_email.to_owned()),
+                    products: vec![params.product_id],
+                    success_url: Some(params.success_url.clone()),
+                    trial_interval: params.trial_days.map(|_| TrialInterval::Day),
+                    trial_interval_count: params.trial_days.map(u32::from),
+                    discount_id: params.discount_id,
+                    allow_discount_codes: Some(false),
+                    ..Default::default()
+                })
+                .await?;
+
+            Ok(checkout.into())
+        })
+    }
+
+    fn create_discount<'a>(&'a self, params: &'a BillingDiscountParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>> {
+        Box::pin(async move {
+            let discount = self
+                .client
+                .create_discount(&DiscountParams {
+                    name: params.code.to_owned(),
+                    code: Some(params.code.to_owned()),
+                    discount_type: if params.percent_off.is_some() {
+                        DiscountType::Percentage
+                    } else {
+                        DiscountType::Fixed
+                    },
+                    // Polar expects percentages in basis points.
+                    basis_points: params.percent_off.map(|percent_off| percent_off as u32 * 100),
+                    amount: params.amount_off_cents,
+                    currency: params.currency.map(str::to_owned),
+                    duration: DiscountDuration::Once,
+                    max_redemptions: params.max_redemptions,
+                    ends_at: params.ends_at,
+                    ..Default::default()
+                })
+                .await?;
+
+            Ok(discount.id)
+        })
+    }
+
+    fn create_product<'a>(&'a self, params: &'a BillingProductParams<'a>) -> BoxFuture<'a, anyhow::Result<Uuid>> {
+        Box::pin(async move {
+            let product = self
+                .client
+                .create_product(&ProductParams {
+                    name: params.name.clone(),
+                    description: Some(params.description.to_owned()),
+                    recurring_interval: Some(match params.interval {
+                        BillingInterval::Month => RecurringInterval::Month,
+                        BillingInterval::Year => RecurringInterval::Year,
+                    }),
+                    prices: vec![PriceParams {
+                        amount_type: AmountType::Fixed,
+                        price_currency: Some(params.currency.to_owned()),
+                        price_amount: Some(params.price_cents),
+                        ..Default::default()
+                    }],
+                    metadata: params.metadata.clone(),
+                    ..Default::default()
+                })
+                .await?;
+
+            Ok(product.id)
+        })
+    }
+
+    fn get_checkout(&self, checkout_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingCheckout>> {
+        Box::pin(async move { Ok(self.client.get_checkout_session(checkout_id).await?.into()) })
+    }
+
+    fn get_customer_orders<'a>(
+        &'a self,
+        external_customer_id: &'a str,
+    ) -> BoxFuture<'a, anyhow::Result<Vec<BillingOrder>>> {
+        Box::pin(async move {
+            let mut orders = Vec::new();
+            let mut page = 1;
+
+            loop {
+                let orders_page = self
+                    .client
+                    .list_orders(&ListOrdersParams {
+                        external_customer_id: Some(vec![external_customer_id.to_owned()]),
+                        sorting: Some(vec![OrderSorting::CreatedAtDesc]),
+                        page: Some(page),
+                        limit: Some(100),
+                        ..Default::default()
+                    })
+                    .await?;
+
+                orders.extend(orders_page.items.into_iter().map(BillingOrder::from));
+
+                if page >= orders_page.pagination.max_page {
+                    break;
+                }
+
+                page += 1;
+            }
+
+            Ok(orders)
+        })
+    }
+
+    fn get_order_invoice_url(&self, order_id: Uuid) -> BoxFuture<'_, anyhow::Result<Url>> {
+        Box::pin(async move {
+            if let Ok(invoice) = self.client.get_order_invoice(order_id).await {
+                return Ok(invoice.url);
+            }
+
+            // Polar only generates invoices on request, in the background.
+            self.client.generate_order_invoice(order_id).await?;
+
+            Err(anyhow::anyhow!("Invoice is being generated, try again in a moment"))
+        })
+    }
+
+    fn get_subscription(&self, subscription_id: Uuid) -> BoxFuture<'_, anyhow::Result<BillingSubscription>> {
+        Box::pin(async move { Ok(self.client.get_subscription(subscription_id).await?.into()) })
+    }
+}
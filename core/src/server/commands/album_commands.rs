@@ -0,0 +1,425 @@
+use sqlx::types::Json;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+
+use sdk::constants::{ERROR_ALREADY_EXISTS, ERROR_IS_INVALID};
+
+use crate::enums::FileVisibility;
+use crate::inputs::{AlbumInput, ItemProperties};
+use crate::server::db_pool;
+use crate::server::models::{Album, File, FolderItem, User};
+
+pub async fn add_files_to_album(album: &Album<'_>, files: &[File<'_>]) -> anyhow::Result<()> {
+    if files
+        .iter()
+        .any(|file| file.user_id != album.user_id || file.trashed_at.is_some())
+    {
+        return Err(anyhow::anyhow!("Invalid file"));
+    }
+
+    if files.iter().any(|file| file.visibility < album.visibility) {
+        return Err(anyhow::anyhow!("File is less visible than the album"));
+    }
+
+    let db_pool = db_pool().await;
+    let file_ids = files.iter().map(|file| file.id).collect::<Vec<_>>();
+
+    sqlx::query!(
+        "INSERT INTO album_files (album_id, file_id, position)
+        SELECT
+            $1,
+            new_files.file_id,
+            (SELECT COALESCE(MAX(position), 0) FROM album_files WHERE album_id = $1) + new_files.position::integer
+        FROM unnest($2::uuid[]) WITH ORDINALITY AS new_files (file_id, position)
+        ON CONFLICT (album_id, file_id) DO NOTHING",
+        album.id,  // $1
+        &file_ids, // $2
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_album(album: &Album<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!("DELETE FROM albums WHERE id = $1", album.id)
+        .execute(db_pool)
+        .await
+        .map(|_| ())
+}
+
+pub async fn get_album_cover_file<'a>(album: &Album<'_>) -> sqlx::Result<File<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        File,
+        r#"SELECT
+            f.id,
+            f.user_id,
+            f.parent_folder_id,
+            f.name,
+            f.visibility as "visibility!: FileVisibility",
+            f.media_type,
+            f.byte_size,
+            f.md5_checksum,
+            f.description,
+            f.properties as "properties!: Json<ItemProperties>",
+            f.trashed_at,
+            f.created_at,
+            f.updated_at
+        FROM files AS f, album_files AS af
+        WHERE af.album_id = $1 AND af.file_id = f.id AND f.trashed_at IS NULL
+        ORDER BY f.id IS NOT DISTINCT FROM $2 DESC, af.position ASC
+        LIMIT 1"#,
+        album.id,            // $1
+        album.cover_file_id, // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_album_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Result<Album<'a>> {
+    let db_pool = db_pool().await;
+    let user_id = user.map(|u| u.id);
+
+    sqlx::query_as!(
+        Album,
+        r#"SELECT
+            id,
+            user_id,
+            cover_file_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            created_at,
+            updated_at
+        FROM albums WHERE id = $1 AND ($2::uuid IS NULL OR user_id = $2) LIMIT 1"#,
+        id,      // $1
+        user_id, // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_album_files_count(album: &Album<'_>) -> sqlx::Result<i64> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!" FROM album_files AS af, files AS f
+        WHERE af.album_id = $1 AND af.file_id = f.id AND f.trashed_at IS NULL"#,
+        album.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| row.count)
+}
+
+pub async fn get_all_album_files<'a>(album: &Album<'_>) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        FolderItem,
+        r#"SELECT
+            f.id,
+            TRUE as "is_file!",
+            f.user_id,
+            f.parent_folder_id,
+            f.name,
+            f.visibility as "visibility!: FileVisibility",
+            f.created_at,
+            f.updated_at
+        FROM files AS f, album_files AS af
+        WHERE af.album_id = $1 AND af.file_id = f.id AND f.trashed_at IS NULL
+        ORDER BY af.position ASC, af.created_at ASC"#,
+        album.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_all_albums<'a>(user: &User<'_>) -> sqlx::Result<Vec<Album<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Album,
+        r#"SELECT
+            id,
+            user_id,
+            cover_file_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            created_at,
+            updated_at
+        FROM albums WHERE user_id = $1 ORDER BY name ASC"#,
+        user.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn insert_album<'a>(user: &User<'_>, input: &AlbumInput) -> Result<Album<'a>, ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+
+    if album_name_exists(user, &input.name).await {
+        let mut validation_errors = ValidationErrors::new();
+
+        validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
+
+        return Err(validation_errors);
+    }
+
+    sqlx::query_as!(
+        Album,
+        r#"INSERT INTO albums (user_id, name, visibility) VALUES ($1, $2, $3)
+        RETURNING
+            id,
+            user_id,
+            cover_file_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            created_at,
+            updated_at"#,
+        user.id,               // $1
+        input.name,            // $2
+        input.visibility as _, // $3
+    )
+    .fetch_one(db_pool)
+    .await
+    .map_err(|_| ValidationErrors::new())
+}
+
+pub async fn remove_files_from_album(album: &Album<'_>, file_ids: &[Uuid]) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "DELETE FROM album_files WHERE album_id = $1 AND file_id = ANY($2)",
+        album.id, // $1
+        file_ids, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn reorder_album_files(album: &Album<'_>, file_ids: &[Uuid]) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "WITH ordered_files AS (
+            SELECT
+                af.id,
+                ROW_NUMBER() OVER (
+                    ORDER BY sorted_files.position ASC NULLS LAST, af.position ASC, af.created_at ASC
+                ) AS position
+            FROM album_files AS af
+            LEFT JOIN unnest($2::uuid[]) WITH ORDINALITY AS sorted_files (file_id, position)
+                ON sorted_files.file_id = af.file_id
+            WHERE af.album_id = $1
+        ) UPDATE album_files AS af SET position = ordered_files.position::integer
+        FROM ordered_files WHERE af.id = ordered_files.id",
+        album.id, // $1
+        file_ids, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn set_album_cover(album: &Album<'_>, file: Option<&File<'_>>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+    let file_id = file.map(|f| f.id);
+
+    let result = sqlx::query!(
+        "UPDATE albums SET cover_file_id = $2
+        WHERE id = $1
+            AND ($2::uuid IS NULL OR EXISTS (SELECT 1 FROM album_files WHERE album_id = $1 AND file_id = $2))",
+        album.id, // $1
+        file_id,  // $2
+    )
+    .execute(db_pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn update_album(album: &Album<'_>, input: &AlbumInput) -> Result<(), ValidationErrors> {
+    input.validate()?;
+
+    let mut validation_errors = ValidationErrors::new();
+    let db_pool = db_pool().await;
+
+    if input.name.to_lowercase() != album.name.to_lowercase()
+        && album_name_exists(&album.user().await, &input.name).await
+    {
+        validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
+    }
+
+    if input.visibility > album.visibility
+        && get_all_album_files(album)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .any(|file| file.visibility < input.visibility)
+    {
+        validation_errors.add("visibility", ERROR_IS_INVALID.clone());
+    }
+
+    if !validation_errors.is_empty() {
+        return Err(validation_errors);
+    }
+
+    sqlx::query!(
+        "UPDATE albums SET name = $2, visibility = $3 WHERE id = $1",
+        album.id,              // $1
+        input.name,            // $2
+        input.visibility as _, // $3
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+    .map_err(|_| ValidationErrors::new())
+}
+
+pub(super) async fn file_is_in_more_visible_album(file: &File<'_>, visibility: FileVisibility) -> sqlx::Result<bool> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM album_files AS af, albums AS a
+            WHERE af.file_id = $1 AND af.album_id = a.id AND a.visibility > $2
+        ) AS "exists!""#,
+        file.id,         // $1
+        visibility as _, // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| row.exists)
+}
+
+async fn album_name_exists(user: &User<'_>, name: &str) -> bool {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "SELECT id FROM albums WHERE user_id = $1 AND LOWER(name) = $2 LIMIT 1",
+        user.id,             // $1
+        name.to_lowercase()  // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::ConflictPolicy;
+    use crate::inputs::FolderItemIds;
+    use crate::server::commands::{batch_update_visibility, get_file_by_id, move_file_to_trash, restore_file};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    async fn insert_test_album<'a>(user: &User<'_>) -> Album<'a> {
+        let input = AlbumInput {
+            name: fake_name(),
+            visibility: FileVisibility::Private,
+        };
+
+        insert_album(user, &input).await.expect("Could not insert album")
+    }
+
+    #[tokio::test]
+    async fn should_add_and_reorder_album_files() {
+        let user = insert_test_user().await;
+        let album = insert_test_album(&user).await;
+        let files = insert_test_files(3, Some(&user)).await;
+
+        add_files_to_album(&album, &files).await.expect("Could not add files");
+        reorder_album_files(&album, &[files[2].id])
+            .await
+            .expect("Could not reorder files");
+
+        let album_files = get_all_album_files(&album).await.unwrap();
+        let ids = album_files.iter().map(|file| file.id).collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![files[2].id, files[0].id, files[1].id]);
+        assert_eq!(get_album_cover_file(&album).await.unwrap().id, files[2].id);
+    }
+
+    #[tokio::test]
+    async fn should_hide_trashed_files_from_albums_until_restored() {
+        let user = insert_test_user().await;
+        let album = insert_test_album(&user).await;
+        let files = insert_test_files(2, Some(&user)).await;
+
+        add_files_to_album(&album, &files).await.expect("Could not add files");
+        set_album_cover(&album, Some(&files[0]))
+            .await
+            .expect("Could not set cover");
+        move_file_to_trash(&files[0]).await.expect("Could not trash file");
+
+        let album = get_album_by_id(album.id, Some(&user)).await.unwrap();
+
+        assert_eq!(album.files_count().await, 1);
+        assert_eq!(get_album_cover_file(&album).await.unwrap().id, files[1].id);
+
+        let trashed_file = get_file_by_id(files[0].id, Some(&user)).await.unwrap();
+
+        restore_file(&trashed_file, ConflictPolicy::Fail)
+            .await
+            .expect("Could not restore file");
+
+        assert_eq!(album.files_count().await, 2);
+        assert_eq!(get_album_cover_file(&album).await.unwrap().id, files[0].id);
+    }
+
+    #[tokio::test]
+    async fn should_not_make_album_files_less_visible_than_the_album() {
+        let user = insert_test_user().await;
+        let input = AlbumInput {
+            name: fake_name(),
+            visibility: FileVisibility::Users,
+        };
+        let album = insert_album(&user, &input).await.expect("Could not insert album");
+        let file = insert_test_file(Some(&user)).await;
+        let ids = FolderItemIds {
+            folder_ids: vec![],
+            file_ids: vec![file.id],
+        };
+
+        batch_update_visibility(&user, &ids, FileVisibility::Users)
+            .await
+            .expect("Could not update visibility");
+
+        let file = get_file_by_id(file.id, Some(&user)).await.unwrap();
+
+        add_files_to_album(&album, &[file]).await.expect("Could not add files");
+
+        let results = batch_update_visibility(&user, &ids, FileVisibility::Private)
+            .await
+            .expect("Could not update visibility");
+
+        assert!(!results[0].is_success());
+    }
+
+    #[tokio::test]
+    async fn should_not_add_less_visible_files_to_an_album() {
+        let user = insert_test_user().await;
+        let input = AlbumInput {
+            name: fake_name(),
+            visibility: FileVisibility::Public,
+        };
+        let album = insert_album(&user, &input).await.expect("Could not insert album");
+        let file = insert_test_file(Some(&user)).await;
+
+        let result = add_files_to_album(&album, &[file]).await;
+
+        assert!(result.is_err());
+    }
+}
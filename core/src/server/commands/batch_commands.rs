@@ -0,0 +1,544 @@
+use std::path::PathBuf;
+
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::enums::{ConflictPolicy, FileVisibility};
+use crate::inputs::FolderItemIds;
+use crate::server::db_pool;
+use crate::server::models::{BatchItemResult, File, Folder, User};
+
+use super::album_commands::file_is_in_more_visible_album;
+use super::folder_commands::folder_contains;
+use super::trash_commands::{
+    delete_folder_tree, folder_id_is_trashed, trash_folder_tree, trash_replaced_item, untrash_file, untrash_folder,
+};
+use super::{
+    NameResolution, get_file_by_id, get_folder_by_id, require_within_quota, resolve_name_conflict,
+    update_user_over_quota_since,
+};
+
+enum BatchOperation<'a> {
+    Delete,
+    Move(Option<&'a Folder<'a>>, ConflictPolicy),
+    MoveToTrash,
+    Restore(ConflictPolicy),
+    UpdateVisibility(FileVisibility),
+}
+
+pub async fn batch_delete(user: &User<'_>, ids: &FolderItemIds) -> sqlx::Result<Vec<BatchItemResult>> {
+    run_batch(user, ids, BatchOperation::Delete).await
+}
+
+pub async fn batch_move(
+    user: &User<'_>,
+    ids: &FolderItemIds,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> sqlx::Result<Vec<BatchItemResult>> {
+    run_batch(user, ids, BatchOperation::Move(target_folder, policy)).await
+}
+
+pub async fn batch_move_to_trash(user: &User<'_>, ids: &FolderItemIds) -> sqlx::Result<Vec<BatchItemResult>> {
+    run_batch(user, ids, BatchOperation::MoveToTrash).await
+}
+
+pub async fn batch_restore(
+    user: &User<'_>,
+    ids: &FolderItemIds,
+    policy: ConflictPolicy,
+) -> sqlx::Result<Vec<BatchItemResult>> {
+    if let Err(error) = require_within_quota(user).await {
+        let folder_results = ids.folder_ids.iter().map(|&id| (id, false));
+        let file_results = ids.file_ids.iter().map(|&id| (id, true));
+
+        return Ok(folder_results
+            .chain(file_results)
+            .map(|(id, is_file)| BatchItemResult {
+                id,
+                is_file,
+                error: Some(error.to_string()),
+            })
+            .collect());
+    }
+
+    run_batch(user, ids, BatchOperation::Restore(policy)).await
+}
+
+pub async fn batch_update_visibility(
+    user: &User<'_>,
+    ids: &FolderItemIds,
+    visibility: FileVisibility,
+) -> sqlx::Result<Vec<BatchItemResult>> {
+    run_batch(user, ids, BatchOperation::UpdateVisibility(visibility)).await
+}
+
+/// Each item gets its own savepoint, so a failing item is rolled back without affecting the rest of the batch.
+async fn run_batch(
+    user: &User<'_>,
+    ids: &FolderItemIds,
+    operation: BatchOperation<'_>,
+) -> sqlx::Result<Vec<BatchItemResult>> {
+    let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+    let mut results = Vec::with_capacity(ids.len());
+    let mut deleted_paths = Vec::new();
+
+    // Folders go first so that restoring a folder together with its contents puts the contents back in place.
+    for &id in &ids.folder_ids {
+        let mut savepoint = transaction.begin().await?;
+
+        let result = match get_folder_by_id(id, Some(user)).await {
+            Ok(folder) => apply_to_folder(&mut savepoint, user, &folder, &operation).await,
+            Err(_) => Err(anyhow::anyhow!("Folder not found")),
+        };
+
+        results.push(finish_item(savepoint, id, false, result, &mut deleted_paths).await?);
+    }
+
+    for &id in &ids.file_ids {
+        let mut savepoint = transaction.begin().await?;
+
+        let result = match get_file_by_id(id, Some(user)).await {
+            Ok(file) => apply_to_file(&mut savepoint, user, &file, &operation).await,
+            Err(_) => Err(anyhow::anyhow!("File not found")),
+        };
+
+        results.push(finish_item(savepoint, id, true, result, &mut deleted_paths).await?);
+    }
+
+    transaction.commit().await?;
+
+    for path in deleted_paths {
+        let _ = std::fs::remove_file(path);
+    }
+
+    if matches!(operation, BatchOperation::Delete) {
+        update_user_over_quota_since(user).await?;
+    }
+
+    Ok(results)
+}
+
+async fn finish_item(
+    savepoint: sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    is_file: bool,
+    result: anyhow::Result<Vec<PathBuf>>,
+    deleted_paths: &mut Vec<PathBuf>,
+) -> sqlx::Result<BatchItemResult> {
+    match result {
+        Ok(paths) => {
+            savepoint.commit().await?;
+            deleted_paths.extend(paths);
+
+            Ok(BatchItemResult {
+                id,
+                is_file,
+                error: None,
+            })
+        }
+        Err(error) => {
+            savepoint.rollback().await?;
+
+            Ok(BatchItemResult {
+                id,
+                is_file,
+                error: Some(error.to_string()),
+            })
+        }
+    }
+}
+
+/// Items go back to their original folder, which can't be in the trash unless restored earlier in the batch.
+async fn get_restore_target_folder<'a>(
+    conn: &mut PgConnection,
+    parent_folder_id: Option<Uuid>,
+) -> anyhow::Result<Option<Folder<'a>>> {
+    let Some(parent_folder_id) = parent_folder_id else {
+        return Ok(None);
+    };
+
+    if folder_id_is_trashed(conn, parent_folder_id).await {
+        return Err(anyhow::anyhow!("Original folder is unavailable"));
+    }
+
+    Ok(Some(get_folder_by_id(parent_folder_id, None).await?))
+}
+
+async fn apply_to_file(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    file: &File<'_>,
+    operation: &BatchOperation<'_>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    match operation {
+        BatchOperation::Delete => {
+            if file.trashed_at.is_none() {
+                return Err(anyhow::anyhow!("File is not in the trash"));
+            }
+
+            sqlx::query!("DELETE FROM files WHERE id = $1", file.id)
+                .execute(conn)
+                .await?;
+
+            return Ok(vec![file.default_path()]);
+        }
+        BatchOperation::Move(target_folder, policy) => {
+            if target_folder.is_some_and(|tf| tf.trashed_at.is_some()) {
+                return Err(anyhow::anyhow!("Cannot move file into the trash"));
+            }
+
+            let target_folder_id = target_folder.map(|tf| tf.id);
+
+            let name = match resolve_name_conflict(&mut *conn, user, &file.into(), target_folder_id, *policy).await? {
+                NameResolution::Available(name) => name,
+                NameResolution::Replace(name, existing_item) => {
+                    trash_replaced_item(&mut *conn, &existing_item).await?;
+
+                    name
+                }
+                NameResolution::MergeInto(_) => return Err(anyhow::anyhow!("Files can't be merged")),
+            };
+
+            sqlx::query!(
+                "UPDATE files SET parent_folder_id = $2, name = $3 WHERE id = $1",
+                file.id,          // $1
+                target_folder_id, // $2
+                name,             // $3
+            )
+            .execute(conn)
+            .await?;
+        }
+        BatchOperation::MoveToTrash => {
+            sqlx::query!(
+                "UPDATE files SET trashed_at = current_timestamp WHERE id = $1 AND trashed_at IS NULL",
+                file.id
+            )
+            .execute(conn)
+            .await?;
+        }
+        BatchOperation::Restore(policy) => {
+            let target_folder = get_restore_target_folder(&mut *conn, file.parent_folder_id).await?;
+
+            untrash_file(conn, user, file, target_folder.as_ref(), *policy).await?;
+        }
+        BatchOperation::UpdateVisibility(visibility) => {
+            if let Some(parent_folder) = file.parent_folder().await
+                && *visibility < parent_folder.visibility
+            {
+                return Err(anyhow::anyhow!("Visibility cannot be lower than the parent folder"));
+            }
+
+            if file_is_in_more_visible_album(file, *visibility).await? {
+                return Err(anyhow::anyhow!(
+                    "Visibility cannot be lower than an album containing the file"
+                ));
+            }
+
+            sqlx::query!(
+                "UPDATE files SET visibility = $2 WHERE id = $1",
+                file.id,          // $1
+                *visibility as _, // $2
+            )
+            .execute(conn)
+            .await?;
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+async fn apply_to_folder(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    folder: &Folder<'_>,
+    operation: &BatchOperation<'_>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    match operation {
+        BatchOperation::Delete => {
+            if folder.trashed_at.is_none() {
+                return Err(anyhow::anyhow!("Folder is not in the trash"));
+            }
+
+            return Ok(delete_folder_tree(conn, folder.id).await?);
+        }
+        BatchOperation::Move(target_folder, policy) => {
+            if let Some(target_folder) = target_folder
+                && folder_contains(folder, target_folder).await
+            {
+                return Err(anyhow::anyhow!("Cannot move folder into itself"));
+            }
+
+            if target_folder.is_some_and(|tf| tf.trashed_at.is_some()) {
+                return Err(anyhow::anyhow!("Cannot move folder into the trash"));
+            }
+
+            let target_folder_id = target_folder.map(|tf| tf.id);
+
+            let name = match resolve_name_conflict(&mut *conn, user, &folder.into(), target_folder_id, *policy).await? {
+                NameResolution::Available(name) => name,
+                NameResolution::Replace(name, existing_item) => {
+                    trash_replaced_item(&mut *conn, &existing_item).await?;
+
+                    name
+                }
+                NameResolution::MergeInto(_) => return Err(anyhow::anyhow!("Folders can't be merged in a batch")),
+            };
+
+            sqlx::query!(
+                "UPDATE folders SET parent_folder_id = $2, name = $3 WHERE id = $1",
+                folder.id,        // $1
+                target_folder_id, // $2
+                name,             // $3
+            )
+            .execute(conn)
+            .await?;
+        }
+        BatchOperation::MoveToTrash => {
+            trash_folder_tree(conn, folder.id).await?;
+        }
+        BatchOperation::Restore(policy) => {
+            let target_folder = get_restore_target_folder(&mut *conn, folder.parent_folder_id).await?;
+
+            untrash_folder(conn, user, folder, target_folder.as_ref(), *policy).await?;
+        }
+        BatchOperation::UpdateVisibility(visibility) => {
+            if let Some(parent_folder) = folder.parent_folder().await
+                && *visibility < parent_folder.visibility
+            {
+                return Err(anyhow::anyhow!("Visibility cannot be lower than the parent folder"));
+            }
+
+            // Descendants are raised to the new visibility when needed, but never lowered.
+            sqlx::query!(
+                "WITH RECURSIVE child_folders AS (
+                    SELECT id FROM folders WHERE id = $1
+                    UNION ALL
+                    SELECT f.id FROM folders AS f, child_folders AS cf WHERE f.parent_folder_id = cf.id
+                ) UPDATE folders SET visibility = CASE WHEN id = $1 THEN $2 ELSE GREATEST(visibility, $2) END
+                WHERE id IN (SELECT id FROM child_folders)",
+                folder.id,        // $1
+                *visibility as _, // $2
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query!(
+                "WITH RECURSIVE child_folders AS (
+                    SELECT id FROM folders WHERE id = $1
+                    UNION ALL
+                    SELECT f.id FROM folders AS f, child_folders AS cf WHERE f.parent_folder_id = cf.id
+                ) UPDATE files SET visibility = GREATEST(visibility, $2)
+                WHERE parent_folder_id IN (SELECT id FROM child_folders)",
+                folder.id,        // $1
+                *visibility as _, // $2
+            )
+            .execute(conn)
+            .await?;
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inputs::FileInput;
+    use crate::server::commands::insert_file;
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_move_items_to_trash_in_a_batch() {
+        let user = insert_test_user().await;
+        let files = insert_test_files(3, Some(&user)).await;
+        let folder = insert_test_folder(Some(&user), None).await;
+
+        let ids = FolderItemIds {
+            file_ids: files.iter().map(|file| file.id).collect(),
+            folder_ids: vec![folder.id, Uuid::new_v4()],
+        };
+
+        let results = batch_move_to_trash(&user, &ids).await.expect("Could not run batch");
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(results.iter().filter(|result| result.is_success()).count(), 4);
+
+        for file in files {
+            let file = get_file_by_id(file.id, Some(&user)).await.unwrap();
+
+            assert!(file.trashed_at.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_resolve_name_conflicts_when_moving_items_in_a_batch() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = FileInput {
+            parent_folder_id: Some(folder.id),
+            name: file.name.to_string(),
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+        let same_name_file = insert_file(&user, &input).await.unwrap();
+
+        let ids = FolderItemIds {
+            file_ids: vec![same_name_file.id],
+            folder_ids: Vec::new(),
+        };
+
+        let results = batch_move(&user, &ids, None, ConflictPolicy::Fail)
+            .await
+            .expect("Could not run batch");
+
+        assert!(!results[0].is_success());
+
+        let results = batch_move(&user, &ids, None, ConflictPolicy::Replace)
+            .await
+            .expect("Could not run batch");
+
+        assert!(results[0].is_success());
+
+        let replaced_file = get_file_by_id(file.id, Some(&user)).await.unwrap();
+        let moved_file = get_file_by_id(same_name_file.id, Some(&user)).await.unwrap();
+
+        assert!(replaced_file.trashed_at.is_some());
+        assert_eq!(replaced_file.name, file.name);
+        assert_eq!(moved_file.parent_folder_id, None);
+        assert_eq!(moved_file.name, file.name);
+    }
+
+    #[tokio::test]
+    async fn should_auto_rename_same_name_items_moved_together_in_a_batch() {
+        let user = insert_test_user().await;
+        let folders = insert_test_folders(2, Some(&user), None).await;
+        let target_folder = insert_test_folder(Some(&user), None).await;
+        let name = fake_name() + ".jpg";
+        let mut file_ids = Vec::new();
+
+        for folder in &folders {
+            let input = FileInput {
+                parent_folder_id: Some(folder.id),
+                name: name.clone(),
+                content: vec![0xFF, 0xD8, 0xFF],
+                conflict_policy: ConflictPolicy::Fail,
+            };
+
+            file_ids.push(insert_file(&user, &input).await.unwrap().id);
+        }
+
+        for target_folder in [Some(&target_folder), None] {
+            let ids = FolderItemIds {
+                file_ids: file_ids.clone(),
+                folder_ids: Vec::new(),
+            };
+
+            let results = batch_move(&user, &ids, target_folder, ConflictPolicy::AutoRename)
+                .await
+                .expect("Could not run batch");
+
+            assert!(results.iter().all(|result| result.is_success()));
+
+            let first_file = get_file_by_id(file_ids[0], Some(&user)).await.unwrap();
+            let second_file = get_file_by_id(file_ids[1], Some(&user)).await.unwrap();
+
+            assert_eq!(first_file.parent_folder_id, target_folder.map(|tf| tf.id));
+            assert_ne!(first_file.name, second_file.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_only_delete_trashed_items_in_a_batch() {
+        let user = insert_test_user().await;
+        let files = insert_test_files(2, Some(&user)).await;
+        let ids = FolderItemIds {
+            file_ids: vec![files[0].id],
+            folder_ids: Vec::new(),
+        };
+
+        batch_move_to_trash(&user, &ids).await.expect("Could not run batch");
+
+        let ids = FolderItemIds {
+            file_ids: files.iter().map(|file| file.id).collect(),
+            folder_ids: Vec::new(),
+        };
+
+        let results = batch_delete(&user, &ids).await.expect("Could not run batch");
+
+        assert!(results[0].is_success());
+        assert!(!results[1].is_success());
+        assert!(get_file_by_id(files[0].id, Some(&user)).await.is_err());
+        assert!(get_file_by_id(files[1].id, Some(&user)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_resolve_name_conflicts_when_restoring_items_in_a_batch() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let name = fake_name() + ".jpg";
+        let input = FileInput {
+            parent_folder_id: Some(folder.id),
+            name: name.clone(),
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+        let file = insert_file(&user, &input).await.unwrap();
+        let ids = FolderItemIds {
+            file_ids: vec![file.id],
+            folder_ids: Vec::new(),
+        };
+
+        batch_move_to_trash(&user, &ids).await.expect("Could not run batch");
+        insert_file(&user, &input).await.unwrap();
+
+        let results = batch_restore(&user, &ids, ConflictPolicy::Fail)
+            .await
+            .expect("Could not run batch");
+
+        assert!(!results[0].is_success());
+
+        let results = batch_restore(&user, &ids, ConflictPolicy::AutoRename)
+            .await
+            .expect("Could not run batch");
+
+        assert!(results[0].is_success());
+
+        let restored_file = get_file_by_id(file.id, Some(&user)).await.unwrap();
+
+        assert!(restored_file.trashed_at.is_none());
+        assert_eq!(restored_file.parent_folder_id, Some(folder.id));
+        assert_ne!(restored_file.name, name);
+    }
+
+    #[tokio::test]
+    async fn should_not_move_a_folder_into_itself_in_a_batch() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        let ids = FolderItemIds {
+            file_ids: vec![file.id],
+            folder_ids: vec![folder.id],
+        };
+
+        let results = batch_move(&user, &ids, Some(&child_folder), ConflictPolicy::Fail)
+            .await
+            .expect("Could not run batch");
+
+        assert!(
+            results
+                .iter()
+                .any(|result| result.id == folder.id && !result.is_success())
+        );
+        assert!(results.iter().any(|result| result.id == file.id && result.is_success()));
+
+        let file = get_file_by_id(file.id, Some(&user)).await.unwrap();
+
+        assert_eq!(file.parent_folder_id, Some(child_folder.id));
+    }
+}
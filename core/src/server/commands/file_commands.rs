@@ -1,9 +1,13 @@
 use std::fs::File as FsFile;
-use std::io::Write;
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
 
 use bytesize::ByteSize;
+use chrono::{DateTime, NaiveDate, Utc};
 use file_format::FileFormat;
 use md5::{Digest, Md5};
+use sqlx::PgConnection;
+use sqlx::types::Json;
 use url::Url;
 use uuid::Uuid;
 use validator::{Validate, ValidationErrors};
@@ -11,19 +15,110 @@ use validator::{Validate, ValidationErrors};
 use sdk::config::APP_CONFIG;
 use sdk::constants::{ERROR_ALREADY_EXISTS, ERROR_IS_INVALID};
 
-use crate::enums::FileVisibility;
-use crate::inputs::{FileInput, RenameInput};
+use crate::enums::{ConflictPolicy, FileVisibility};
+use crate::inputs::{FileInput, ItemMetadataInput, ItemProperties, RenameInput};
 use crate::server::config::STORAGE_CONFIG;
-use crate::server::constants::{ALLOWED_FILE_FORMATS, ERROR_IS_TOO_LARGE};
+use crate::server::constants::{ERROR_IS_OVER_QUOTA, ERROR_IS_TOO_LARGE, RECENT_FILES_LIMIT};
 use crate::server::db_pool;
-use crate::server::models::{File, FileKey, Folder, User};
+use crate::server::models::{File, FileKey, Folder, FolderItem, User};
 
-use super::{file_name_exists, get_available_space, get_folder_by_id, get_parent_folders_by_id};
+use super::trash_commands::{folder_id_is_trashed, trash_replaced_item};
+use super::{
+    NameResolution, copy_visibility, file_name_exists, generate_available_name, get_available_space, get_folder_by_id,
+    get_parent_folders_by_id, require_within_quota, resolve_name_conflict,
+};
+
+pub async fn copy_file<'a>(file: &File<'_>, target_folder: Option<&Folder<'_>>) -> anyhow::Result<File<'a>> {
+    let user = file.user().await;
+
+    require_within_quota(&user).await?;
+
+    if user.available_space().await.min(get_available_space()) < ByteSize(file.byte_size as u64) {
+        return Err(anyhow::anyhow!("Not enough space available"));
+    }
+
+    let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    if let Some(target_folder) = target_folder
+        && folder_id_is_trashed(&mut transaction, target_folder.id).await
+    {
+        return Err(anyhow::anyhow!("Cannot copy file into the trash"));
+    }
+
+    let mut blob_paths = Vec::new();
+
+    let copied_file = insert_file_copy(&mut transaction, &user, file, target_folder, &mut blob_paths).await?;
+
+    commit_file_copies(transaction, &blob_paths).await?;
+
+    Ok(copied_file)
+}
+
+/// The blobs are written before the commit, so a committed copy never misses its blob, and are removed if it fails.
+pub(super) async fn commit_file_copies(
+    transaction: sqlx::Transaction<'_, sqlx::Postgres>,
+    blob_paths: &[(PathBuf, PathBuf)],
+) -> anyhow::Result<()> {
+    let mut written_paths = Vec::new();
+    let mut result = Ok(());
+
+    for (source_path, copy_path) in blob_paths {
+        if let Some(directory) = copy_path.parent() {
+            let _ = std::fs::create_dir_all(directory);
+        }
+
+        if let Err(error) = std::fs::copy(source_path, copy_path) {
+            result = Err(error.into());
+            break;
+        }
+
+        written_paths.push(copy_path);
+    }
+
+    if result.is_ok() {
+        result = transaction.commit().await.map_err(Into::into);
+    }
+
+    if result.is_err() {
+        for path in written_paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    result
+}
 
 pub async fn get_file_parent_folders<'a>(file: &File<'_>) -> sqlx::Result<Vec<Folder<'a>>> {
     get_parent_folders_by_id(file.parent_folder_id).await
 }
 
+pub async fn get_all_recent_files<'a>(user: &User<'_>) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        FolderItem,
+        r#"SELECT
+            f.id,
+            f.user_id,
+            f.parent_folder_id,
+            TRUE as "is_file!",
+            f.name,
+            f.visibility as "visibility!: FileVisibility",
+            f.created_at,
+            f.updated_at
+        FROM files AS f LEFT JOIN file_accesses AS fa ON fa.file_id = f.id AND fa.user_id = $1
+        WHERE f.trashed_at IS NULL AND (f.user_id = $1 OR (fa.accessed_at IS NOT NULL AND f.visibility >= $3))
+        ORDER BY GREATEST(f.created_at, fa.accessed_at) DESC
+        LIMIT $2"#,
+        user.id,                    // $1
+        RECENT_FILES_LIMIT as i64,  // $2
+        FileVisibility::Users as _, // $3
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
 pub async fn get_file_key_by_id(id: Uuid) -> sqlx::Result<FileKey> {
     let db_pool = db_pool().await;
 
@@ -47,6 +142,19 @@ pub async fn get_file_url(file: &File<'_>) -> anyhow::Result<Url> {
     Ok(file_url)
 }
 
+pub async fn get_root_files_byte_size(user: &User<'_>) -> sqlx::Result<ByteSize> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT COALESCE(SUM(byte_size), 0)::bigint AS "byte_size!" FROM files
+        WHERE user_id = $1 AND parent_folder_id IS NULL AND trashed_at IS NULL"#,
+        user.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| ByteSize(row.byte_size as u64))
+}
+
 pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<'a>, ValidationErrors> {
     input.validate()?;
 
@@ -57,8 +165,11 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
     let mut visibility = FileVisibility::Private;
     let byte_size = input.content.len();
     let file_format = FileFormat::from_bytes(&input.content);
+    let mut transaction = db_pool.begin().await.map_err(|_| ValidationErrors::new())?;
 
-    if file_name_exists(user, input.parent_folder_id, &input.name).await {
+    if input.conflict_policy == ConflictPolicy::Fail
+        && file_name_exists(&mut transaction, user, input.parent_folder_id, &input.name).await
+    {
         validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
     }
 
@@ -71,9 +182,10 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
     }
 
     let file_size = ByteSize(byte_size as u64);
+    let entitlements = user.entitlements().await;
 
     let available_space = [
-        STORAGE_CONFIG.max_size_per_file(),
+        entitlements.max_file_size(),
         user.available_space().await,
         get_available_space(),
     ]
@@ -82,9 +194,11 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
     .cloned()
     .unwrap_or(ByteSize(0));
 
-    if available_space < file_size {
+    if user.is_over_quota().await {
+        validation_errors.add("content", ERROR_IS_OVER_QUOTA.clone());
+    } else if available_space < file_size {
         validation_errors.add("content", ERROR_IS_TOO_LARGE.clone());
-    } else if !ALLOWED_FILE_FORMATS.contains(&file_format) {
+    } else if !entitlements.allows_file_format(&file_format) {
         validation_errors.add("content", ERROR_IS_INVALID.clone());
     }
 
@@ -92,14 +206,54 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
         return Err(validation_errors);
     }
 
+    let (name, replaced_item) = if input.conflict_policy == ConflictPolicy::Fail {
+        (input.name.clone(), None)
+    } else {
+        let new_file = FolderItem {
+            id: Uuid::nil(),
+            is_file: true,
+            user_id: user.id,
+            parent_folder_id: None,
+            name: input.name.as_str().into(),
+            visibility,
+            created_at: Utc::now(),
+            updated_at: None,
+        };
+
+        match resolve_name_conflict(
+            &mut transaction,
+            user,
+            &new_file,
+            input.parent_folder_id,
+            input.conflict_policy,
+        )
+        .await
+        {
+            Ok(NameResolution::Available(name)) => (name, None),
+            Ok(NameResolution::Replace(name, existing_item)) => (name, Some(existing_item)),
+            _ => {
+                validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
+
+                return Err(validation_errors);
+            }
+        }
+    };
+
     md5_hasher.update(&input.content);
 
     let md5_checksum = format!("{:x}", md5_hasher.finalize());
 
+    if let Some(replaced_item) = &replaced_item {
+        trash_replaced_item(&mut transaction, replaced_item)
+            .await
+            .map_err(|_| ValidationErrors::new())?;
+    }
+
     let result = sqlx::query_as!(
         File,
-        r#"INSERT INTO files (user_id, parent_folder_id, name, visibility, media_type, byte_size, md5_checksum)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        r#"INSERT INTO files (
+            user_id, parent_folder_id, name, visibility, media_type, byte_size, md5_checksum, taken_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING
             id,
             user_id,
@@ -109,22 +263,25 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
             media_type,
             byte_size,
             md5_checksum,
+            description,
+            properties as "properties!: Json<ItemProperties>",
             trashed_at,
             created_at,
             updated_at"#,
-        user.id,                  // $1
-        input.parent_folder_id,   // $2
-        input.name,               // $3
-        visibility as _,          // $4
-        file_format.media_type(), // $5
-        byte_size as i64,         // $6
-        md5_checksum,             // $7
+        user.id,                          // $1
+        input.parent_folder_id,           // $2
+        name,                             // $3
+        visibility as _,                  // $4
+        file_format.media_type(),         // $5
+        byte_size as i64,                 // $6
+        md5_checksum,                     // $7
+        extract_taken_at(&input.content), // $8
     )
-    .fetch_one(db_pool)
+    .fetch_one(&mut *transaction)
     .await;
 
     match result {
-        Ok(file) => {
+        Ok(file) if transaction.commit().await.is_ok() => {
             let _ = std::fs::create_dir_all(file.directory());
             let mut fs_file = FsFile::create(file.default_path()).unwrap();
 
@@ -132,10 +289,66 @@ pub async fn insert_file<'a>(user: &User<'_>, input: &FileInput) -> Result<File<
 
             Ok(file)
         }
-        Err(_) => Err(ValidationErrors::new()),
+        _ => Err(ValidationErrors::new()),
     }
 }
 
+pub(super) async fn insert_file_copy<'a>(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    file: &File<'_>,
+    target_folder: Option<&Folder<'_>>,
+    blob_paths: &mut Vec<(PathBuf, PathBuf)>,
+) -> anyhow::Result<File<'a>> {
+    let target_folder_id = target_folder.map(|tf| tf.id);
+    let visibility = copy_visibility(file.visibility, target_folder);
+    let name = generate_available_name(&mut *conn, user, target_folder_id, &file.name, true).await;
+
+    let copied_file = sqlx::query_as!(
+        File,
+        r#"INSERT INTO files (
+            user_id,
+            parent_folder_id,
+            name,
+            visibility,
+            media_type,
+            byte_size,
+            md5_checksum,
+            taken_at,
+            description,
+            properties
+        ) SELECT $1, $2, $3, $4, $5, $6, $7, taken_at, description, properties FROM files WHERE id = $8
+        RETURNING
+            id,
+            user_id,
+            parent_folder_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            media_type,
+            byte_size,
+            md5_checksum,
+            description,
+            properties as "properties!: Json<ItemProperties>",
+            trashed_at,
+            created_at,
+            updated_at"#,
+        user.id,            // $1
+        target_folder_id,   // $2
+        name,               // $3
+        visibility as _,    // $4
+        &file.media_type,   // $5
+        file.byte_size,     // $6
+        &file.md5_checksum, // $7
+        file.id,            // $8
+    )
+    .fetch_one(conn)
+    .await?;
+
+    blob_paths.push((file.default_path(), copied_file.default_path()));
+
+    Ok(copied_file)
+}
+
 pub async fn insert_file_key(file: &File<'_>) -> sqlx::Result<FileKey> {
     let db_pool = db_pool().await;
 
@@ -148,23 +361,61 @@ pub async fn insert_file_key(file: &File<'_>) -> sqlx::Result<FileKey> {
     .await
 }
 
-pub async fn move_file(file: &File<'_>, target_folder: Option<&Folder<'_>>) -> sqlx::Result<()> {
+pub async fn insert_or_update_file_access(user: &User<'_>, file: &File<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "INSERT INTO file_accesses (user_id, file_id) VALUES ($1, $2)
+        ON CONFLICT (user_id, file_id) DO UPDATE SET accessed_at = current_timestamp",
+        user.id, // $1
+        file.id, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn move_file(
+    file: &File<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
     let target_folder_id = target_folder.map(|tf| tf.id);
 
     if file.parent_folder_id == target_folder_id {
         return Ok(());
     }
 
+    if target_folder.is_some_and(|tf| tf.trashed_at.is_some()) {
+        return Err(anyhow::anyhow!("Cannot move file into the trash"));
+    }
+
+    let user = file.user().await;
     let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    let name = match resolve_name_conflict(&mut transaction, &user, &file.into(), target_folder_id, policy).await? {
+        NameResolution::Available(name) => name,
+        NameResolution::Replace(name, existing_item) => {
+            trash_replaced_item(&mut transaction, &existing_item).await?;
+
+            name
+        }
+        NameResolution::MergeInto(_) => return Err(anyhow::anyhow!("Files can't be merged")),
+    };
 
     sqlx::query!(
-        "UPDATE files SET parent_folder_id = $2 WHERE id = $1",
+        "UPDATE files SET parent_folder_id = $2, name = $3 WHERE id = $1",
         file.id,          // $1
         target_folder_id, // $2
+        name,             // $3
     )
-    .execute(db_pool)
-    .await
-    .map(|_| ())
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
 }
 
 pub async fn rename_file(file: &File<'_>, input: &RenameInput) -> Result<(), ValidationErrors> {
@@ -174,10 +425,13 @@ pub async fn rename_file(file: &File<'_>, input: &RenameInput) -> Result<(), Val
         return Ok(());
     }
 
+    let db_pool = db_pool().await;
+    let mut conn = db_pool.acquire().await.map_err(|_| ValidationErrors::new())?;
+
     if input.name.to_lowercase() != file.name.to_lowercase() {
         let mut validation_errors = ValidationErrors::new();
 
-        if file_name_exists(&file.user().await, file.parent_folder_id, &input.name).await {
+        if file_name_exists(&mut conn, &file.user().await, file.parent_folder_id, &input.name).await {
             validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
         }
 
@@ -186,17 +440,52 @@ pub async fn rename_file(file: &File<'_>, input: &RenameInput) -> Result<(), Val
         }
     }
 
-    let db_pool = db_pool().await;
-
     sqlx::query!("UPDATE files SET name = $1 WHERE id = $2", input.name, file.id)
-        .execute(db_pool)
+        .execute(&mut *conn)
         .await
         .map(|_| ())
         .map_err(|_| ValidationErrors::new())
 }
 
+pub async fn update_file_metadata(file: &File<'_>, input: &ItemMetadataInput) -> Result<(), ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+    let description = Some(input.description.trim()).filter(|description| !description.is_empty());
+
+    sqlx::query!(
+        "UPDATE files SET description = $2, properties = $3 WHERE id = $1",
+        file.id,                      // $1
+        description,                  // $2
+        Json(&input.properties) as _, // $3
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+    .map_err(|_| ValidationErrors::new())
+}
+
+fn extract_taken_at(content: &[u8]) -> Option<DateTime<Utc>> {
+    let exif = exif::Reader::new()
+        .read_from_container(&mut Cursor::new(content))
+        .ok()?;
+    let field = exif.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+
+    let exif::Value::Ascii(ref values) = field.value else {
+        return None;
+    };
+
+    let date_time = exif::DateTime::from_ascii(values.first()?).ok()?;
+
+    NaiveDate::from_ymd_opt(date_time.year.into(), date_time.month.into(), date_time.day.into())?
+        .and_hms_opt(date_time.hour.into(), date_time.minute.into(), date_time.second.into())
+        .map(|naive_date_time| naive_date_time.and_utc())
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::inputs::FolderItemIds;
+    use crate::server::commands::{batch_update_visibility, get_file_by_id, move_file_to_trash};
     use crate::test_utils::*;
 
     use super::*;
@@ -208,6 +497,7 @@ mod tests {
             parent_folder_id: None,
             name: fake_name() + ".jpg",
             content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
         };
 
         let result = insert_file(&user, &input).await;
@@ -222,6 +512,172 @@ mod tests {
         assert_eq!(file.media_type, "image/jpeg")
     }
 
+    #[tokio::test]
+    async fn should_copy_a_file_with_a_new_name() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+
+        let result = copy_file(&file, None).await;
+
+        assert!(result.is_ok());
+
+        let copied_file = result.unwrap();
+
+        assert_ne!(copied_file.id, file.id);
+        assert_eq!(
+            copied_file.name,
+            format!("{} (1).jpg", file.name.trim_end_matches(".jpg"))
+        );
+        assert_eq!(copied_file.md5_checksum, file.md5_checksum);
+        assert_eq!(copied_file.read(), file.read());
+    }
+
+    #[tokio::test]
+    async fn should_update_file_metadata_and_copy_it() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = ItemMetadataInput {
+            id: file.id,
+            description: "  Sunset at the beach  ".to_owned(),
+            properties: ItemProperties::from([("camera".to_owned(), "X100V".to_owned())]),
+        };
+
+        update_file_metadata(&file, &input)
+            .await
+            .expect("Could not update metadata");
+
+        let file = get_file_by_id(file.id, None).await.unwrap();
+        let copied_file = copy_file(&file, None).await.unwrap();
+
+        assert_eq!(file.description.as_deref(), Some("Sunset at the beach"));
+        assert_eq!(file.properties.get("camera").map(String::as_str), Some("X100V"));
+        assert_eq!(copied_file.description, file.description);
+        assert_eq!(copied_file.properties.0, file.properties.0);
+    }
+
+    #[tokio::test]
+    async fn should_not_update_file_metadata_with_a_blank_property_key() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = ItemMetadataInput {
+            id: file.id,
+            description: String::new(),
+            properties: ItemProperties::from([(" ".to_owned(), "value".to_owned())]),
+        };
+
+        let result = update_file_metadata(&file, &input).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_rename_a_moved_file_when_its_name_is_taken() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = FileInput {
+            parent_folder_id: Some(folder.id),
+            name: file.name.to_string(),
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+        let same_name_file = insert_file(&user, &input).await.unwrap();
+
+        assert!(move_file(&same_name_file, None, ConflictPolicy::Fail).await.is_err());
+
+        move_file(&same_name_file, None, ConflictPolicy::AutoRename)
+            .await
+            .unwrap();
+
+        let moved_file = get_file_by_id(same_name_file.id, None).await.unwrap();
+        let (stem, extension) = file.name.rsplit_once('.').unwrap();
+
+        assert_eq!(moved_file.parent_folder_id, None);
+        assert_eq!(moved_file.name, format!("{stem} (1).{extension}"));
+    }
+
+    #[tokio::test]
+    async fn should_move_a_replaced_file_to_the_trash() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = FileInput {
+            parent_folder_id: None,
+            name: file.name.to_string(),
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Replace,
+        };
+        let new_file = insert_file(&user, &input).await.unwrap();
+        let replaced_file = get_file_by_id(file.id, None).await.unwrap();
+
+        assert_eq!(new_file.name, file.name);
+        assert_eq!(replaced_file.name, file.name);
+        assert!(replaced_file.trashed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn should_not_conflict_with_a_trashed_file() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = FileInput {
+            parent_folder_id: None,
+            name: file.name.to_string(),
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+
+        move_file_to_trash(&file).await.unwrap();
+
+        let new_file = insert_file(&user, &input).await.unwrap();
+
+        assert_eq!(new_file.name, file.name);
+    }
+
+    #[tokio::test]
+    async fn should_get_recently_accessed_file_first() {
+        let user = insert_test_user().await;
+        let files = insert_test_files(3, Some(&user)).await;
+
+        insert_or_update_file_access(&user, &files[0])
+            .await
+            .expect("Could not update file access");
+
+        let result = get_all_recent_files(&user).await;
+
+        assert!(result.is_ok());
+
+        let recent_files = result.unwrap();
+
+        assert_eq!(recent_files.len(), 3);
+        assert_eq!(recent_files[0].id, files[0].id);
+    }
+
+    #[tokio::test]
+    async fn should_get_recently_accessed_shared_file() {
+        let user = insert_test_user().await;
+        let shared_file = insert_test_file(None).await;
+        let private_file = insert_test_file(None).await;
+        let ids = FolderItemIds {
+            folder_ids: vec![],
+            file_ids: vec![shared_file.id],
+        };
+
+        batch_update_visibility(&shared_file.user().await, &ids, FileVisibility::Users)
+            .await
+            .expect("Could not update visibility");
+
+        insert_or_update_file_access(&user, &shared_file)
+            .await
+            .expect("Could not update file access");
+        insert_or_update_file_access(&user, &private_file)
+            .await
+            .expect("Could not update file access");
+
+        let recent_files = get_all_recent_files(&user).await.unwrap();
+
+        assert_eq!(recent_files.len(), 1);
+        assert_eq!(recent_files[0].id, shared_file.id);
+    }
+
     #[tokio::test]
     async fn should_not_insert_an_invalid_file() {
         let user = insert_test_user().await;
@@ -229,6 +685,7 @@ mod tests {
             parent_folder_id: None,
             name: fake_name() + ".jpg",
             content: vec![],
+            conflict_policy: ConflictPolicy::Fail,
         };
 
         let result = insert_file(&user, &input).await;
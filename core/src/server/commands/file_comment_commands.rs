@@ -0,0 +1,240 @@
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+
+use sdk::constants::ERROR_IS_INVALID;
+
+use crate::enums::NotificationKind;
+use crate::inputs::{FileCommentInput, FileCommentUpdateInput};
+use crate::server::db_pool;
+use crate::server::models::{File, FileComment, User};
+
+use super::insert_notification;
+
+pub async fn delete_file_comment(file_comment: &FileComment<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!("DELETE FROM file_comments WHERE id = $1", file_comment.id)
+        .execute(db_pool)
+        .await
+        .map(|_| ())
+}
+
+pub async fn get_all_file_comments<'a>(file: &File<'_>) -> sqlx::Result<Vec<FileComment<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        FileComment,
+        "SELECT * FROM file_comments WHERE file_id = $1 ORDER BY created_at ASC, id ASC",
+        file.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_file_comment_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Result<FileComment<'a>> {
+    let db_pool = db_pool().await;
+    let user_id = user.map(|u| u.id);
+
+    sqlx::query_as!(
+        FileComment,
+        "SELECT * FROM file_comments WHERE id = $1 AND ($2::uuid IS NULL OR user_id = $2) LIMIT 1",
+        id,      // $1
+        user_id, // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn insert_file_comment<'a>(
+    user: &User<'_>,
+    file: &File<'_>,
+    input: &FileCommentInput,
+) -> Result<FileComment<'a>, ValidationErrors> {
+    input.validate()?;
+
+    let mut validation_errors = ValidationErrors::new();
+    let mut parent_comment = None;
+
+    if input.file_id != file.id || !file.is_visible_to(user) {
+        validation_errors.add("file_id", ERROR_IS_INVALID.clone());
+    }
+
+    if let Some(parent_comment_id) = input.parent_comment_id {
+        match get_file_comment_by_id(parent_comment_id, None).await {
+            Ok(comment) if comment.file_id == file.id => parent_comment = Some(comment),
+            _ => validation_errors.add("parent_comment_id", ERROR_IS_INVALID.clone()),
+        }
+    }
+
+    if input.region.is_some_and(|region| !region.is_valid()) {
+        validation_errors.add("region", ERROR_IS_INVALID.clone());
+    }
+
+    if !validation_errors.is_empty() {
+        return Err(validation_errors);
+    }
+
+    let db_pool = db_pool().await;
+
+    let file_comment = sqlx::query_as!(
+        FileComment,
+        "INSERT INTO file_comments (
+            file_id, user_id, parent_comment_id, body, region_x, region_y, region_width, region_height
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *",
+        file.id,                                  // $1
+        user.id,                                  // $2
+        input.parent_comment_id,                  // $3
+        input.body.trim(),                        // $4
+        input.region.map(|region| region.x),      // $5
+        input.region.map(|region| region.y),      // $6
+        input.region.map(|region| region.width),  // $7
+        input.region.map(|region| region.height), // $8
+    )
+    .fetch_one(db_pool)
+    .await
+    .map_err(|_| ValidationErrors::new())?;
+
+    let mut recipient_ids = vec![file.user_id];
+
+    if let Some(parent_comment) = parent_comment {
+        recipient_ids.push(parent_comment.user_id);
+    }
+
+    recipient_ids.dedup();
+
+    for recipient_id in recipient_ids
+        .into_iter()
+        .filter(|recipient_id| *recipient_id != user.id)
+    {
+        let _ = insert_notification(
+            recipient_id,
+            Some(user.id),
+            NotificationKind::FileComment,
+            Some(file.id),
+            Some(file_comment.id),
+            None,
+        )
+        .await;
+    }
+
+    Ok(file_comment)
+}
+
+pub async fn update_file_comment(
+    file_comment: &FileComment<'_>,
+    input: &FileCommentUpdateInput,
+) -> Result<(), ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE file_comments SET body = $2 WHERE id = $1",
+        file_comment.id,   // $1
+        input.body.trim(), // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+    .map_err(|_| ValidationErrors::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::FileVisibility;
+    use crate::inputs::{FolderItemIds, ImageRegion};
+    use crate::server::commands::{batch_update_visibility, get_all_notifications, get_file_by_id};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_insert_a_reply_and_notify_the_owner() {
+        let owner = insert_test_user().await;
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&owner)).await;
+        let ids = FolderItemIds {
+            folder_ids: vec![],
+            file_ids: vec![file.id],
+        };
+
+        batch_update_visibility(&owner, &ids, FileVisibility::Users)
+            .await
+            .expect("Could not share file");
+
+        let file = get_file_by_id(file.id, Some(&owner)).await.unwrap();
+        let input = FileCommentInput {
+            file_id: file.id,
+            parent_comment_id: None,
+            body: fake_name(),
+            region: Some(ImageRegion {
+                x: 0.25,
+                y: 0.25,
+                width: 0.5,
+                height: 0.5,
+            }),
+        };
+
+        let file_comment = insert_file_comment(&owner, &file, &input)
+            .await
+            .expect("Could not insert comment");
+
+        assert_eq!(file_comment.region(), input.region);
+        assert!(get_all_notifications(&owner).await.unwrap().is_empty());
+
+        let reply_input = FileCommentInput {
+            parent_comment_id: Some(file_comment.id),
+            region: None,
+            ..input
+        };
+
+        let reply = insert_file_comment(&user, &file, &reply_input)
+            .await
+            .expect("Could not insert reply");
+        let notifications = get_all_notifications(&owner).await.unwrap();
+
+        assert_eq!(reply.parent_comment_id, Some(file_comment.id));
+        assert_eq!(get_all_file_comments(&file).await.unwrap().len(), 2);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].file_comment_id, Some(reply.id));
+    }
+
+    #[tokio::test]
+    async fn should_not_insert_a_comment_on_a_private_file_of_another_user() {
+        let owner = insert_test_user().await;
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&owner)).await;
+        let input = FileCommentInput {
+            file_id: file.id,
+            parent_comment_id: None,
+            body: fake_name(),
+            region: None,
+        };
+
+        let result = insert_file_comment(&user, &file, &input).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_insert_a_comment_with_an_invalid_region() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let input = FileCommentInput {
+            file_id: file.id,
+            parent_comment_id: None,
+            body: fake_name(),
+            region: Some(ImageRegion {
+                x: 0.75,
+                y: 0.0,
+                width: 0.5,
+                height: 0.5,
+            }),
+        };
+
+        let result = insert_file_comment(&user, &file, &input).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -1,14 +1,123 @@
+use std::path::PathBuf;
+
+use bytesize::ByteSize;
+use sqlx::PgConnection;
+use sqlx::types::Json;
 use uuid::Uuid;
 use validator::{Validate, ValidationErrors};
 
 use sdk::constants::ERROR_ALREADY_EXISTS;
 
-use crate::enums::FileVisibility;
-use crate::inputs::RenameInput;
+use crate::enums::{ConflictPolicy, FileVisibility};
+use crate::inputs::{ItemMetadataInput, ItemProperties, RenameInput};
 use crate::server::db_pool;
-use crate::server::models::Folder;
+use crate::server::models::{File, Folder, FolderItem, FolderStats, User};
+
+use super::file_commands::{commit_file_copies, insert_file_copy};
+use super::trash_commands::{folder_id_is_trashed, trash_replaced_item};
+use super::{
+    NameResolution, copy_visibility, file_name_exists, generate_available_name, get_available_space, get_folder_by_id,
+    require_within_quota, resolve_name_conflict,
+};
+
+pub async fn copy_folder<'a>(folder: &Folder<'_>, target_folder: Option<&Folder<'_>>) -> anyhow::Result<Folder<'a>> {
+    if let Some(target_folder) = target_folder
+        && folder_contains(folder, target_folder).await
+    {
+        return Err(anyhow::anyhow!("Cannot copy folder into itself"));
+    }
+
+    let user = folder.user().await;
+
+    require_within_quota(&user).await?;
 
-use super::file_name_exists;
+    if user.available_space().await.min(get_available_space()) < get_folder_byte_size(folder).await? {
+        return Err(anyhow::anyhow!("Not enough space available"));
+    }
+
+    let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    if let Some(target_folder) = target_folder
+        && folder_id_is_trashed(&mut transaction, target_folder.id).await
+    {
+        return Err(anyhow::anyhow!("Cannot copy folder into the trash"));
+    }
+
+    let mut blob_paths = Vec::new();
+
+    let copied_folder = insert_folder_copy(&mut transaction, &user, folder, target_folder, &mut blob_paths).await?;
+
+    commit_file_copies(transaction, &blob_paths).await?;
+
+    Ok(copied_folder)
+}
+
+pub(super) async fn folder_contains(folder: &Folder<'_>, target_folder: &Folder<'_>) -> bool {
+    if folder.id == target_folder.id || Some(folder.id) == target_folder.parent_folder_id {
+        return true;
+    }
+
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "WITH RECURSIVE parent_folders AS (
+            SELECT id, parent_folder_id FROM folders WHERE id = $1
+            UNION ALL
+            SELECT f.id, f.parent_folder_id FROM folders as f, parent_folders AS pf
+            WHERE f.id = pf.parent_folder_id
+        ) SELECT id FROM parent_folders WHERE id = $2 LIMIT 1",
+        target_folder.id, // $1
+        folder.id,        // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .is_ok()
+}
+
+pub async fn get_folder_byte_size(folder: &Folder<'_>) -> sqlx::Result<ByteSize> {
+    get_folder_stats(folder).await.map(|stats| stats.byte_size())
+}
+
+pub async fn get_folder_stats(folder: &Folder<'_>) -> sqlx::Result<FolderStats> {
+    let db_pool = db_pool().await;
+
+    let cached_stats = sqlx::query_as!(
+        FolderStats,
+        "SELECT folder_id, byte_size, files_count, folders_count FROM folder_stats WHERE folder_id = $1 LIMIT 1",
+        folder.id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    if let Some(stats) = cached_stats {
+        return Ok(stats);
+    }
+
+    // The cached row is removed by triggers whenever a descendant changes, so it only needs to be computed here.
+    sqlx::query_as!(
+        FolderStats,
+        r#"WITH RECURSIVE child_folders AS (
+            SELECT id FROM folders WHERE id = $1
+            UNION ALL
+            SELECT f.id FROM folders AS f, child_folders AS cf WHERE f.parent_folder_id = cf.id AND f.trashed_at IS NULL
+        ) INSERT INTO folder_stats (folder_id, byte_size, files_count, folders_count)
+        SELECT
+            $1,
+            COALESCE(SUM(byte_size), 0)::bigint,
+            COUNT(*)::integer,
+            (SELECT COUNT(*) - 1 FROM child_folders)::integer
+        FROM files WHERE parent_folder_id IN (SELECT id FROM child_folders) AND trashed_at IS NULL
+        ON CONFLICT (folder_id) DO UPDATE SET
+            byte_size = EXCLUDED.byte_size,
+            files_count = EXCLUDED.files_count,
+            folders_count = EXCLUDED.folders_count
+        RETURNING folder_id, byte_size, files_count, folders_count"#,
+        folder.id, // $1
+    )
+    .fetch_one(db_pool)
+    .await
+}
 
 pub async fn get_folder_parent_folders<'a>(folder: &Folder<'_>) -> sqlx::Result<Vec<Folder<'a>>> {
     get_parent_folders_by_id(folder.parent_folder_id).await
@@ -24,67 +133,269 @@ pub async fn get_parent_folders_by_id<'a>(id: Option<Uuid>) -> sqlx::Result<Vec<
     sqlx::query_as!(
         Folder,
         r#"WITH RECURSIVE parent_folders AS (
-            SELECT * FROM folders WHERE id = $1
+            SELECT *, 0 AS depth FROM folders WHERE id = $1
             UNION ALL
-            SELECT f.* FROM folders as f, parent_folders AS pf WHERE f.id = pf.parent_folder_id
+            SELECT f.*, pf.depth + 1 FROM folders as f, parent_folders AS pf WHERE f.id = pf.parent_folder_id
             ) SELECT
                 id as "id!",
                 user_id as "user_id!",
                 parent_folder_id,
                 name as "name!",
                 visibility as "visibility!: FileVisibility",
+                description,
+                properties as "properties!: Json<ItemProperties>",
                 trashed_at,
                 created_at as "created_at!",
                 updated_at
-            FROM parent_folders LIMIT 1"#,
+            FROM parent_folders ORDER BY depth DESC"#,
         id
     )
     .fetch_all(db_pool)
     .await
-    .map(|folders| folders.into_iter().rev().collect())
 }
 
-pub async fn move_folder(folder: &Folder<'_>, target_folder: Option<&Folder<'_>>) -> sqlx::Result<()> {
+async fn insert_folder_copy<'a>(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    folder: &Folder<'_>,
+    target_folder: Option<&Folder<'_>>,
+    blob_paths: &mut Vec<(PathBuf, PathBuf)>,
+) -> anyhow::Result<Folder<'a>> {
+    let target_folder_id = target_folder.map(|tf| tf.id);
+    let visibility = copy_visibility(folder.visibility, target_folder);
+    let name = generate_available_name(&mut *conn, user, target_folder_id, &folder.name, false).await;
+
+    let copied_folder = sqlx::query_as!(
+        Folder,
+        r#"INSERT INTO folders (user_id, parent_folder_id, name, visibility, description, properties)
+        SELECT $1, $2, $3, $4, description, properties FROM folders WHERE id = $5
+        RETURNING
+            id,
+            user_id,
+            parent_folder_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            description,
+            properties as "properties!: Json<ItemProperties>",
+            trashed_at,
+            created_at,
+            updated_at"#,
+        user.id,          // $1
+        target_folder_id, // $2
+        name,             // $3
+        visibility as _,  // $4
+        folder.id,        // $5
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let child_folders = sqlx::query_as!(
+        Folder,
+        r#"SELECT
+            id,
+            user_id,
+            parent_folder_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            description,
+            properties as "properties!: Json<ItemProperties>",
+            trashed_at,
+            created_at,
+            updated_at
+        FROM folders WHERE parent_folder_id = $1 AND trashed_at IS NULL"#,
+        folder.id, // $1
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for child_folder in &child_folders {
+        Box::pin(insert_folder_copy(
+            &mut *conn,
+            user,
+            child_folder,
+            Some(&copied_folder),
+            blob_paths,
+        ))
+        .await?;
+    }
+
+    let files = sqlx::query_as!(
+        File,
+        r#"SELECT
+            id,
+            user_id,
+            parent_folder_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            media_type,
+            byte_size,
+            md5_checksum,
+            description,
+            properties as "properties!: Json<ItemProperties>",
+            trashed_at,
+            created_at,
+            updated_at
+        FROM files WHERE parent_folder_id = $1 AND trashed_at IS NULL"#,
+        folder.id, // $1
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for file in &files {
+        insert_file_copy(&mut *conn, user, file, Some(&copied_folder), blob_paths).await?;
+    }
+
+    Ok(copied_folder)
+}
+
+async fn get_child_items<'a>(conn: &mut PgConnection, folder_id: Uuid) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    sqlx::query_as!(
+        FolderItem,
+        r#"(
+            SELECT
+                id,
+                TRUE as "is_file!",
+                user_id,
+                parent_folder_id,
+                name,
+                visibility as "visibility!: FileVisibility",
+                created_at,
+                updated_at
+            FROM files WHERE parent_folder_id = $1 AND trashed_at IS NULL
+        ) UNION ALL (
+            SELECT
+                id,
+                FALSE as "is_file!",
+                user_id,
+                parent_folder_id,
+                name,
+                visibility as "visibility!: FileVisibility",
+                created_at,
+                updated_at
+            FROM folders WHERE parent_folder_id = $1 AND trashed_at IS NULL
+        )"#,
+        folder_id
+    )
+    .fetch_all(conn)
+    .await
+}
+
+/// Moves every child into the target folder, including the ones trashed on their own, then deletes the empty folder.
+pub(super) async fn merge_folder(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    folder: &Folder<'_>,
+    target_folder: &Folder<'_>,
+) -> anyhow::Result<()> {
+    // Trashed items don't hold their name, so they can move as they are.
+    sqlx::query!(
+        "WITH moved_files AS (
+            UPDATE files SET parent_folder_id = $2 WHERE parent_folder_id = $1 AND trashed_at IS NOT NULL
+        ) UPDATE folders SET parent_folder_id = $2 WHERE parent_folder_id = $1 AND trashed_at IS NOT NULL",
+        folder.id,        // $1
+        target_folder.id, // $2
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    for child_item in &get_child_items(&mut *conn, folder.id).await? {
+        let policy = if child_item.is_file {
+            ConflictPolicy::AutoRename
+        } else {
+            ConflictPolicy::Merge
+        };
+
+        let name = match resolve_name_conflict(&mut *conn, user, child_item, Some(target_folder.id), policy).await? {
+            NameResolution::Available(name) => name,
+            NameResolution::MergeInto(existing_folder) => {
+                Box::pin(merge_folder(&mut *conn, user, &child_item.into(), &existing_folder)).await?;
+
+                continue;
+            }
+            NameResolution::Replace(..) => return Err(anyhow::anyhow!("Items can't be replaced in a merge")),
+        };
+
+        if child_item.is_file {
+            sqlx::query!(
+                "UPDATE files SET parent_folder_id = $2, name = $3 WHERE id = $1",
+                child_item.id,    // $1
+                target_folder.id, // $2
+                name,             // $3
+            )
+            .execute(&mut *conn)
+            .await?;
+        } else {
+            sqlx::query!(
+                "UPDATE folders SET parent_folder_id = $2, name = $3 WHERE id = $1",
+                child_item.id,    // $1
+                target_folder.id, // $2
+                name,             // $3
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    sqlx::query!("DELETE FROM folders WHERE id = $1", folder.id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn move_folder(
+    folder: &Folder<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
     let target_folder_id = target_folder.map(|tf| tf.id);
 
     if folder.parent_folder_id == target_folder_id {
         return Ok(());
     }
 
+    if let Some(target_folder) = target_folder
+        && folder_contains(folder, target_folder).await
+    {
+        return Err(anyhow::anyhow!("Cannot move folder into itself"));
+    }
+
+    if target_folder.is_some_and(|tf| tf.trashed_at.is_some()) {
+        return Err(anyhow::anyhow!("Cannot move folder into the trash"));
+    }
+
+    let user = folder.user().await;
     let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
 
-    if let Some(target_folder) = target_folder {
-        let is_invalid = folder.id == target_folder.id
-            || Some(folder.id) == target_folder.parent_folder_id
-            || sqlx::query!(
-                "WITH RECURSIVE parent_folders AS (
-                    SELECT id, parent_folder_id FROM folders WHERE id = $1
-                    UNION ALL
-                    SELECT f.id, f.parent_folder_id FROM folders as f, parent_folders AS pf
-                    WHERE f.id = pf.parent_folder_id
-                ) SELECT id FROM parent_folders WHERE id = $2 LIMIT 1",
-                target_folder.id, // $1
-                folder.id,        // $2
-            )
-            .fetch_one(db_pool)
-            .await
-            .is_ok();
+    let name = match resolve_name_conflict(&mut transaction, &user, &folder.into(), target_folder_id, policy).await? {
+        NameResolution::Available(name) => name,
+        NameResolution::Replace(name, existing_item) => {
+            trash_replaced_item(&mut transaction, &existing_item).await?;
 
-        if is_invalid {
-            return Err(sqlx::Error::InvalidArgument(
-                "Cannot move folder into itself".to_owned(),
-            ));
+            name
         }
-    }
+        NameResolution::MergeInto(existing_folder) => {
+            merge_folder(&mut transaction, &user, folder, &existing_folder).await?;
+
+            transaction.commit().await?;
+
+            return Ok(());
+        }
+    };
 
     sqlx::query!(
-        "UPDATE folders SET parent_folder_id = $2 WHERE id = $1",
+        "UPDATE folders SET parent_folder_id = $2, name = $3 WHERE id = $1",
         folder.id,        // $1
         target_folder_id, // $2
+        name,             // $3
     )
-    .execute(db_pool)
-    .await
-    .map(|_| ())
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
 }
 
 pub async fn rename_folder(folder: &Folder<'_>, input: &RenameInput) -> Result<(), ValidationErrors> {
@@ -94,10 +405,13 @@ pub async fn rename_folder(folder: &Folder<'_>, input: &RenameInput) -> Result<(
         return Ok(());
     }
 
+    let db_pool = db_pool().await;
+    let mut conn = db_pool.acquire().await.map_err(|_| ValidationErrors::new())?;
+
     if input.name.to_lowercase() != folder.name.to_lowercase() {
         let mut validation_errors = ValidationErrors::new();
 
-        if file_name_exists(&folder.user().await, folder.parent_folder_id, &input.name).await {
+        if file_name_exists(&mut conn, &folder.user().await, folder.parent_folder_id, &input.name).await {
             validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
         }
 
@@ -106,11 +420,190 @@ pub async fn rename_folder(folder: &Folder<'_>, input: &RenameInput) -> Result<(
         }
     }
 
-    let db_pool = db_pool().await;
-
     sqlx::query!("UPDATE folders SET name = $1 WHERE id = $2", input.name, folder.id)
-        .execute(db_pool)
+        .execute(&mut *conn)
         .await
         .map(|_| ())
         .map_err(|_| ValidationErrors::new())
 }
+
+pub async fn update_folder_metadata(folder: &Folder<'_>, input: &ItemMetadataInput) -> Result<(), ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+    let description = Some(input.description.trim()).filter(|description| !description.is_empty());
+
+    sqlx::query!(
+        "UPDATE folders SET description = $2, properties = $3 WHERE id = $1",
+        folder.id,                    // $1
+        description,                  // $2
+        Json(&input.properties) as _, // $3
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+    .map_err(|_| ValidationErrors::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inputs::{FolderInput, FolderItemsQuery};
+    use crate::server::commands::{copy_file, get_all_folder_items, insert_folder, move_file, move_folder_to_trash};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_copy_a_folder_with_its_items() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let target_folder = insert_test_folder(Some(&user), None).await;
+
+        insert_test_folders(2, Some(&user), Some(&folder)).await;
+
+        let result = copy_folder(&folder, Some(&target_folder)).await;
+
+        assert!(result.is_ok());
+
+        let copied_folder = result.unwrap();
+
+        assert_eq!(copied_folder.name, folder.name);
+        assert_eq!(copied_folder.parent_folder_id, Some(target_folder.id));
+
+        let copied_items = get_all_folder_items(None, Some(&copied_folder), &FolderItemsQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(copied_items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_merge_a_moved_folder_into_a_folder_with_the_same_name() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let parent_folder = insert_test_folder(Some(&user), None).await;
+        let input = FolderInput {
+            parent_folder_id: Some(parent_folder.id),
+            name: folder.name.to_string(),
+            visibility: FileVisibility::Private,
+        };
+        let same_name_folder = insert_folder(&user, &input).await.unwrap();
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file(&files[1], Some(&same_name_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+
+        assert!(
+            move_folder(&same_name_folder, None, ConflictPolicy::Fail)
+                .await
+                .is_err()
+        );
+
+        move_folder(&same_name_folder, None, ConflictPolicy::Merge)
+            .await
+            .unwrap();
+
+        let folder_items = get_all_folder_items(None, Some(&folder), &FolderItemsQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(folder_items.len(), 2);
+        assert!(get_folder_by_id(same_name_folder.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_merge_a_moved_folder_into_a_trashed_folder() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let parent_folder = insert_test_folder(Some(&user), None).await;
+        let input = FolderInput {
+            parent_folder_id: Some(parent_folder.id),
+            name: folder.name.to_string(),
+            visibility: FileVisibility::Private,
+        };
+        let same_name_folder = insert_folder(&user, &input).await.unwrap();
+
+        move_folder_to_trash(&folder).await.unwrap();
+        move_folder(&same_name_folder, None, ConflictPolicy::Merge)
+            .await
+            .unwrap();
+
+        let moved_folder = get_folder_by_id(same_name_folder.id, None).await.unwrap();
+
+        assert_eq!(moved_folder.parent_folder_id, None);
+        assert_eq!(moved_folder.name, folder.name);
+        assert!(moved_folder.trashed_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_not_copy_items_into_a_trashed_folder() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let trashed_folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&trashed_folder)).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        move_folder_to_trash(&trashed_folder).await.unwrap();
+
+        assert!(copy_file(&file, Some(&child_folder)).await.is_err());
+        assert!(copy_folder(&folder, Some(&child_folder)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_copy_a_folder_into_itself() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+
+        let result = copy_folder(&folder, Some(&child_folder)).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_invalidate_folder_stats_when_items_change() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file(&files[0], Some(&child_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+
+        let stats = get_folder_stats(&folder).await.unwrap();
+
+        assert_eq!(stats.files_count, 1);
+        assert_eq!(stats.folders_count, 1);
+        assert_eq!(stats.byte_size, files[0].byte_size);
+
+        move_file(&files[1], Some(&child_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+
+        let stats = get_folder_stats(&folder).await.unwrap();
+
+        assert_eq!(stats.files_count, 2);
+        assert_eq!(stats.byte_size, files[0].byte_size + files[1].byte_size);
+    }
+
+    #[tokio::test]
+    async fn should_get_all_parent_folders_in_order() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let grandchild_folder = insert_test_folder(Some(&user), Some(&child_folder)).await;
+
+        let parent_folders = get_folder_parent_folders(&grandchild_folder).await.unwrap();
+
+        assert_eq!(
+            parent_folders
+                .iter()
+                .map(|parent_folder| parent_folder.id)
+                .collect::<Vec<_>>(),
+            vec![folder.id, child_folder.id]
+        );
+    }
+}
@@ -1,14 +1,206 @@
 use crate::enums::FileVisibility;
+use crate::inputs::FolderItemsQuery;
 use crate::server::db_pool;
 use crate::server::models::{Folder, FolderItem, User};
 
+use super::get_parent_folders_by_id;
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// `*` and `?` work as wildcards, and patterns without any wildcard match names containing them.
+fn name_pattern_to_like(pattern: &str) -> String {
+    let escaped = escape_like(pattern).replace('*', "%").replace('?', "_");
+
+    if pattern.contains(['*', '?']) {
+        escaped
+    } else {
+        format!("%{escaped}%")
+    }
+}
+
+/// Filtered listings search the whole drive, so they can't be scoped to a parent folder.
 pub async fn get_all_folder_items<'a>(
     user: Option<&User<'_>>,
     parent_folder: Option<&Folder<'_>>,
+    query: &FolderItemsQuery,
 ) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    if parent_folder.is_some() && query.filter.is_some() {
+        return Err(sqlx::Error::InvalidArgument(
+            "Filtered folder items can't have a parent folder".to_owned(),
+        ));
+    }
+
     let db_pool = db_pool().await;
     let user_id = user.map(|u| u.id);
     let parent_folder_id = parent_folder.map(|f| f.id);
+    let filter = query.filter.as_ref();
+    let name_pattern = filter.and_then(|f| f.name_pattern.as_deref()).map(name_pattern_to_like);
+    let media_type_prefix = filter
+        .and_then(|f| f.media_type.as_deref())
+        .map(|media_type| format!("{}%", escape_like(media_type)));
+    let min_byte_size = filter.and_then(|f| f.min_byte_size).map(|byte_size| byte_size as i64);
+    let max_byte_size = filter.and_then(|f| f.max_byte_size).map(|byte_size| byte_size as i64);
+    let visibility = filter.and_then(|f| f.visibility);
+    let metadata_pattern = filter
+        .and_then(|f| f.metadata_pattern.as_deref())
+        .map(name_pattern_to_like);
+    let tags = filter.map(|f| f.tags.clone()).unwrap_or_default();
+
+    sqlx::query_as!(
+        FolderItem,
+        r#"WITH all_items AS (
+            (
+                SELECT
+                    id,
+                    user_id,
+                    parent_folder_id,
+                    FALSE as is_file,
+                    name,
+                    visibility,
+                    0::bigint AS byte_size,
+                    ''::varchar AS media_type,
+                    description,
+                    properties,
+                    trashed_at,
+                    created_at,
+                    updated_at
+                FROM folders WHERE ($1::uuid IS NULL OR user_id = $1)
+            ) UNION ALL (
+                SELECT
+                    id,
+                    user_id,
+                    parent_folder_id,
+                    TRUE as is_file,
+                    name,
+                    visibility,
+                    byte_size,
+                    media_type,
+                    description,
+                    properties,
+                    trashed_at,
+                    created_at,
+                    updated_at
+                FROM files WHERE ($1::uuid IS NULL OR user_id = $1)
+            )
+        ), sorted_items AS (
+            SELECT
+                *,
+                CASE WHEN $3 THEN is_file::int ELSE 0 END AS folder_rank,
+                (CASE $4
+                    WHEN 'size' THEN byte_size::text
+                    WHEN 'created_at' THEN TO_CHAR(created_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'updated_at' THEN TO_CHAR(
+                        COALESCE(updated_at, created_at) AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS'
+                    )
+                    WHEN 'type' THEN media_type || '/' || name::text
+                    ELSE name::text
+                END) COLLATE natural_sort AS sort_key
+            FROM all_items
+            WHERE id = $6
+                OR (
+                    ($8 OR ($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                    AND trashed_at IS NULL
+                )
+        ) SELECT
+            si.id as "id!",
+            si.user_id as "user_id!",
+            si.parent_folder_id,
+            si.is_file as "is_file!",
+            si.name as "name!",
+            si.visibility as "visibility!: FileVisibility",
+            si.created_at as "created_at!",
+            si.updated_at
+        FROM sorted_items AS si LEFT JOIN sorted_items AS ci ON ci.id = $6
+        WHERE ($8 OR ($2::uuid IS NULL AND si.parent_folder_id IS NULL) OR si.parent_folder_id = $2)
+            AND si.trashed_at IS NULL
+            AND ($9::text IS NULL OR si.name ILIKE $9)
+            AND ($10::text IS NULL OR si.media_type LIKE $10)
+            AND ($11::date IS NULL OR si.created_at >= $11::date)
+            AND ($12::date IS NULL OR si.created_at < $12::date + 1)
+            AND ($13::bigint IS NULL OR (si.is_file AND si.byte_size >= $13))
+            AND ($14::bigint IS NULL OR (si.is_file AND si.byte_size <= $14))
+            AND ($15::file_visibility IS NULL OR si.visibility = $15)
+            AND (
+                $16::text IS NULL
+                OR si.description ILIKE $16
+                OR EXISTS (SELECT 1 FROM jsonb_each_text(si.properties) AS p WHERE p.key ILIKE $16 OR p.value ILIKE $16)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM unnest($17::text[]) AS tag
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM jsonb_object_keys(si.properties) AS property_key WHERE LOWER(property_key) = LOWER(tag)
+                )
+            )
+            AND (
+                $6::uuid IS NULL
+                OR si.folder_rank > ci.folder_rank
+                OR (si.folder_rank = ci.folder_rank AND (
+                    ($5 = 'asc' AND (si.sort_key, si.id) > (ci.sort_key, ci.id))
+                    OR ($5 = 'desc' AND (si.sort_key, si.id) < (ci.sort_key, ci.id))
+                ))
+            )
+        ORDER BY
+            si.folder_rank ASC,
+            CASE WHEN $5 = 'asc' THEN si.sort_key END ASC,
+            CASE WHEN $5 = 'desc' THEN si.sort_key END DESC,
+            CASE WHEN $5 = 'asc' THEN si.id END ASC,
+            CASE WHEN $5 = 'desc' THEN si.id END DESC
+        LIMIT $7"#,
+        user_id,                               // $1
+        parent_folder_id,                      // $2
+        query.folders_first,                   // $3
+        query.sort_by.to_string(),             // $4
+        query.sort_direction.to_string(),      // $5
+        query.cursor,                          // $6
+        query.limit.map(|limit| limit as i64), // $7
+        filter.is_some(),                      // $8
+        name_pattern,                          // $9
+        media_type_prefix,                     // $10
+        filter.and_then(|f| f.created_after),  // $11
+        filter.and_then(|f| f.created_before), // $12
+        min_byte_size,                         // $13
+        max_byte_size,                         // $14
+        visibility as _,                       // $15
+        metadata_pattern,                      // $16
+        &tags,                                 // $17
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+/// When a file and a folder share a name, the folder wins.
+pub async fn get_folder_item_by_path<'a>(user: &User<'_>, path: &str) -> sqlx::Result<FolderItem<'a>> {
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    let Some((name, parent_folder_names)) = segments.split_last() else {
+        return Err(sqlx::Error::RowNotFound);
+    };
+
+    let db_pool = db_pool().await;
+    let mut parent_folder_id = None;
+
+    for parent_folder_name in parent_folder_names {
+        let parent_folder = sqlx::query!(
+            "SELECT id FROM folders
+            WHERE user_id = $1
+                AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                AND LOWER(name) = $3
+                AND trashed_at IS NULL
+            LIMIT 1",
+            user.id,                           // $1
+            parent_folder_id,                  // $2
+            parent_folder_name.to_lowercase()  // $3
+        )
+        .fetch_one(db_pool)
+        .await?;
+
+        parent_folder_id = Some(parent_folder.id);
+    }
 
     sqlx::query_as!(
         FolderItem,
@@ -33,10 +225,10 @@ pub async fn get_all_folder_items<'a>(
                     created_at,
                     updated_at
                 FROM folders
-                WHERE ($1::uuid IS NULL OR user_id = $1)
+                WHERE user_id = $1
                     AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                    AND LOWER(name) = $3
                     AND trashed_at IS NULL
-                ORDER BY name ASC
             ) UNION ALL (
                 SELECT
                     id,
@@ -48,15 +240,120 @@ pub async fn get_all_folder_items<'a>(
                     created_at,
                     updated_at
                 FROM files
-                WHERE ($1::uuid IS NULL OR user_id = $1)
+                WHERE user_id = $1
                     AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                    AND LOWER(name) = $3
                     AND trashed_at IS NULL
-                ORDER BY name ASC
             )
-        )"#,
-        user_id,          // $1
-        parent_folder_id, // $2
+        ) AS matching_items ORDER BY is_file ASC LIMIT 1"#,
+        user.id,             // $1
+        parent_folder_id,    // $2
+        name.to_lowercase()  // $3
     )
-    .fetch_all(db_pool)
+    .fetch_one(db_pool)
     .await
 }
+
+pub async fn get_folder_item_path(folder_item: &FolderItem<'_>) -> sqlx::Result<String> {
+    let parent_folders = get_parent_folders_by_id(folder_item.parent_folder_id).await?;
+
+    Ok(parent_folders
+        .iter()
+        .map(|parent_folder| parent_folder.name.as_ref())
+        .chain([folder_item.name.as_ref()])
+        .fold(String::new(), |path, name| path + "/" + name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::ConflictPolicy;
+    use crate::inputs::{FolderItemsFilter, ItemMetadataInput, ItemProperties};
+    use crate::server::commands::{move_file, update_file_metadata};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_resolve_a_path_and_back() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        move_file(&file, Some(&child_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+
+        let path = format!("/{}/{}/{}", folder.name, child_folder.name.to_uppercase(), file.name);
+
+        let result = get_folder_item_by_path(&user, &path).await;
+
+        assert!(result.is_ok());
+
+        let folder_item = result.unwrap();
+
+        assert_eq!(folder_item.id, file.id);
+        assert!(folder_item.is_file);
+
+        let canonical_path = get_folder_item_path(&folder_item).await.unwrap();
+
+        assert_eq!(
+            canonical_path,
+            format!("/{}/{}/{}", folder.name, child_folder.name, file.name)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_search_items_by_property_value() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let files = insert_test_files(2, Some(&user)).await;
+        let value = fake_name();
+        let input = ItemMetadataInput {
+            id: files[0].id,
+            description: String::new(),
+            properties: ItemProperties::from([("project".to_owned(), value.clone())]),
+        };
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        update_file_metadata(&files[0], &input).await.unwrap();
+
+        let query = FolderItemsQuery {
+            filter: Some(FolderItemsFilter {
+                metadata_pattern: Some(value.to_uppercase()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let folder_items = get_all_folder_items(Some(&user), None, &query).await.unwrap();
+
+        assert_eq!(folder_items.len(), 1);
+        assert_eq!(folder_items[0].id, files[0].id);
+    }
+
+    #[tokio::test]
+    async fn should_not_filter_items_within_a_parent_folder() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let query = FolderItemsQuery {
+            filter: Some(FolderItemsFilter::default()),
+            ..Default::default()
+        };
+
+        let result = get_all_folder_items(Some(&user), Some(&folder), &query).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_not_resolve_a_missing_path() {
+        let user = insert_test_user().await;
+
+        insert_test_folder(Some(&user), None).await;
+
+        let result = get_folder_item_by_path(&user, "/missing/path").await;
+
+        assert!(result.is_err());
+    }
+}
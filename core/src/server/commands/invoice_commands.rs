@@ -0,0 +1,114 @@
+use sqlx::PgConnection;
+use url::Url;
+use uuid::Uuid;
+
+use crate::server::billing::{BILLING_PROVIDER, BillingOrder};
+use crate::server::db_pool;
+use crate::server::models::{Invoice, User};
+
+use super::get_plan_by_product_id;
+
+pub async fn get_all_invoices(user: &User<'_>) -> sqlx::Result<Vec<Invoice>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Invoice,
+        "SELECT * FROM invoices WHERE user_id = $1 ORDER BY ordered_at DESC",
+        user.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_invoice_by_id(id: Uuid, user: &User<'_>) -> sqlx::Result<Invoice> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Invoice,
+        "SELECT * FROM invoices WHERE id = $1 AND user_id = $2 LIMIT 1",
+        id,      // $1
+        user.id  // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+/// Not cached, since the URLs returned by the billing provider expire.
+pub async fn get_invoice_url(invoice: &Invoice) -> anyhow::Result<Url> {
+    BILLING_PROVIDER.get_order_invoice_url(invoice.polar_order_id).await
+}
+
+pub async fn sync_user_invoices(user: &User<'_>) -> anyhow::Result<usize> {
+    let orders = BILLING_PROVIDER.get_customer_orders(&user.id.to_string()).await?;
+    let db_pool = db_pool().await;
+    let mut conn = db_pool.acquire().await?;
+
+    for order in &orders {
+        upsert_invoice(&mut conn, user.id, order).await?;
+    }
+
+    Ok(orders.len())
+}
+
+pub(super) async fn upsert_invoice(conn: &mut PgConnection, user_id: Uuid, order: &BillingOrder) -> anyhow::Result<()> {
+    let plan_id = if let Some(product_id) = order.product_id {
+        get_plan_by_product_id(product_id).await.ok().map(|plan| plan.id)
+    } else {
+        None
+    };
+
+    // Orders don't go back to unpaid, so a late delivery of an older state can't undo a payment.
+    sqlx::query!(
+        "INSERT INTO invoices (user_id, plan_id, polar_order_id, amount_cents, currency, is_paid, ordered_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (polar_order_id) DO UPDATE SET
+            plan_id = EXCLUDED.plan_id,
+            amount_cents = EXCLUDED.amount_cents,
+            currency = EXCLUDED.currency,
+            is_paid = invoices.is_paid OR EXCLUDED.is_paid
+        WHERE invoices.user_id = EXCLUDED.user_id",
+        user_id,                            // $1
+        plan_id,                            // $2
+        order.id,                           // $3
+        i32::try_from(order.amount_cents)?, // $4
+        order.currency,                     // $5
+        order.is_paid,                      // $6
+        order.created_at                    // $7
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::commands::{confirm_plan_checkout, create_user_plan_checkout, insert_plan};
+    use crate::server::models::Entitlements;
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_sync_invoices_from_the_billing_provider() {
+        let user = insert_test_user().await;
+        let other_user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 150, 1500, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let checkout = create_user_plan_checkout(&user, &plan, false, None).await.unwrap();
+
+        confirm_plan_checkout(checkout.id).await.unwrap();
+
+        assert_eq!(sync_user_invoices(&user).await.unwrap(), 1);
+
+        let invoices = get_all_invoices(&user).await.unwrap();
+
+        assert_eq!(invoices.len(), 1);
+        assert_eq!(invoices[0].plan_id, Some(plan.id));
+        assert_eq!(invoices[0].amount(), "$ 1.50 USD");
+        assert!(invoices[0].is_paid);
+        assert!(get_invoice_url(&invoices[0]).await.is_ok());
+        assert!(get_invoice_by_id(invoices[0].id, &other_user).await.is_err());
+    }
+}
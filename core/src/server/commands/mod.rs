@@ -3,6 +3,8 @@ use chrono::{DateTime, Utc};
 use bytesize::ByteSize;
 use rand::distr::Alphanumeric;
 use rand::{Rng, rng};
+use sqlx::PgConnection;
+use sqlx::types::Json;
 use strum::IntoEnumIterator;
 use uuid::Uuid;
 use validator::{Validate, ValidationErrors};
@@ -10,28 +12,51 @@ use validator::{Validate, ValidationErrors};
 use sdk::auth_client::{Auth, auth_client};
 use sdk::constants::{ERROR_ALREADY_EXISTS, ERROR_IS_INVALID};
 
-use crate::enums::FileVisibility;
-use crate::inputs::FolderInput;
+use crate::constants::ERROR_NAME_CONFLICT;
+use crate::enums::{ConflictPolicy, FileVisibility};
+use crate::inputs::{FolderInput, ItemProperties};
 use crate::server::config::STORAGE_CONFIG;
 
 use super::db_pool;
-use super::models::{File, Folder, Session, User};
+use super::models::{File, Folder, FolderItem, Session, User};
 
+mod album_commands;
+mod batch_commands;
 mod file_commands;
+mod file_comment_commands;
 mod folder_commands;
 mod folder_item_commands;
+mod invoice_commands;
+mod notification_commands;
 mod plan_commands;
+mod promotion_code_commands;
 mod session_commands;
+mod smart_folder_commands;
+mod star_commands;
+mod storage_grant_commands;
+mod timeline_commands;
 mod trash_commands;
 mod user_commands;
+mod webhook_commands;
 
+pub use album_commands::*;
+pub use batch_commands::*;
 pub use file_commands::*;
+pub use file_comment_commands::*;
 pub use folder_commands::*;
 pub use folder_item_commands::*;
+pub use invoice_commands::*;
+pub use notification_commands::*;
 pub use plan_commands::*;
+pub use promotion_code_commands::*;
 pub use session_commands::*;
+pub use smart_folder_commands::*;
+pub use star_commands::*;
+pub use storage_grant_commands::*;
+pub use timeline_commands::*;
 pub use trash_commands::*;
 pub use user_commands::*;
+pub use webhook_commands::*;
 
 pub async fn confirm_authorization(token: &str, expires_at: DateTime<Utc>) -> anyhow::Result<Session<'_>> {
     let auth = Auth::new(token, expires_at, None);
@@ -50,6 +75,13 @@ pub async fn confirm_authorization(token: &str, expires_at: DateTime<Utc>) -> an
     Ok(insert_session(&user, &refreshed_auth).await?)
 }
 
+fn copy_visibility(visibility: FileVisibility, target_folder: Option<&Folder<'_>>) -> FileVisibility {
+    match target_folder {
+        Some(target_folder) if target_folder.visibility > visibility => target_folder.visibility,
+        _ => visibility,
+    }
+}
+
 pub async fn enable_user(user: &User<'_>) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
 
@@ -66,30 +98,64 @@ pub async fn enable_user(user: &User<'_>) -> sqlx::Result<()> {
     .map(|_| ())
 }
 
-async fn file_name_exists(user: &User<'_>, parent_folder_id: Option<Uuid>, name: &str) -> bool {
-    let db_pool = db_pool().await;
-
+async fn file_name_exists(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    parent_folder_id: Option<Uuid>,
+    name: &str,
+) -> bool {
     sqlx::query!(
         "(
             SELECT id FROM files
             WHERE user_id = $1
                 AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2) AND LOWER(name) = $3
+                AND trashed_at IS NULL
             LIMIT 1
         ) UNION (
             SELECT id FROM folders
             WHERE user_id = $1
                 AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2) AND LOWER(name) = $3
+                AND trashed_at IS NULL
             LIMIT 1
         )",
         user.id,             // $1
         parent_folder_id,    // $2
         name.to_lowercase()  // $3
     )
-    .fetch_one(db_pool)
+    .fetch_one(conn)
     .await
     .is_ok()
 }
 
+async fn generate_available_name(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    parent_folder_id: Option<Uuid>,
+    name: &str,
+    has_extension: bool,
+) -> String {
+    if !file_name_exists(&mut *conn, user, parent_folder_id, name).await {
+        return name.to_owned();
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if has_extension && !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (name, String::new()),
+    };
+
+    let mut counter = 1;
+
+    loop {
+        let available_name = format!("{stem} ({counter}){extension}");
+
+        if !file_name_exists(&mut *conn, user, parent_folder_id, &available_name).await {
+            return available_name;
+        }
+
+        counter += 1;
+    }
+}
+
 fn generate_random_string(length: u8) -> String {
     rng()
         .sample_iter(&Alphanumeric)
@@ -104,6 +170,47 @@ pub fn get_available_space() -> ByteSize {
     ByteSize(stats.f_bavail * stats.f_bsize as u64)
 }
 
+pub async fn get_conflicting_item_names(
+    user: &User<'_>,
+    parent_folder_id: Option<Uuid>,
+    names: &[String],
+    except_id: Option<Uuid>,
+) -> sqlx::Result<Vec<String>> {
+    let db_pool = db_pool().await;
+    let lowercase_names = names.iter().map(|name| name.to_lowercase()).collect::<Vec<_>>();
+
+    let taken_names = sqlx::query!(
+        r#"(
+            SELECT LOWER(name) AS "name!" FROM files
+            WHERE user_id = $1
+                AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                AND LOWER(name) = ANY($3)
+                AND id IS DISTINCT FROM $4
+        ) UNION (
+            SELECT LOWER(name) AS "name!" FROM folders
+            WHERE user_id = $1
+                AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2)
+                AND LOWER(name) = ANY($3)
+                AND id IS DISTINCT FROM $4
+        )"#,
+        user.id,          // $1
+        parent_folder_id, // $2
+        &lowercase_names, // $3
+        except_id,        // $4
+    )
+    .fetch_all(db_pool)
+    .await?
+    .into_iter()
+    .map(|row| row.name)
+    .collect::<Vec<_>>();
+
+    Ok(names
+        .iter()
+        .filter(|name| taken_names.contains(&name.to_lowercase()))
+        .cloned()
+        .collect())
+}
+
 pub async fn get_file_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Result<File<'a>> {
     let db_pool = db_pool().await;
     let user_id = user.map(|u| u.id);
@@ -119,6 +226,8 @@ pub async fn get_file_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Resu
             media_type,
             byte_size,
             md5_checksum,
+            description,
+            properties as "properties!: Json<ItemProperties>",
             trashed_at,
             created_at,
             updated_at
@@ -142,6 +251,8 @@ pub async fn get_folder_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Re
             parent_folder_id,
             name,
             visibility as "visibility!: FileVisibility",
+            description,
+            properties as "properties!: Json<ItemProperties>",
             trashed_at,
             created_at,
             updated_at
@@ -153,6 +264,53 @@ pub async fn get_folder_by_id<'a>(id: Uuid, user: Option<&User<'_>>) -> sqlx::Re
     .await
 }
 
+async fn get_item_by_name<'a>(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    parent_folder_id: Option<Uuid>,
+    name: &str,
+) -> sqlx::Result<FolderItem<'a>> {
+    sqlx::query_as!(
+        FolderItem,
+        r#"(
+            SELECT
+                id,
+                TRUE as "is_file!",
+                user_id,
+                parent_folder_id,
+                name,
+                visibility as "visibility!: FileVisibility",
+                created_at,
+                updated_at
+            FROM files
+            WHERE user_id = $1
+                AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2) AND LOWER(name) = $3
+                AND trashed_at IS NULL
+            LIMIT 1
+        ) UNION ALL (
+            SELECT
+                id,
+                FALSE as "is_file!",
+                user_id,
+                parent_folder_id,
+                name,
+                visibility as "visibility!: FileVisibility",
+                created_at,
+                updated_at
+            FROM folders
+            WHERE user_id = $1
+                AND (($2::uuid IS NULL AND parent_folder_id IS NULL) OR parent_folder_id = $2) AND LOWER(name) = $3
+                AND trashed_at IS NULL
+            LIMIT 1
+        ) LIMIT 1"#,
+        user.id,             // $1
+        parent_folder_id,    // $2
+        name.to_lowercase()  // $3
+    )
+    .fetch_one(conn)
+    .await
+}
+
 pub async fn get_used_space_by_user(user: &User<'_>) -> ByteSize {
     let db_pool = db_pool().await;
 
@@ -195,8 +353,9 @@ pub async fn insert_folder<'a>(user: &User<'_>, input: &FolderInput) -> Result<F
 
     let mut validation_errors = ValidationErrors::new();
     let db_pool = db_pool().await;
+    let mut conn = db_pool.acquire().await.map_err(|_| ValidationErrors::new())?;
 
-    if file_name_exists(user, input.parent_folder_id, &input.name).await {
+    if file_name_exists(&mut conn, user, input.parent_folder_id, &input.name).await {
         validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
     }
 
@@ -226,6 +385,8 @@ pub async fn insert_folder<'a>(user: &User<'_>, input: &FolderInput) -> Result<F
             parent_folder_id,
             name,
             visibility as "visibility!: FileVisibility",
+            description,
+            properties as "properties!: Json<ItemProperties>",
             trashed_at,
             created_at,
             updated_at"#,
@@ -234,13 +395,64 @@ pub async fn insert_folder<'a>(user: &User<'_>, input: &FolderInput) -> Result<F
         input.name,             // $3
         input.visibility as _   // $4
     )
-    .fetch_one(db_pool)
+    .fetch_one(&mut *conn)
     .await
     .map_err(|_| ValidationErrors::new())
 }
 
+enum NameResolution<'a> {
+    Available(String),
+    MergeInto(Folder<'a>),
+    Replace(String, FolderItem<'a>),
+}
+
+async fn require_within_quota(user: &User<'_>) -> anyhow::Result<()> {
+    if user.is_over_quota().await {
+        return Err(anyhow::anyhow!("Storage is over quota, the account is read-only"));
+    }
+
+    Ok(())
+}
+
+async fn resolve_name_conflict<'a>(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    item: &FolderItem<'_>,
+    target_folder_id: Option<Uuid>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<NameResolution<'a>> {
+    let existing_item = match get_item_by_name(&mut *conn, user, target_folder_id, &item.name).await {
+        Ok(existing_item) if existing_item.id != item.id => existing_item,
+        _ => return Ok(NameResolution::Available(item.name.to_string())),
+    };
+
+    match policy {
+        ConflictPolicy::Fail => Err(anyhow::anyhow!(ERROR_NAME_CONFLICT)),
+        ConflictPolicy::Merge if !item.is_file && !existing_item.is_file => Ok(NameResolution::MergeInto(
+            get_folder_by_id(existing_item.id, None).await?,
+        )),
+        ConflictPolicy::AutoRename | ConflictPolicy::Merge => Ok(NameResolution::Available(
+            generate_available_name(conn, user, target_folder_id, &item.name, item.is_file).await,
+        )),
+        ConflictPolicy::Replace => {
+            if !existing_item.is_file
+                && get_parent_folders_by_id(item.parent_folder_id)
+                    .await?
+                    .iter()
+                    .any(|folder| folder.id == existing_item.id)
+            {
+                return Err(anyhow::anyhow!("Cannot replace a folder containing the item"));
+            }
+
+            Ok(NameResolution::Replace(item.name.to_string(), existing_item))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::enums::SortDirection;
+    use crate::inputs::FolderItemsQuery;
     use crate::test_utils::*;
 
     use super::*;
@@ -251,7 +463,7 @@ mod tests {
 
         insert_test_folders(7, Some(&user), None).await;
 
-        let result = get_all_folder_items(Some(&user), None).await;
+        let result = get_all_folder_items(Some(&user), None, &FolderItemsQuery::default()).await;
 
         assert!(result.is_ok());
 
@@ -267,7 +479,7 @@ mod tests {
 
         insert_test_folders(7, Some(&user), Some(&parent_folder)).await;
 
-        let result = get_all_folder_items(Some(&user), Some(&parent_folder)).await;
+        let result = get_all_folder_items(Some(&user), Some(&parent_folder), &FolderItemsQuery::default()).await;
 
         assert!(result.is_ok());
 
@@ -276,11 +488,66 @@ mod tests {
         assert_eq!(folders.len(), 7);
     }
 
+    #[tokio::test]
+    async fn should_get_folder_items_by_pages() {
+        let user = insert_test_user().await;
+
+        insert_test_folders(3, Some(&user), None).await;
+        insert_test_files(4, Some(&user)).await;
+
+        let mut query = FolderItemsQuery {
+            limit: Some(4),
+            ..Default::default()
+        };
+
+        let first_page = get_all_folder_items(Some(&user), None, &query).await.unwrap();
+
+        assert_eq!(first_page.len(), 4);
+        assert!(first_page.iter().take(3).all(|item| !item.is_file));
+
+        query.cursor = first_page.last().map(|item| item.id);
+
+        let second_page = get_all_folder_items(Some(&user), None, &query).await.unwrap();
+
+        assert_eq!(second_page.len(), 3);
+        assert!(second_page.iter().all(|item| item.is_file));
+        assert!(
+            second_page
+                .iter()
+                .all(|item| first_page.iter().all(|other| other.id != item.id))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_folder_items_sorted_by_name_naturally() {
+        let user = insert_test_user().await;
+
+        for name in ["Photo 10", "Photo 9", "Photo 100"] {
+            let input = FolderInput {
+                parent_folder_id: None,
+                name: name.to_owned(),
+                visibility: FileVisibility::Private,
+            };
+
+            insert_folder(&user, &input).await.expect("Could not insert folder");
+        }
+
+        let query = FolderItemsQuery {
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+
+        let folder_items = get_all_folder_items(Some(&user), None, &query).await.unwrap();
+        let names = folder_items.iter().map(|item| item.name.as_ref()).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["Photo 100", "Photo 10", "Photo 9"]);
+    }
+
     #[tokio::test]
     async fn should_get_zero_folders_by_user() {
         let user = insert_test_user().await;
 
-        let result = get_all_folder_items(Some(&user), None).await;
+        let result = get_all_folder_items(Some(&user), None, &FolderItemsQuery::default()).await;
 
         assert!(result.is_ok());
 
@@ -0,0 +1,79 @@
+use uuid::Uuid;
+
+use crate::enums::NotificationKind;
+use crate::server::constants::NOTIFICATIONS_LIMIT;
+use crate::server::db_pool;
+use crate::server::models::{Notification, User};
+
+pub async fn get_all_notifications(user: &User<'_>) -> sqlx::Result<Vec<Notification>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Notification,
+        r#"SELECT
+            id,
+            user_id,
+            actor_user_id,
+            kind as "kind!: NotificationKind",
+            file_id,
+            file_comment_id,
+            plan_id,
+            read_at,
+            created_at,
+            updated_at
+        FROM notifications WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2"#,
+        user.id,                    // $1
+        NOTIFICATIONS_LIMIT as i64, // $2
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_unread_notifications_count(user: &User<'_>) -> sqlx::Result<i64> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!" FROM notifications WHERE user_id = $1 AND read_at IS NULL"#,
+        user.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| row.count)
+}
+
+pub async fn insert_notification(
+    user_id: Uuid,
+    actor_user_id: Option<Uuid>,
+    kind: NotificationKind,
+    file_id: Option<Uuid>,
+    file_comment_id: Option<Uuid>,
+    plan_id: Option<Uuid>,
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "INSERT INTO notifications (user_id, actor_user_id, kind, file_id, file_comment_id, plan_id)
+        VALUES ($1, $2, $3, $4, $5, $6)",
+        user_id,         // $1
+        actor_user_id,   // $2
+        kind as _,       // $3
+        file_id,         // $4
+        file_comment_id, // $5
+        plan_id,         // $6
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn mark_notifications_as_read(user: &User<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE notifications SET read_at = current_timestamp WHERE user_id = $1 AND read_at IS NULL",
+        user.id
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
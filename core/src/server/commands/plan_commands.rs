@@ -1,30 +1,40 @@
 use std::collections::HashMap;
 
-use polar_rs::{
-    AmountType, CheckoutSession, CheckoutSessionParams, PriceParams, ProductParams, RecurringInterval,
-    SubscriptionParams,
-};
 use uuid::Uuid;
 
-use crate::server::config::POLAR_CONFIG;
-use crate::server::models::{Plan, User};
-use crate::server::{POLAR_CLIENT, db_pool};
+use crate::enums::NotificationKind;
+use crate::server::billing::{
+    BILLING_PROVIDER, BillingCheckout, BillingCheckoutParams, BillingInterval, BillingProductParams,
+    BillingSubscription,
+};
+use crate::server::config::{BILLING_CONFIG, POLAR_CONFIG};
+use crate::server::db_pool;
+use crate::server::models::{Entitlements, Plan, PromotionCode, User};
 
-use super::get_user_by_id;
+use super::{
+    get_promotion_code_by_polar_discount_id, get_user_by_id, insert_notification, insert_promotion_code_redemption,
+    sync_user_invoices, update_user_over_quota_since,
+};
+
+pub async fn archive_plan(plan: &Plan<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE plans SET archived_at = current_timestamp WHERE archived_at IS NULL AND id = $1",
+        plan.id
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
 
 pub async fn cancel_subscription(user: &User<'_>) -> anyhow::Result<()> {
     let Some(subscription_id) = user.polar_subscription_id else {
         return Err(anyhow::anyhow!("User has no active subscription"));
     };
 
-    let subscription = POLAR_CLIENT
-        .update_subscription(
-            subscription_id,
-            &SubscriptionParams {
-                cancel_at_period_end: Some(true),
-                ..Default::default()
-            },
-        )
+    let subscription = BILLING_PROVIDER
+        .cancel_subscription_at_period_end(subscription_id)
         .await?;
 
     let db_pool = db_pool().await;
@@ -40,10 +50,49 @@ pub async fn cancel_subscription(user: &User<'_>) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub async fn change_subscription_plan(user: &User<'_>, plan: &Plan<'_>, is_yearly: bool) -> anyhow::Result<()> {
+    let Some(subscription_id) = user.polar_subscription_id else {
+        return Err(anyhow::anyhow!("User has no active subscription"));
+    };
+
+    if plan.is_archived() {
+        return Err(anyhow::anyhow!("Plan is archived"));
+    }
+
+    if user.used_space().await > plan.quota() + user.granted_space().await? {
+        return Err(anyhow::anyhow!("Not enough space in the selected plan"));
+    }
+
+    let product_id = if is_yearly {
+        plan.polar_yearly_product_id
+    } else {
+        plan.polar_monthly_product_id
+    };
+
+    let subscription = BILLING_PROVIDER
+        .change_subscription_product(subscription_id, product_id)
+        .await?;
+
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE users SET plan_id = $2, plan_expires_at = COALESCE($3, plan_expires_at) WHERE id = $1",
+        user.id,                         // $1
+        plan.id,                         // $2
+        subscription.current_period_end  // $3
+    )
+    .execute(db_pool)
+    .await?;
+
+    update_user_over_quota_since(&get_user_by_id(user.id).await?).await?;
+
+    Ok(())
+}
+
 pub async fn confirm_plan_checkout(checkout_id: Uuid) -> anyhow::Result<()> {
-    let checkout = POLAR_CLIENT.get_checkout_session(checkout_id).await?;
+    let checkout = BILLING_PROVIDER.get_checkout(checkout_id).await?;
 
-    if !checkout.status.is_succeeded() {
+    if !checkout.is_succeeded {
         return Err(anyhow::anyhow!("Invalid checkout session"));
     }
 
@@ -57,19 +106,43 @@ pub async fn confirm_plan_checkout(checkout_id: Uuid) -> anyhow::Result<()> {
 
     let plan = get_plan_by_product_id(checkout.product_id).await?;
 
+    if let Some(discount_id) = checkout.discount_id
+        && let Ok(promotion_code) = get_promotion_code_by_polar_discount_id(discount_id).await
+    {
+        insert_promotion_code_redemption(&promotion_code, &user, checkout.id).await?;
+    }
+
+    // Later orders, like renewals, arrive with the `order.*` webhooks.
+    if let Err(err) = sync_user_invoices(&user).await {
+        tracing::error!("Could not sync invoices: {err}");
+    }
+
     if Some(plan.id) == user.plan_id && checkout.subscription_id == user.polar_subscription_id {
         return Ok(());
     }
 
+    let plan_expires_at = if let Some(subscription_id) = checkout.subscription_id {
+        BILLING_PROVIDER
+            .get_subscription(subscription_id)
+            .await?
+            .current_period_end
+    } else {
+        None
+    };
+
     sqlx::query!(
-        "UPDATE users SET plan_id = $2, polar_subscription_id = $3 WHERE id = $1",
+        "UPDATE users SET plan_id = $2, polar_subscription_id = $3, plan_expires_at = COALESCE($4, plan_expires_at)
+        WHERE id = $1",
         user.id,                  // $1
         plan.id,                  // $2
-        checkout.subscription_id  // $3
+        checkout.subscription_id, // $3
+        plan_expires_at           // $4
     )
     .execute(db_pool)
     .await?;
 
+    update_user_over_quota_since(&get_user_by_id(user.id).await?).await?;
+
     Ok(())
 }
 
@@ -77,36 +150,125 @@ pub async fn create_user_plan_checkout(
     user: &User<'_>,
     plan: &Plan<'_>,
     is_yearly: bool,
-) -> anyhow::Result<CheckoutSession> {
+    promotion_code: Option<&PromotionCode<'_>>,
+) -> anyhow::Result<BillingCheckout> {
+    if plan.is_archived() {
+        return Err(anyhow::anyhow!("Plan is archived"));
+    }
+
+    if let Some(promotion_code) = promotion_code
+        && promotion_code
+            .currency
+            .as_ref()
+            .is_some_and(|currency| *currency != plan.currency)
+    {
+        return Err(anyhow::anyhow!("Promotion code is for another currency"));
+    }
+
+    let trial_days = if plan.trial_days > 0 && user.plan_id.is_none() && !user_had_plan(user).await? {
+        Some(plan.trial_days as u16)
+    } else {
+        None
+    };
+
     let product_id = if is_yearly {
         plan.polar_yearly_product_id
     } else {
         plan.polar_monthly_product_id
     };
 
-    Ok(POLAR_CLIENT
-        .create_checkout_session(&CheckoutSessionParams {
-            external_customer_id: Some(user.id.to_string()),
-            customer_name: Some(user.full_name.to_string()),
-            customer_email: Some(user.email.to_string()),
-            products: vec![product_id],
-            success_url: Some(
-                POLAR_CONFIG
-                    .success_base_url
-                    .join("confirm-checkout?checkout_id={CHECKOUT_ID}")
-                    .unwrap(),
-            ),
-            ..Default::default()
+    BILLING_PROVIDER
+        .create_checkout(&BillingCheckoutParams {
+            product_id,
+            external_customer_id: user.id.to_string(),
+            customer_name: &user.full_name,
+            customer_email: &user.email,
+            success_url: POLAR_CONFIG
+                .success_base_url
+                .join("confirm-checkout?checkout_id={CHECKOUT_ID}")
+                .unwrap(),
+            trial_days,
+            discount_id: promotion_code.map(|promotion_code| promotion_code.polar_discount_id),
         })
-        .await?)
+        .await
 }
 
-pub async fn get_all_plans<'a>() -> sqlx::Result<Vec<Plan<'a>>> {
+pub async fn expire_plans() -> sqlx::Result<usize> {
     let db_pool = db_pool().await;
 
-    sqlx::query_as!(Plan, "SELECT * FROM plans ORDER BY quota_gib ASC")
-        .fetch_all(db_pool)
-        .await
+    // Matches `User::plan_ends_at`, so auto-renewing plans aren't expired before the renewal webhook can arrive.
+    let users = sqlx::query_as!(
+        User,
+        "SELECT * FROM users WHERE plan_id IS NOT NULL
+            AND plan_expires_at + CASE WHEN polar_subscription_id IS NULL THEN INTERVAL '0' ELSE make_interval(days => $1) END
+                <= current_timestamp",
+        BILLING_CONFIG.plan_renewal_grace_days as i32
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut expired_count = 0;
+
+    for user in users {
+        let (Some(plan_id), Some(plan_expires_at)) = (user.plan_id, user.plan_expires_at) else {
+            continue;
+        };
+
+        let mut transaction = db_pool.begin().await?;
+
+        // Leaves alone the users whose plan was renewed or changed since they were fetched.
+        let result = sqlx::query!(
+            "UPDATE users SET plan_id = NULL, plan_expires_at = NULL, polar_subscription_id = NULL
+            WHERE id = $1 AND plan_id = $2 AND plan_expires_at = $3",
+            user.id,         // $1
+            plan_id,         // $2
+            plan_expires_at  // $3
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            continue;
+        }
+
+        sqlx::query!(
+            "INSERT INTO plan_expirations (user_id, plan_id, plan_expired_at) VALUES ($1, $2, $3)",
+            user.id,         // $1
+            plan_id,         // $2
+            plan_expires_at  // $3
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        expired_count += 1;
+
+        if let Err(error) =
+            insert_notification(user.id, None, NotificationKind::PlanExpired, None, None, Some(plan_id)).await
+        {
+            tracing::error!("Could not notify user {} about the expired plan: {error}", user.id);
+        }
+
+        // The fetched user's plan already ended, so it counts as expired without fetching the user again.
+        if let Err(error) = update_user_over_quota_since(&user).await {
+            tracing::error!("Could not update the over quota state of user {}: {error}", user.id);
+        }
+    }
+
+    Ok(expired_count)
+}
+
+pub async fn get_all_plans<'a>(include_archived: bool) -> sqlx::Result<Vec<Plan<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Plan,
+        "SELECT * FROM plans WHERE $1 OR archived_at IS NULL ORDER BY quota_gib ASC",
+        include_archived
+    )
+    .fetch_all(db_pool)
+    .await
 }
 
 pub async fn get_plan_by_id<'a>(id: Uuid) -> sqlx::Result<Plan<'a>> {
@@ -129,54 +291,75 @@ pub async fn get_plan_by_product_id<'a>(product_id: Uuid) -> sqlx::Result<Plan<'
     .await
 }
 
-pub async fn insert_plan(
+pub async fn get_plan_subscribers_count(plan: &Plan<'_>) -> sqlx::Result<i64> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM users WHERE plan_id = $1 AND plan_expires_at > current_timestamp"#,
+        plan.id
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_user_subscription(user: &User<'_>) -> anyhow::Result<Option<BillingSubscription>> {
+    let Some(subscription_id) = user.polar_subscription_id else {
+        return Ok(None);
+    };
+
+    Ok(Some(BILLING_PROVIDER.get_subscription(subscription_id).await?))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_plan<'a>(
     name: &str,
     description: &str,
-    quota_gib: u8,
-    monthly_price_cents: u8,
-    yearly_price_cents: u16,
-) -> sqlx::Result<()> {
+    quota_gib: u16,
+    monthly_price_cents: u32,
+    yearly_price_cents: u32,
+    currency: &str,
+    entitlements: &Entitlements,
+    trial_days: u16,
+) -> anyhow::Result<Plan<'a>> {
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(anyhow::anyhow!("Currency must be a lowercase ISO 4217 code"));
+    }
+
+    let monthly_price_cents = i32::try_from(monthly_price_cents)?;
+    let yearly_price_cents = i32::try_from(yearly_price_cents)?;
+    let max_file_size_gib = i16::try_from(entitlements.max_file_size_gib)?;
+    let trash_retention_days = i16::try_from(entitlements.trash_retention_days)?;
+    let trial_days = i16::try_from(trial_days)?;
     let db_pool = db_pool().await;
 
     let mut metadata = HashMap::new();
 
     metadata.insert("quota_gib".to_owned(), quota_gib.to_string());
 
-    let monthly_product = POLAR_CLIENT
-        .create_product(&ProductParams {
+    let monthly_product_id = BILLING_PROVIDER
+        .create_product(&BillingProductParams {
             name: format!("{name} (monthly subscription)"),
-            description: Some(description.to_owned()),
-            recurring_interval: Some(RecurringInterval::Month),
-            prices: vec![PriceParams {
-                amount_type: AmountType::Fixed,
-                price_currency: Some("usd".to_owned()),
-                price_amount: Some(monthly_price_cents as u32),
-                ..Default::default()
-            }],
+            description,
+            interval: BillingInterval::Month,
+            price_cents: monthly_price_cents as u32,
+            currency,
             metadata: metadata.clone(),
-            ..Default::default()
         })
-        .await
-        .expect("Failed to create monthly product");
+        .await?;
 
-    let yearly_product = POLAR_CLIENT
-        .create_product(&ProductParams {
+    let yearly_product_id = BILLING_PROVIDER
+        .create_product(&BillingProductParams {
             name: format!("{name} (yearly subscription)"),
-            description: Some(description.to_owned()),
-            recurring_interval: Some(RecurringInterval::Year),
-            prices: vec![PriceParams {
-                amount_type: AmountType::Fixed,
-                price_currency: Some("usd".to_owned()),
-                price_amount: Some(yearly_price_cents as u32),
-                ..Default::default()
-            }],
+            description,
+            interval: BillingInterval::Year,
+            price_cents: yearly_price_cents as u32,
+            currency,
             metadata: metadata.clone(),
-            ..Default::default()
         })
-        .await
-        .expect("Failed to create yearly product");
+        .await?;
 
-    sqlx::query!(
+    Ok(sqlx::query_as!(
+        Plan,
         "INSERT INTO plans (
             name,
             description,
@@ -184,19 +367,114 @@ pub async fn insert_plan(
             monthly_price_cents,
             yearly_price_cents,
             polar_monthly_product_id,
-            polar_yearly_product_id
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7)",
-        name,                       // $1
-        description,                // $2
-        quota_gib as i16,           // $3
-        monthly_price_cents as i16, // $4
-        yearly_price_cents as i16,  // $5
-        monthly_product.id,         // $6
-        yearly_product.id           // $7
+            polar_yearly_product_id,
+            currency,
+            max_file_size_gib,
+            allowed_media_types,
+            trash_retention_days,
+            trial_days
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING *",
+        name,                              // $1
+        description,                       // $2
+        quota_gib as i32,                  // $3
+        monthly_price_cents,               // $4
+        yearly_price_cents,                // $5
+        monthly_product_id,                // $6
+        yearly_product_id,                 // $7
+        currency,                          // $8
+        max_file_size_gib,                 // $9
+        &entitlements.allowed_media_types, // $10
+        trash_retention_days,              // $11
+        trial_days                         // $12
     )
-    .execute(db_pool)
-    .await
-    .map(|_| ())
+    .fetch_one(db_pool)
+    .await?)
+}
+
+pub async fn notify_expiring_plans() -> sqlx::Result<usize> {
+    let db_pool = db_pool().await;
+
+    let mut notified_count = 0;
+
+    for notice_days in &BILLING_CONFIG.plan_expiry_notice_days {
+        let users = sqlx::query_as!(
+            User,
+            "SELECT * FROM users AS u WHERE plan_id IS NOT NULL AND polar_subscription_id IS NULL
+                AND plan_expires_at > current_timestamp
+                AND plan_expires_at <= current_timestamp + make_interval(days => $1)
+                AND NOT EXISTS (
+                    SELECT id FROM notifications
+                    WHERE user_id = u.id AND kind = 'plan_expiring'
+                        AND created_at >= u.plan_expires_at - make_interval(days => $1)
+                )",
+            *notice_days as i32
+        )
+        .fetch_all(db_pool)
+        .await?;
+
+        for user in users {
+            insert_notification(user.id, None, NotificationKind::PlanExpiring, None, None, user.plan_id).await?;
+
+            notified_count += 1;
+        }
+    }
+
+    Ok(notified_count)
+}
+
+/// Prices can't be changed, since they belong to the products of existing subscriptions.
+pub async fn update_plan<'a>(
+    plan: &Plan<'_>,
+    name: Option<&str>,
+    description: Option<&str>,
+    quota_gib: Option<u16>,
+    entitlements: Option<&Entitlements>,
+    trial_days: Option<u16>,
+) -> anyhow::Result<Plan<'a>> {
+    let max_file_size_gib = entitlements
+        .map(|entitlements| i16::try_from(entitlements.max_file_size_gib))
+        .transpose()?;
+    let trash_retention_days = entitlements
+        .map(|entitlements| i16::try_from(entitlements.trash_retention_days))
+        .transpose()?;
+    let trial_days = trial_days.map(i16::try_from).transpose()?;
+    let db_pool = db_pool().await;
+
+    let plan = sqlx::query_as!(
+        Plan,
+        "UPDATE plans SET
+            name = COALESCE($2, name),
+            description = COALESCE($3, description),
+            quota_gib = COALESCE($4, quota_gib),
+            max_file_size_gib = COALESCE($5, max_file_size_gib),
+            allowed_media_types = COALESCE($6, allowed_media_types),
+            trash_retention_days = COALESCE($7, trash_retention_days),
+            trial_days = COALESCE($8, trial_days),
+            updated_at = current_timestamp
+        WHERE id = $1 RETURNING *",
+        plan.id,                                                                      // $1
+        name,                                                                         // $2
+        description,                                                                  // $3
+        quota_gib.map(|quota_gib| quota_gib as i32),                                  // $4
+        max_file_size_gib,                                                            // $5
+        entitlements.map(|entitlements| entitlements.allowed_media_types.as_slice()), // $6
+        trash_retention_days,                                                         // $7
+        trial_days                                                                    // $8
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    if quota_gib.is_some() || entitlements.is_some() {
+        let users = sqlx::query_as!(User, "SELECT * FROM users WHERE plan_id = $1", plan.id)
+            .fetch_all(db_pool)
+            .await?;
+
+        for user in users {
+            update_user_over_quota_since(&user).await?;
+        }
+    }
+
+    Ok(plan)
 }
 
 pub async fn update_user_plan(user: &User<'_>, plan: &Plan<'_>) -> Result<(), sqlx::Error> {
@@ -211,3 +489,301 @@ pub async fn update_user_plan(user: &User<'_>, plan: &Plan<'_>) -> Result<(), sq
     .await
     .map(|_| ())
 }
+
+pub async fn user_had_plan(user: &User<'_>) -> sqlx::Result<bool> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT EXISTS(SELECT id FROM plan_expirations WHERE user_id = $1) AS "exists!""#,
+        user.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| row.exists)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use sdk::constants::ERROR_IS_INVALID;
+
+    use crate::enums::ConflictPolicy;
+    use crate::inputs::FileInput;
+    use crate::server::commands::{get_all_notifications, insert_file, insert_promotion_code};
+    use crate::server::constants::ERROR_IS_TOO_LARGE;
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_apply_the_entitlements_of_the_plan_to_uploads() {
+        let user = insert_test_user().await;
+        let entitlements = Entitlements {
+            max_file_size_gib: 2,
+            allowed_media_types: vec!["image/png".to_owned()],
+            trash_retention_days: 180,
+        };
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &entitlements, 0)
+            .await
+            .unwrap();
+        let checkout = create_user_plan_checkout(&user, &plan, false, None).await.unwrap();
+
+        confirm_plan_checkout(checkout.id).await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert_eq!(user.entitlements().await, entitlements);
+
+        let jpeg_input = FileInput {
+            parent_folder_id: None,
+            name: fake_name() + ".jpg",
+            content: vec![0xFF, 0xD8, 0xFF],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+        let png_input = FileInput {
+            parent_folder_id: None,
+            name: fake_name() + ".png",
+            content: vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A],
+            conflict_policy: ConflictPolicy::Fail,
+        };
+        let errors = insert_file(&user, &jpeg_input).await.unwrap_err();
+
+        assert_eq!(errors.field_errors()["content"][0], *ERROR_IS_INVALID);
+        assert!(insert_file(&user, &png_input).await.is_ok());
+
+        let entitlements = Entitlements {
+            max_file_size_gib: 0,
+            ..entitlements
+        };
+
+        update_plan(&plan, None, None, None, Some(&entitlements), None)
+            .await
+            .unwrap();
+
+        let png_input = FileInput {
+            name: fake_name() + ".png",
+            ..png_input
+        };
+        let errors = insert_file(&user, &png_input).await.unwrap_err();
+
+        assert_eq!(errors.field_errors()["content"][0], *ERROR_IS_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn should_track_subscribers_going_over_a_lowered_quota() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let checkout = create_user_plan_checkout(&user, &plan, false, None).await.unwrap();
+
+        confirm_plan_checkout(checkout.id).await.unwrap();
+        insert_test_file(Some(&user)).await;
+
+        assert!(get_user_by_id(user.id).await.unwrap().over_quota_since.is_none());
+
+        update_plan(&plan, None, None, Some(0), None, None).await.unwrap();
+
+        assert!(get_user_by_id(user.id).await.unwrap().over_quota_since.is_some());
+    }
+
+    #[tokio::test]
+    async fn should_hide_an_archived_plan_from_new_checkouts() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(
+            &fake_name(),
+            "",
+            10,
+            100_000,
+            1_000_000,
+            "eur",
+            &Entitlements::free(),
+            0,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.monthly_price(), "€ 1000.00 EUR");
+
+        archive_plan(&plan).await.unwrap();
+
+        let plan = get_plan_by_id(plan.id).await.unwrap();
+
+        assert!(plan.is_archived());
+        assert!(
+            !get_all_plans(false)
+                .await
+                .unwrap()
+                .iter()
+                .any(|listed_plan| listed_plan.id == plan.id)
+        );
+        assert!(
+            get_all_plans(true)
+                .await
+                .unwrap()
+                .iter()
+                .any(|listed_plan| listed_plan.id == plan.id)
+        );
+        assert!(create_user_plan_checkout(&user, &plan, false, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_notify_about_an_expiring_plan_and_expire_it() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let db_pool = db_pool().await;
+
+        sqlx::query!(
+            "UPDATE users SET plan_id = $2, plan_expires_at = current_timestamp + interval '12 hours' WHERE id = $1",
+            user.id, // $1
+            plan.id  // $2
+        )
+        .execute(db_pool)
+        .await
+        .unwrap();
+
+        notify_expiring_plans().await.unwrap();
+        notify_expiring_plans().await.unwrap();
+
+        let notifications = get_all_notifications(&user).await.unwrap();
+
+        assert_eq!(
+            notifications
+                .iter()
+                .filter(|notification| notification.kind == NotificationKind::PlanExpiring)
+                .count(),
+            1
+        );
+
+        sqlx::query!(
+            "UPDATE users SET plan_expires_at = current_timestamp - interval '1 minute' WHERE id = $1",
+            user.id
+        )
+        .execute(db_pool)
+        .await
+        .unwrap();
+
+        expire_plans().await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+        let notifications = get_all_notifications(&user).await.unwrap();
+
+        assert!(user.plan_id.is_none());
+        assert!(user.plan_expires_at.is_none());
+        assert!(
+            notifications
+                .iter()
+                .any(|notification| notification.kind == NotificationKind::PlanExpired
+                    && notification.plan_id == Some(plan.id))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_keep_an_auto_renewing_plan_until_the_renewal_grace_ends() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let db_pool = db_pool().await;
+
+        sqlx::query!(
+            "UPDATE users SET plan_id = $2, polar_subscription_id = $3, plan_expires_at = current_timestamp - interval '1 hour'
+            WHERE id = $1",
+            user.id,       // $1
+            plan.id,       // $2
+            Uuid::new_v4() // $3
+        )
+        .execute(db_pool)
+        .await
+        .unwrap();
+
+        expire_plans().await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert_eq!(user.plan_id, Some(plan.id));
+        assert_eq!(user.plan().await.map(|plan| plan.id), Some(plan.id));
+
+        let expired_days = BILLING_CONFIG.plan_renewal_grace_days as i32 + 1;
+
+        sqlx::query!(
+            "UPDATE users SET plan_expires_at = current_timestamp - make_interval(days => $2) WHERE id = $1",
+            user.id,      // $1
+            expired_days  // $2
+        )
+        .execute(db_pool)
+        .await
+        .unwrap();
+
+        expire_plans().await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert!(user.plan_id.is_none());
+        assert!(user.polar_subscription_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_start_a_trial_with_a_promotion_code() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 14)
+            .await
+            .unwrap();
+        let code = format!("test-{}", &Uuid::new_v4().simple().to_string()[..16]);
+        let promotion_code = insert_promotion_code(&code, None, Some((50, "usd")), None, None)
+            .await
+            .unwrap();
+        let checkout = create_user_plan_checkout(&user, &plan, true, Some(&promotion_code))
+            .await
+            .unwrap();
+
+        confirm_plan_checkout(checkout.id).await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert_eq!(promotion_code.discount(), "$ 0.50 USD off");
+        assert_eq!(promotion_code.redemptions_count().await.unwrap(), 1);
+        assert!(
+            user.plan_expires_at
+                .is_some_and(|expires_at| expires_at < Utc::now() + TimeDelta::days(15))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_subscribe_to_a_plan_switch_it_and_cancel_it() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let checkout = create_user_plan_checkout(&user, &plan, false, None).await.unwrap();
+
+        confirm_plan_checkout(checkout.id).await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert_eq!(user.plan().await.map(|plan| plan.id), Some(plan.id));
+        assert_eq!(user.polar_subscription_id, checkout.subscription_id);
+
+        let other_plan = insert_plan(&fake_name(), "", 20, 200, 2000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+
+        change_subscription_plan(&user, &other_plan, true).await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+        let subscription = get_user_subscription(&user).await.unwrap().unwrap();
+
+        assert_eq!(user.plan_id, Some(other_plan.id));
+        assert_eq!(subscription.product_id, other_plan.polar_yearly_product_id);
+
+        cancel_subscription(&user).await.unwrap();
+
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert!(user.polar_subscription_id.is_none());
+        assert_eq!(user.plan().await.map(|plan| plan.id), Some(other_plan.id));
+    }
+}
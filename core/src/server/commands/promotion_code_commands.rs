@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::server::billing::{BILLING_PROVIDER, BillingDiscountParams};
+use crate::server::db_pool;
+use crate::server::models::{PromotionCode, User};
+
+pub async fn get_all_promotion_codes<'a>() -> sqlx::Result<Vec<PromotionCode<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(PromotionCode, "SELECT * FROM promotion_codes ORDER BY created_at DESC")
+        .fetch_all(db_pool)
+        .await
+}
+
+pub async fn get_promotion_code_by_code<'a>(code: &str) -> sqlx::Result<PromotionCode<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        PromotionCode,
+        "SELECT * FROM promotion_codes WHERE code = upper($1) LIMIT 1",
+        code.trim()
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub(super) async fn get_promotion_code_by_polar_discount_id<'a>(
+    polar_discount_id: Uuid,
+) -> sqlx::Result<PromotionCode<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        PromotionCode,
+        "SELECT * FROM promotion_codes WHERE polar_discount_id = $1 LIMIT 1",
+        polar_discount_id
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_promotion_code_redemptions_count(promotion_code: &PromotionCode<'_>) -> sqlx::Result<i64> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!" FROM promotion_code_redemptions WHERE promotion_code_id = $1"#,
+        promotion_code.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| row.count)
+}
+
+pub async fn get_redeemable_promotion_code<'a>(code: &str) -> anyhow::Result<PromotionCode<'a>> {
+    let promotion_code = get_promotion_code_by_code(code).await?;
+
+    if promotion_code.is_expired() {
+        return Err(anyhow::anyhow!("Promotion code is expired"));
+    }
+
+    if let Some(usage_limit) = promotion_code.usage_limit
+        && promotion_code.redemptions_count().await? >= usage_limit as i64
+    {
+        return Err(anyhow::anyhow!("Promotion code has reached its usage limit"));
+    }
+
+    Ok(promotion_code)
+}
+
+pub async fn insert_promotion_code<'a>(
+    code: &str,
+    percent_off: Option<u8>,
+    amount_off: Option<(u32, &str)>,
+    usage_limit: Option<u32>,
+    expires_at: Option<DateTime<Utc>>,
+) -> anyhow::Result<PromotionCode<'a>> {
+    let code = code.trim().to_uppercase();
+
+    if !(3..=32).contains(&code.len()) || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow::anyhow!(
+            "Code must have 3 to 32 letters, digits, dashes or underscores"
+        ));
+    }
+
+    match (percent_off, amount_off) {
+        (Some(percent_off), None) if (1..=100).contains(&percent_off) => {}
+        (None, Some((amount_off_cents, currency)))
+            if amount_off_cents > 0 && currency.len() == 3 && currency.chars().all(|c| c.is_ascii_lowercase()) => {}
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Either a percentage between 1 and 100 or an amount with a lowercase currency is required"
+            ));
+        }
+    }
+
+    if expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err(anyhow::anyhow!("Expiration must be in the future"));
+    }
+
+    let amount_off_cents = amount_off.map(|(amount_off_cents, _)| amount_off_cents);
+    let currency = amount_off.map(|(_, currency)| currency);
+
+    let polar_discount_id = BILLING_PROVIDER
+        .create_discount(&BillingDiscountParams {
+            code: &code,
+            percent_off,
+            amount_off_cents,
+            currency,
+            max_redemptions: usage_limit,
+            ends_at: expires_at,
+        })
+        .await?;
+
+    let db_pool = db_pool().await;
+
+    Ok(sqlx::query_as!(
+        PromotionCode,
+        "INSERT INTO promotion_codes (
+            code, percent_off, amount_off_cents, currency, usage_limit, expires_at, polar_discount_id
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+        code,                                             // $1
+        percent_off.map(i16::from),                       // $2
+        amount_off_cents.map(i32::try_from).transpose()?, // $3
+        currency,                                         // $4
+        usage_limit.map(i32::try_from).transpose()?,      // $5
+        expires_at,                                       // $6
+        polar_discount_id                                 // $7
+    )
+    .fetch_one(db_pool)
+    .await?)
+}
+
+pub(super) async fn insert_promotion_code_redemption(
+    promotion_code: &PromotionCode<'_>,
+    user: &User<'_>,
+    checkout_id: Uuid,
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "INSERT INTO promotion_code_redemptions (promotion_code_id, user_id, checkout_id) VALUES ($1, $2, $3)
+        ON CONFLICT (checkout_id) DO NOTHING",
+        promotion_code.id, // $1
+        user.id,           // $2
+        checkout_id        // $3
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_stop_redeeming_a_promotion_code_at_its_usage_limit() {
+        let user = insert_test_user().await;
+        let code = format!("test-{}", &Uuid::new_v4().simple().to_string()[..16]);
+        let promotion_code = insert_promotion_code(&code, Some(10), None, Some(1), None)
+            .await
+            .unwrap();
+
+        assert_eq!(promotion_code.code, code.to_uppercase());
+        assert_eq!(promotion_code.discount(), "10% off");
+        assert!(get_redeemable_promotion_code(&code).await.is_ok());
+        assert!(insert_promotion_code("NO", Some(10), None, None, None).await.is_err());
+        assert!(
+            insert_promotion_code(&code, Some(10), Some((500, "usd")), None, None)
+                .await
+                .is_err()
+        );
+
+        insert_promotion_code_redemption(&promotion_code, &user, Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert!(get_redeemable_promotion_code(&code).await.is_err());
+    }
+}
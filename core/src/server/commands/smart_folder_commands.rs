@@ -0,0 +1,273 @@
+use sqlx::types::Json;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+
+use sdk::constants::ERROR_ALREADY_EXISTS;
+
+use crate::inputs::{FolderItemsQuery, SmartFolderInput};
+use crate::server::db_pool;
+use crate::server::models::{FolderItem, SmartFolder, SmartFolderDefinition, User};
+
+use super::get_all_folder_items;
+
+pub async fn delete_smart_folder(smart_folder: &SmartFolder<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!("DELETE FROM smart_folders WHERE id = $1", smart_folder.id)
+        .execute(db_pool)
+        .await
+        .map(|_| ())
+}
+
+pub async fn get_all_smart_folder_items<'a>(
+    smart_folder: &SmartFolder<'_>,
+    query: &FolderItemsQuery,
+) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    let user = smart_folder.user().await;
+    let query = FolderItemsQuery {
+        filter: Some(smart_folder.filter()),
+        ..query.clone()
+    };
+
+    get_all_folder_items(Some(&user), None, &query).await
+}
+
+pub async fn get_all_smart_folders<'a>(user: &User<'_>) -> sqlx::Result<Vec<SmartFolder<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        SmartFolder,
+        r#"SELECT
+            id,
+            user_id,
+            name,
+            definition as "definition!: Json<SmartFolderDefinition>",
+            created_at,
+            updated_at
+        FROM smart_folders WHERE user_id = $1 ORDER BY name ASC"#,
+        user.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_smart_folder_by_id<'a>(id: Uuid, user: &User<'_>) -> sqlx::Result<SmartFolder<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        SmartFolder,
+        r#"SELECT
+            id,
+            user_id,
+            name,
+            definition as "definition!: Json<SmartFolderDefinition>",
+            created_at,
+            updated_at
+        FROM smart_folders WHERE id = $1 AND user_id = $2 LIMIT 1"#,
+        id,      // $1
+        user.id, // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn insert_smart_folder<'a>(
+    user: &User<'_>,
+    input: &SmartFolderInput,
+) -> Result<SmartFolder<'a>, ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+
+    if smart_folder_name_exists(user, &input.name).await {
+        let mut validation_errors = ValidationErrors::new();
+
+        validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
+
+        return Err(validation_errors);
+    }
+
+    sqlx::query_as!(
+        SmartFolder,
+        r#"INSERT INTO smart_folders (user_id, name, definition) VALUES ($1, $2, $3)
+        RETURNING
+            id,
+            user_id,
+            name,
+            definition as "definition!: Json<SmartFolderDefinition>",
+            created_at,
+            updated_at"#,
+        user.id,                                               // $1
+        input.name,                                            // $2
+        Json(SmartFolderDefinition::from(&input.filter)) as _, // $3
+    )
+    .fetch_one(db_pool)
+    .await
+    .map_err(|_| ValidationErrors::new())
+}
+
+pub async fn update_smart_folder(
+    smart_folder: &SmartFolder<'_>,
+    input: &SmartFolderInput,
+) -> Result<(), ValidationErrors> {
+    input.validate()?;
+
+    let db_pool = db_pool().await;
+
+    if input.name.to_lowercase() != smart_folder.name.to_lowercase()
+        && smart_folder_name_exists(&smart_folder.user().await, &input.name).await
+    {
+        let mut validation_errors = ValidationErrors::new();
+
+        validation_errors.add("name", ERROR_ALREADY_EXISTS.clone());
+
+        return Err(validation_errors);
+    }
+
+    sqlx::query!(
+        "UPDATE smart_folders SET name = $2, definition = $3 WHERE id = $1",
+        smart_folder.id,                                       // $1
+        input.name,                                            // $2
+        Json(SmartFolderDefinition::from(&input.filter)) as _, // $3
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+    .map_err(|_| ValidationErrors::new())
+}
+
+async fn smart_folder_name_exists(user: &User<'_>, name: &str) -> bool {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "SELECT id FROM smart_folders WHERE user_id = $1 AND LOWER(name) = $2 LIMIT 1",
+        user.id,             // $1
+        name.to_lowercase()  // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::ConflictPolicy;
+    use crate::inputs::{FolderItemsFilter, ItemMetadataInput, ItemProperties};
+    use crate::server::commands::{move_file, update_file_metadata};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_list_items_matching_a_smart_folder() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+
+        let input = SmartFolderInput {
+            name: fake_name(),
+            filter: FolderItemsFilter {
+                name_pattern: Some("*.jpg".to_owned()),
+                media_type: Some("image/".to_owned()),
+                ..Default::default()
+            },
+        };
+
+        let smart_folder = insert_smart_folder(&user, &input)
+            .await
+            .expect("Could not insert smart folder");
+
+        let folder_items = get_all_smart_folder_items(&smart_folder, &FolderItemsQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(folder_items.len(), 2);
+        assert!(folder_items.iter().all(|folder_item| folder_item.is_file));
+    }
+
+    #[tokio::test]
+    async fn should_update_a_smart_folder_to_match_tagged_items() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let files = insert_test_files(2, Some(&user)).await;
+        let metadata_input = ItemMetadataInput {
+            id: files[0].id,
+            description: String::new(),
+            properties: ItemProperties::from([("Holidays".to_owned(), String::new())]),
+        };
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        update_file_metadata(&files[0], &metadata_input).await.unwrap();
+
+        let smart_folder = insert_smart_folder(
+            &user,
+            &SmartFolderInput {
+                name: fake_name(),
+                filter: FolderItemsFilter {
+                    media_type: Some("image_".to_owned()),
+                    ..Default::default()
+                },
+            },
+        )
+        .await
+        .expect("Could not insert smart folder");
+
+        let folder_items = get_all_smart_folder_items(&smart_folder, &FolderItemsQuery::default())
+            .await
+            .unwrap();
+
+        assert!(folder_items.is_empty());
+
+        let input = SmartFolderInput {
+            name: fake_name(),
+            filter: FolderItemsFilter {
+                tags: vec!["holidays".to_owned()],
+                ..Default::default()
+            },
+        };
+
+        update_smart_folder(&smart_folder, &input)
+            .await
+            .expect("Could not update smart folder");
+
+        let smart_folder = get_smart_folder_by_id(smart_folder.id, &user).await.unwrap();
+        let folder_items = get_all_smart_folder_items(&smart_folder, &FolderItemsQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(smart_folder.name, input.name);
+        assert_eq!(folder_items.len(), 1);
+        assert_eq!(folder_items[0].id, files[0].id);
+    }
+
+    #[tokio::test]
+    async fn should_read_a_stored_definition() {
+        let definition = serde_json::from_str::<SmartFolderDefinition>(
+            r#"{"version":"2","name_pattern":"beach","tags":["holidays"]}"#,
+        )
+        .expect("Could not parse definition");
+        let filter = definition.filter();
+
+        assert_eq!(filter.name_pattern.as_deref(), Some("beach"));
+        assert_eq!(filter.tags, vec!["holidays".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn should_upgrade_a_definition_saved_before_tags() {
+        let definition = serde_json::from_str::<SmartFolderDefinition>(r#"{"version":"1","name_pattern":"beach"}"#)
+            .expect("Could not parse definition");
+        let filter = definition.filter();
+
+        assert_eq!(filter.name_pattern.as_deref(), Some("beach"));
+        assert!(filter.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_not_read_a_definition_with_an_unknown_version() {
+        let result = serde_json::from_str::<SmartFolderDefinition>(r#"{"version":"99","name_pattern":"beach"}"#);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::enums::FileVisibility;
+use crate::server::db_pool;
+use crate::server::models::{File, Folder, FolderItem, User};
+
+pub async fn file_is_starred(file: &File<'_>) -> bool {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "SELECT id FROM stars WHERE user_id = $1 AND file_id = $2 LIMIT 1",
+        file.user_id, // $1
+        file.id,      // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .is_ok()
+}
+
+pub async fn folder_is_starred(folder: &Folder<'_>) -> bool {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "SELECT id FROM stars WHERE user_id = $1 AND folder_id = $2 LIMIT 1",
+        folder.user_id, // $1
+        folder.id,      // $2
+    )
+    .fetch_one(db_pool)
+    .await
+    .is_ok()
+}
+
+pub async fn get_all_starred_items<'a>(user: &User<'_>) -> sqlx::Result<Vec<FolderItem<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        FolderItem,
+        r#"SELECT
+            id as "id!",
+            user_id as "user_id!",
+            parent_folder_id,
+            is_file as "is_file!",
+            name as "name!",
+            "visibility!: FileVisibility",
+            created_at as "created_at!",
+            updated_at
+        FROM (
+            (
+                SELECT
+                    f.id,
+                    f.user_id,
+                    f.parent_folder_id,
+                    FALSE as is_file,
+                    f.name,
+                    f.visibility as "visibility!: FileVisibility",
+                    f.created_at,
+                    f.updated_at
+                FROM folders AS f, stars AS s
+                WHERE s.user_id = $1 AND s.folder_id = f.id AND f.trashed_at IS NULL
+            ) UNION ALL (
+                SELECT
+                    f.id,
+                    f.user_id,
+                    f.parent_folder_id,
+                    TRUE as is_file,
+                    f.name,
+                    f.visibility as "visibility!: FileVisibility",
+                    f.created_at,
+                    f.updated_at
+                FROM files AS f, stars AS s
+                WHERE s.user_id = $1 AND s.file_id = f.id AND f.trashed_at IS NULL
+            )
+        ) AS starred_items ORDER BY is_file ASC, name ASC"#,
+        user.id, // $1
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn star_file(user: &User<'_>, file: &File<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "INSERT INTO stars (user_id, file_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user.id, // $1
+        file.id, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn star_folder(user: &User<'_>, folder: &Folder<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "INSERT INTO stars (user_id, folder_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user.id,   // $1
+        folder.id, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn unstar_file(user: &User<'_>, file: &File<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "DELETE FROM stars WHERE user_id = $1 AND file_id = $2",
+        user.id, // $1
+        file.id, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn unstar_folder(user: &User<'_>, folder: &Folder<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "DELETE FROM stars WHERE user_id = $1 AND folder_id = $2",
+        user.id,   // $1
+        folder.id, // $2
+    )
+    .execute(db_pool)
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_starred_items_by_user() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+        let folder = insert_test_folder(Some(&user), None).await;
+
+        insert_test_files(3, Some(&user)).await;
+
+        star_file(&user, &file).await.expect("Could not star file");
+        star_folder(&user, &folder).await.expect("Could not star folder");
+
+        let result = get_all_starred_items(&user).await;
+
+        assert!(result.is_ok());
+
+        let starred_items = result.unwrap();
+
+        assert_eq!(starred_items.len(), 2);
+        assert!(
+            starred_items
+                .iter()
+                .all(|item| item.id == file.id || item.id == folder.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_unstar_a_file() {
+        let user = insert_test_user().await;
+        let file = insert_test_file(Some(&user)).await;
+
+        star_file(&user, &file).await.expect("Could not star file");
+
+        let result = unstar_file(&user, &file).await;
+
+        assert!(result.is_ok());
+
+        let starred_items = get_all_starred_items(&user).await.unwrap();
+
+        assert!(starred_items.is_empty());
+    }
+}
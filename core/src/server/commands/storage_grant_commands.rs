@@ -0,0 +1,124 @@
+use bytesize::ByteSize;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::server::db_pool;
+use crate::server::models::{StorageGrant, User};
+
+use super::{get_user_by_id, update_user_over_quota_since};
+
+pub async fn get_active_storage_grants_size(user: &User<'_>) -> sqlx::Result<ByteSize> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        r#"SELECT COALESCE(SUM(byte_size), 0)::bigint AS "byte_size!" FROM storage_grants
+        WHERE user_id = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires_at > current_timestamp)"#,
+        user.id
+    )
+    .fetch_one(db_pool)
+    .await
+    .map(|row| ByteSize(row.byte_size as u64))
+}
+
+pub async fn get_all_storage_grants<'a>(user: &User<'_>) -> sqlx::Result<Vec<StorageGrant<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        StorageGrant,
+        "SELECT * FROM storage_grants WHERE user_id = $1 ORDER BY created_at DESC",
+        user.id
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_storage_grant_by_id<'a>(id: Uuid) -> sqlx::Result<StorageGrant<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(StorageGrant, "SELECT * FROM storage_grants WHERE id = $1 LIMIT 1", id)
+        .fetch_one(db_pool)
+        .await
+}
+
+pub async fn insert_storage_grant<'a>(
+    user: &User<'_>,
+    size: ByteSize,
+    reason: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> anyhow::Result<StorageGrant<'a>> {
+    if size.as_u64() == 0 {
+        return Err(anyhow::anyhow!("Size can't be zero"));
+    }
+
+    if expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err(anyhow::anyhow!("Expiration must be in the future"));
+    }
+
+    let db_pool = db_pool().await;
+
+    let storage_grant = sqlx::query_as!(
+        StorageGrant,
+        "INSERT INTO storage_grants (user_id, byte_size, reason, expires_at) VALUES ($1, $2, $3, $4) RETURNING *",
+        user.id,                       // $1
+        i64::try_from(size.as_u64())?, // $2
+        reason,                        // $3
+        expires_at                     // $4
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    update_user_over_quota_since(user).await?;
+
+    Ok(storage_grant)
+}
+
+pub async fn revoke_storage_grant(storage_grant: &StorageGrant<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE storage_grants SET revoked_at = current_timestamp WHERE revoked_at IS NULL AND id = $1",
+        storage_grant.id
+    )
+    .execute(db_pool)
+    .await?;
+
+    let user = get_user_by_id(storage_grant.user_id).await?;
+
+    update_user_over_quota_since(&user).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_grant_extra_space_and_revoke_it() {
+        let user = insert_test_user().await;
+        let total_space = user.total_space().await;
+
+        let storage_grant = insert_storage_grant(&user, ByteSize::gib(2), "Beta tester", None)
+            .await
+            .unwrap();
+
+        assert!(storage_grant.is_active());
+        assert_eq!(user.total_space().await, total_space + ByteSize::gib(2));
+        assert!(
+            insert_storage_grant(&user, ByteSize::gib(1), "", Some(Utc::now()))
+                .await
+                .is_err()
+        );
+
+        revoke_storage_grant(&storage_grant).await.unwrap();
+
+        let storage_grant = get_storage_grant_by_id(storage_grant.id).await.unwrap();
+        let user = get_user_by_id(user.id).await.unwrap();
+
+        assert!(!storage_grant.is_active());
+        assert_eq!(user.total_space().await, total_space);
+        assert_eq!(get_all_storage_grants(&user).await.unwrap().len(), 1);
+    }
+}
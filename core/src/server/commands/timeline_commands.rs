@@ -0,0 +1,133 @@
+use crate::enums::{FileVisibility, TimelineGrouping};
+use crate::inputs::TimelineQuery;
+use crate::server::db_pool;
+use crate::server::models::{TimelineGroup, TimelineItem, User};
+
+pub async fn get_all_timeline_items<'a>(user: &User<'_>, query: &TimelineQuery) -> sqlx::Result<Vec<TimelineItem<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        TimelineItem,
+        r#"WITH photos AS (
+            SELECT
+                id,
+                user_id,
+                parent_folder_id,
+                name,
+                visibility,
+                COALESCE(taken_at, created_at) AS captured_at,
+                created_at,
+                updated_at
+            FROM files
+            WHERE user_id = $1 AND media_type LIKE 'image/%' AND (id = $3 OR trashed_at IS NULL)
+        ) SELECT
+            p.id as "id!",
+            p.user_id as "user_id!",
+            p.parent_folder_id,
+            p.name as "name!",
+            p.visibility as "visibility!: FileVisibility",
+            p.captured_at as "captured_at!",
+            p.created_at as "created_at!",
+            p.updated_at
+        FROM photos AS p LEFT JOIN photos AS cp ON cp.id = $3
+        WHERE ($2::date IS NULL OR p.captured_at < $2::date + 1)
+            AND ($3::uuid IS NULL OR (p.captured_at, p.id) < (cp.captured_at, cp.id))
+        ORDER BY p.captured_at DESC, p.id DESC
+        LIMIT $4"#,
+        user.id,                               // $1
+        query.before,                          // $2
+        query.cursor,                          // $3
+        query.limit.map(|limit| limit as i64), // $4
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_timeline_groups(user: &User<'_>, grouping: TimelineGrouping) -> sqlx::Result<Vec<TimelineGroup>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        TimelineGroup,
+        r#"SELECT
+            date_trunc($2, COALESCE(taken_at, created_at) AT TIME ZONE 'UTC')::date AS "period!",
+            COUNT(*) AS "files_count!"
+        FROM files
+        WHERE user_id = $1 AND media_type LIKE 'image/%' AND trashed_at IS NULL
+        GROUP BY 1
+        ORDER BY 1 DESC"#,
+        user.id,              // $1
+        grouping.to_string(), // $2
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::commands::{get_file_by_id, move_file_to_trash};
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_get_timeline_items_by_pages() {
+        let user = insert_test_user().await;
+        let files = insert_test_files(3, Some(&user)).await;
+
+        let mut query = TimelineQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let first_page = get_all_timeline_items(&user, &query).await.unwrap();
+
+        assert_eq!(first_page.len(), 2);
+
+        query.cursor = first_page.last().map(|item| item.id);
+
+        let second_page = get_all_timeline_items(&user, &query).await.unwrap();
+
+        assert_eq!(second_page.len(), 1);
+        assert!(
+            files
+                .iter()
+                .all(|file| first_page.iter().chain(&second_page).any(|item| item.id == file.id))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_timeline_items_after_a_trashed_cursor() {
+        let user = insert_test_user().await;
+
+        insert_test_files(3, Some(&user)).await;
+
+        let mut query = TimelineQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let first_page = get_all_timeline_items(&user, &query).await.unwrap();
+        let cursor_file = get_file_by_id(first_page[1].id, None).await.unwrap();
+
+        move_file_to_trash(&cursor_file).await.unwrap();
+
+        query.cursor = Some(cursor_file.id);
+
+        let second_page = get_all_timeline_items(&user, &query).await.unwrap();
+
+        assert_eq!(second_page.len(), 1);
+        assert!(second_page.iter().all(|item| item.id != cursor_file.id));
+    }
+
+    #[tokio::test]
+    async fn should_get_timeline_groups_by_day() {
+        let user = insert_test_user().await;
+
+        insert_test_files(3, Some(&user)).await;
+
+        let groups = get_timeline_groups(&user, TimelineGrouping::Day).await.unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files_count, 3);
+    }
+}
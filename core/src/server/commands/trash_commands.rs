@@ -1,8 +1,17 @@
-use crate::enums::FileVisibility;
+use std::path::PathBuf;
+
+use chrono::{TimeDelta, Utc};
+use sqlx::PgConnection;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::enums::{ConflictPolicy, FileVisibility};
+use crate::inputs::ItemProperties;
 use crate::server::db_pool;
-use crate::server::models::{File, Folder, FolderItem, User};
+use crate::server::models::{File, Folder, FolderItem, TrashItem, User};
 
-use super::get_all_folder_items;
+use super::folder_commands::{folder_contains, merge_folder};
+use super::{NameResolution, require_within_quota, resolve_name_conflict, update_user_over_quota_since};
 
 pub async fn delete_file(file: &File<'_>) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
@@ -13,57 +22,94 @@ pub async fn delete_file(file: &File<'_>) -> sqlx::Result<()> {
 
     let _ = std::fs::remove_file(file.default_path());
 
+    update_user_over_quota_since(&file.user().await).await?;
+
     Ok(())
 }
 
 pub async fn delete_folder(folder: &Folder<'_>) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
 
-    let folder_items = get_all_folder_items(None, Some(folder)).await;
+    let deleted_paths = delete_folder_tree(&mut transaction, folder.id).await?;
 
-    if let Ok(folder_items) = folder_items {
-        futures::future::join_all(folder_items.iter().map(|item| async {
-            if item.is_file {
-                delete_file(&item.into()).await
-            } else {
-                delete_folder(&item.into()).await
-            }
-        }))
-        .await;
+    transaction.commit().await?;
+
+    for path in deleted_paths {
+        let _ = std::fs::remove_file(path);
     }
 
+    update_user_over_quota_since(&folder.user().await).await?;
+
+    Ok(())
+}
+
+/// Returns the paths of the deleted files, to be removed once the transaction is committed.
+pub(super) async fn delete_folder_tree(conn: &mut PgConnection, folder_id: Uuid) -> sqlx::Result<Vec<PathBuf>> {
+    let folder_ids = sqlx::query!(
+        r#"WITH RECURSIVE child_folders AS (
+            SELECT id FROM folders WHERE id = $1
+            UNION ALL
+            SELECT f.id FROM folders AS f, child_folders AS cf
+            WHERE f.parent_folder_id = cf.id AND (f.trashed_at IS NULL OR f.trashed_with_folder_id = $1)
+        ) SELECT id AS "id!" FROM child_folders"#,
+        folder_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| row.id)
+    .collect::<Vec<_>>();
+
     sqlx::query!(
-        "UPDATE folders SET parent_folder_id = NULL WHERE trashed_at IS NOT NULL AND parent_folder_id = $1",
-        folder.id
+        "UPDATE folders SET parent_folder_id = NULL WHERE parent_folder_id = ANY($1) AND NOT id = ANY($1)",
+        &folder_ids
     )
-    .execute(db_pool)
+    .execute(&mut *conn)
     .await?;
 
     sqlx::query!(
-        "UPDATE files SET parent_folder_id = NULL WHERE trashed_at IS NOT NULL AND parent_folder_id = $1",
-        folder.id
+        "UPDATE files SET parent_folder_id = NULL
+        WHERE parent_folder_id = ANY($1) AND trashed_at IS NOT NULL AND trashed_with_folder_id IS DISTINCT FROM $2",
+        &folder_ids, // $1
+        folder_id,   // $2
     )
-    .execute(db_pool)
+    .execute(&mut *conn)
     .await?;
 
-    sqlx::query!("DELETE FROM folders WHERE id = $1", folder.id)
-        .execute(db_pool)
+    let deleted_files = sqlx::query_as!(
+        File,
+        r#"DELETE FROM files WHERE parent_folder_id = ANY($1)
+        RETURNING
+            id,
+            user_id,
+            parent_folder_id,
+            name,
+            visibility as "visibility!: FileVisibility",
+            media_type,
+            byte_size,
+            md5_checksum,
+            description,
+            properties as "properties!: Json<ItemProperties>",
+            trashed_at,
+            created_at,
+            updated_at"#,
+        &folder_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM folders WHERE id = ANY($1)", &folder_ids)
+        .execute(&mut *conn)
         .await?;
 
-    Ok(())
+    Ok(deleted_files.iter().map(|file| file.default_path()).collect())
 }
 
-pub async fn empty_trash(user: &User<'_>) -> sqlx::Result<()> {
+pub async fn empty_trash(user: &User<'_>) -> anyhow::Result<()> {
     let trash_items = get_all_trash_items(user).await?;
 
-    futures::future::join_all(trash_items.iter().map(|item| async {
-        if item.is_file {
-            delete_file(&item.into()).await
-        } else {
-            delete_folder(&item.into()).await
-        }
-    }))
-    .await;
+    delete_trash_items(&trash_items.iter().map(FolderItem::from).collect::<Vec<_>>()).await?;
 
     Ok(())
 }
@@ -73,38 +119,44 @@ pub async fn folder_is_trashed(folder: &Folder<'_>) -> bool {
         return true;
     }
 
-    if let Some(parent_folder_id) = folder.parent_folder_id {
-        let db_pool = db_pool().await;
+    let Some(parent_folder_id) = folder.parent_folder_id else {
+        return false;
+    };
 
-        return sqlx::query!(
-            "WITH RECURSIVE parent_folders AS (
-                SELECT id, parent_folder_id, trashed_at FROM folders WHERE id = $1
-                UNION ALL
-                SELECT f.id, f.parent_folder_id, f.trashed_at FROM folders as f, parent_folders AS pf
-                WHERE f.id = pf.parent_folder_id
-            ) SELECT id FROM parent_folders WHERE trashed_at IS NOT NULL LIMIT 1",
-            parent_folder_id
-        )
-        .fetch_one(db_pool)
-        .await
-        .is_ok();
+    match db_pool().await.acquire().await {
+        Ok(mut conn) => folder_id_is_trashed(&mut conn, parent_folder_id).await,
+        Err(_) => false,
     }
+}
 
-    false
+pub(super) async fn folder_id_is_trashed(conn: &mut PgConnection, folder_id: Uuid) -> bool {
+    sqlx::query!(
+        "WITH RECURSIVE parent_folders AS (
+            SELECT id, parent_folder_id, trashed_at FROM folders WHERE id = $1
+            UNION ALL
+            SELECT f.id, f.parent_folder_id, f.trashed_at FROM folders as f, parent_folders AS pf
+            WHERE f.id = pf.parent_folder_id
+        ) SELECT id FROM parent_folders WHERE trashed_at IS NOT NULL LIMIT 1",
+        folder_id
+    )
+    .fetch_one(conn)
+    .await
+    .is_ok()
 }
 
-pub async fn get_all_trash_items<'a>(user: &User<'_>) -> sqlx::Result<Vec<FolderItem<'a>>> {
+pub async fn get_all_trash_items<'a>(user: &User<'_>) -> sqlx::Result<Vec<TrashItem<'a>>> {
     let db_pool = db_pool().await;
 
     sqlx::query_as!(
-        FolderItem,
+        TrashItem,
         r#"SELECT
             id as "id!",
+            is_file as "is_file!",
             user_id as "user_id!",
             parent_folder_id,
-            is_file as "is_file!",
             name as "name!",
             "visibility!: FileVisibility",
+            trashed_at as "trashed_at!",
             created_at as "created_at!",
             updated_at
         FROM (
@@ -116,9 +168,12 @@ pub async fn get_all_trash_items<'a>(user: &User<'_>) -> sqlx::Result<Vec<Folder
                     FALSE as is_file,
                     name,
                     visibility as "visibility!: FileVisibility",
+                    trashed_at,
                     created_at,
                     updated_at
-                FROM folders WHERE user_id = $1 AND trashed_at IS NOT NULL ORDER BY name ASC
+                FROM folders
+                WHERE user_id = $1 AND trashed_at IS NOT NULL AND trashed_with_folder_id IS NULL
+                ORDER BY name ASC
             ) UNION ALL (
                 SELECT
                     id,
@@ -127,9 +182,12 @@ pub async fn get_all_trash_items<'a>(user: &User<'_>) -> sqlx::Result<Vec<Folder
                     TRUE as is_file,
                     name,
                     visibility as "visibility!: FileVisibility",
+                    trashed_at,
                     created_at,
                     updated_at
-                FROM files WHERE user_id = $1 AND trashed_at IS NOT NULL ORDER BY name ASC
+                FROM files
+                WHERE user_id = $1 AND trashed_at IS NOT NULL AND trashed_with_folder_id IS NULL
+                ORDER BY name ASC
             )
         )"#,
         user.id, // $1
@@ -151,68 +209,478 @@ pub async fn move_file_to_trash(file: &File<'_>) -> sqlx::Result<()> {
         .map(|_| ())
 }
 
+/// The content is trashed with the folder, so it only shows up in the trash through the folder.
 pub async fn move_folder_to_trash(folder: &Folder<'_>) -> sqlx::Result<()> {
     if folder.trashed_at.is_some() {
         return Ok(());
     }
 
     let db_pool = db_pool().await;
+    let mut conn = db_pool.acquire().await?;
 
-    sqlx::query!(
-        "UPDATE folders SET trashed_at = current_timestamp WHERE id = $1",
-        folder.id
+    trash_folder_tree(&mut conn, folder.id).await
+}
+
+pub async fn purge_expired_trash_items() -> anyhow::Result<usize> {
+    let db_pool = db_pool().await;
+
+    let users = sqlx::query_as!(
+        User,
+        "SELECT * FROM users AS u
+        WHERE EXISTS (SELECT id FROM files WHERE user_id = u.id AND trashed_at IS NOT NULL)
+            OR EXISTS (SELECT id FROM folders WHERE user_id = u.id AND trashed_at IS NOT NULL)"
     )
-    .execute(db_pool)
-    .await
-    .map(|_| ())
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut expired_items = Vec::new();
+
+    for user in users {
+        let trash_retention_days = user.entitlements().await.trash_retention_days;
+        let expired_before = Utc::now() - TimeDelta::days(trash_retention_days as i64);
+
+        expired_items.extend(
+            get_all_trash_items(&user)
+                .await?
+                .iter()
+                .filter(|item| item.trashed_at < expired_before)
+                .map(FolderItem::from),
+        );
+    }
+
+    delete_trash_items(&expired_items).await
 }
 
-pub async fn restore_file(file: &File<'_>) -> sqlx::Result<()> {
+/// Fails while the original folder is in the trash, so callers have to pick another one with `restore_file_to_folder`.
+pub async fn restore_file(file: &File<'_>, policy: ConflictPolicy) -> anyhow::Result<()> {
     if file.trashed_at.is_none() {
         return Ok(());
     }
 
+    let parent_folder = file.parent_folder().await;
+
+    if let Some(parent_folder) = &parent_folder
+        && parent_folder.is_trashed().await
+    {
+        return Err(anyhow::anyhow!("Original folder is unavailable"));
+    }
+
+    restore_file_to_folder(file, parent_folder.as_ref(), policy).await
+}
+
+pub async fn restore_file_to_folder(
+    file: &File<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
+    let user = file.user().await;
+
+    require_within_quota(&user).await?;
+
     let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    untrash_file(&mut transaction, &user, file, target_folder, policy).await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+pub async fn restore_folder(folder: &Folder<'_>, policy: ConflictPolicy) -> anyhow::Result<()> {
+    if folder.trashed_at.is_none() {
+        return Ok(());
+    }
 
-    let parent_folder_id = if let Some(parent_folder) = file.parent_folder().await
-        && !parent_folder.is_trashed().await
+    let parent_folder = folder.parent_folder().await;
+
+    if let Some(parent_folder) = &parent_folder
+        && parent_folder.is_trashed().await
     {
-        file.parent_folder_id
+        return Err(anyhow::anyhow!("Original folder is unavailable"));
+    }
+
+    restore_folder_to_folder(folder, parent_folder.as_ref(), policy).await
+}
+
+pub async fn restore_folder_to_folder(
+    folder: &Folder<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
+    let user = folder.user().await;
+
+    require_within_quota(&user).await?;
+
+    let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    untrash_folder(&mut transaction, &user, folder, target_folder, policy).await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+pub(super) async fn trash_folder_tree(conn: &mut PgConnection, folder_id: Uuid) -> sqlx::Result<()> {
+    sqlx::query!(
+        "WITH RECURSIVE child_folders AS (
+            SELECT id FROM folders WHERE id = $1 AND trashed_at IS NULL
+            UNION ALL
+            SELECT f.id FROM folders AS f, child_folders AS cf
+            WHERE f.parent_folder_id = cf.id AND f.trashed_at IS NULL
+        ), trashed_files AS (
+            UPDATE files SET trashed_at = current_timestamp, trashed_with_folder_id = $1
+            WHERE parent_folder_id IN (SELECT id FROM child_folders) AND trashed_at IS NULL
+        ) UPDATE folders SET
+            trashed_at = current_timestamp,
+            trashed_with_folder_id = CASE WHEN id = $1 THEN NULL ELSE $1 END
+        WHERE id IN (SELECT id FROM child_folders)",
+        folder_id
+    )
+    .execute(conn)
+    .await
+    .map(|_| ())
+}
+
+pub(super) async fn trash_replaced_item(conn: &mut PgConnection, item: &FolderItem<'_>) -> sqlx::Result<()> {
+    if item.is_file {
+        sqlx::query!("UPDATE files SET trashed_at = current_timestamp WHERE id = $1", item.id)
+            .execute(conn)
+            .await
+            .map(|_| ())
     } else {
-        None
+        trash_folder_tree(conn, item.id).await
+    }
+}
+
+pub(super) async fn untrash_file(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    file: &File<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
+    if file.trashed_at.is_none() {
+        return Err(anyhow::anyhow!("File is not in the trash"));
+    }
+
+    if let Some(target_folder) = target_folder
+        && (target_folder.user_id != file.user_id || folder_id_is_trashed(&mut *conn, target_folder.id).await)
+    {
+        return Err(anyhow::anyhow!("Invalid target folder"));
+    }
+
+    let target_folder_id = target_folder.map(|tf| tf.id);
+
+    let name = match resolve_name_conflict(&mut *conn, user, &file.into(), target_folder_id, policy).await? {
+        NameResolution::Available(name) => name,
+        NameResolution::Replace(name, existing_item) => {
+            trash_replaced_item(&mut *conn, &existing_item).await?;
+
+            name
+        }
+        NameResolution::MergeInto(_) => return Err(anyhow::anyhow!("Files can't be merged")),
     };
 
     sqlx::query!(
-        "UPDATE files SET trashed_at = NULL, parent_folder_id = $2 WHERE id = $1",
+        "UPDATE files SET trashed_at = NULL, trashed_with_folder_id = NULL, parent_folder_id = $2, name = $3
+        WHERE id = $1",
         file.id,          // $1
-        parent_folder_id  // $2
+        target_folder_id, // $2
+        name,             // $3
     )
-    .execute(db_pool)
-    .await
-    .map(|_| ())
+    .execute(conn)
+    .await?;
+
+    Ok(())
 }
 
-pub async fn restore_folder(folder: &Folder<'_>) -> sqlx::Result<()> {
+pub(super) async fn untrash_folder(
+    conn: &mut PgConnection,
+    user: &User<'_>,
+    folder: &Folder<'_>,
+    target_folder: Option<&Folder<'_>>,
+    policy: ConflictPolicy,
+) -> anyhow::Result<()> {
     if folder.trashed_at.is_none() {
-        return Ok(());
+        return Err(anyhow::anyhow!("Folder is not in the trash"));
     }
 
-    let db_pool = db_pool().await;
-
-    let parent_folder_id = if let Some(parent_folder) = folder.parent_folder().await
-        && !parent_folder.is_trashed().await
+    if let Some(target_folder) = target_folder
+        && (target_folder.user_id != folder.user_id
+            || folder_contains(folder, target_folder).await
+            || folder_id_is_trashed(&mut *conn, target_folder.id).await)
     {
-        folder.parent_folder_id
-    } else {
-        None
+        return Err(anyhow::anyhow!("Invalid target folder"));
+    }
+
+    let target_folder_id = target_folder.map(|tf| tf.id);
+
+    untrash_folder_tree(&mut *conn, folder.id).await?;
+
+    let name = match resolve_name_conflict(&mut *conn, user, &folder.into(), target_folder_id, policy).await? {
+        NameResolution::Available(name) => name,
+        NameResolution::Replace(name, existing_item) => {
+            trash_replaced_item(&mut *conn, &existing_item).await?;
+
+            name
+        }
+        NameResolution::MergeInto(existing_folder) => return merge_folder(conn, user, folder, &existing_folder).await,
     };
 
     sqlx::query!(
-        "UPDATE folders SET parent_folder_id = $2, trashed_at = NULL WHERE id = $1",
+        "UPDATE folders SET parent_folder_id = $2, name = $3, trashed_at = NULL, trashed_with_folder_id = NULL
+        WHERE id = $1",
         folder.id,        // $1
-        parent_folder_id  // $2
+        target_folder_id, // $2
+        name,             // $3
     )
-    .execute(db_pool)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn untrash_folder_tree(conn: &mut PgConnection, folder_id: Uuid) -> sqlx::Result<()> {
+    sqlx::query!(
+        "WITH restored_files AS (
+            UPDATE files SET trashed_at = NULL, trashed_with_folder_id = NULL WHERE trashed_with_folder_id = $1
+        ) UPDATE folders SET trashed_at = NULL, trashed_with_folder_id = NULL WHERE trashed_with_folder_id = $1",
+        folder_id
+    )
+    .execute(conn)
     .await
     .map(|_| ())
 }
+
+async fn delete_trash_items(trash_items: &[FolderItem<'_>]) -> anyhow::Result<usize> {
+    let mut failed_count = 0;
+
+    for item in trash_items {
+        let result = if item.is_file {
+            delete_file(&item.into()).await
+        } else {
+            delete_folder(&item.into()).await
+        };
+
+        if let Err(error) = result {
+            tracing::error!("Could not delete trash item {}: {error}", item.id);
+
+            failed_count += 1;
+        }
+    }
+
+    if failed_count > 0 {
+        return Err(anyhow::anyhow!(
+            "Could not delete {failed_count} of {} trash items",
+            trash_items.len()
+        ));
+    }
+
+    Ok(trash_items.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inputs::FolderInput;
+    use crate::server::commands::{get_file_by_id, get_folder_by_id, insert_folder, move_file};
+    use crate::server::config::USERS_CONFIG;
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_restore_a_file_to_another_folder_when_its_folder_is_trashed() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let target_folder = insert_test_folder(Some(&user), None).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        move_file(&file, Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file_to_trash(&get_file_by_id(file.id, None).await.unwrap())
+            .await
+            .unwrap();
+        move_folder_to_trash(&folder).await.unwrap();
+
+        let file = get_file_by_id(file.id, None).await.unwrap();
+        let folder = get_folder_by_id(folder.id, None).await.unwrap();
+        let trash_item = get_all_trash_items(&user)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|trash_item| trash_item.id == file.id)
+            .unwrap();
+
+        assert!(!trash_item.can_be_restored_in_place().await);
+        assert!(
+            restore_file_to_folder(&file, Some(&folder), ConflictPolicy::Fail)
+                .await
+                .is_err()
+        );
+
+        restore_file_to_folder(&file, Some(&target_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+
+        let file = get_file_by_id(file.id, None).await.unwrap();
+
+        assert!(file.trashed_at.is_none());
+        assert_eq!(file.parent_folder_id, Some(target_folder.id));
+    }
+
+    #[tokio::test]
+    async fn should_not_restore_items_in_place_when_their_folder_is_trashed() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        move_file(&file, Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file_to_trash(&get_file_by_id(file.id, None).await.unwrap())
+            .await
+            .unwrap();
+        move_folder_to_trash(&child_folder).await.unwrap();
+        move_folder_to_trash(&folder).await.unwrap();
+
+        let file = get_file_by_id(file.id, None).await.unwrap();
+        let child_folder = get_folder_by_id(child_folder.id, None).await.unwrap();
+
+        assert!(restore_file(&file, ConflictPolicy::Fail).await.is_err());
+        assert!(restore_folder(&child_folder, ConflictPolicy::Fail).await.is_err());
+        assert!(get_file_by_id(file.id, None).await.unwrap().trashed_at.is_some());
+        assert!(
+            get_folder_by_id(child_folder.id, None)
+                .await
+                .unwrap()
+                .trashed_at
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_trash_and_restore_a_folder_with_its_content() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let child_folder = insert_test_folder(Some(&user), Some(&folder)).await;
+        let file = insert_test_file(Some(&user)).await;
+
+        move_file(&file, Some(&child_folder), ConflictPolicy::Fail)
+            .await
+            .unwrap();
+        move_folder_to_trash(&folder).await.unwrap();
+
+        let trash_item_ids = get_all_trash_items(&user)
+            .await
+            .unwrap()
+            .iter()
+            .map(|trash_item| trash_item.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(trash_item_ids, vec![folder.id]);
+        assert!(get_file_by_id(file.id, None).await.unwrap().trashed_at.is_some());
+        assert!(
+            get_folder_by_id(child_folder.id, None)
+                .await
+                .unwrap()
+                .trashed_at
+                .is_some()
+        );
+
+        let folder = get_folder_by_id(folder.id, None).await.unwrap();
+
+        restore_folder(&folder, ConflictPolicy::Fail).await.unwrap();
+
+        assert!(get_all_trash_items(&user).await.unwrap().is_empty());
+        assert!(get_file_by_id(file.id, None).await.unwrap().trashed_at.is_none());
+        assert!(
+            get_folder_by_id(child_folder.id, None)
+                .await
+                .unwrap()
+                .trashed_at
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_keep_items_trashed_on_their_own_when_deleting_a_folder() {
+        let user = insert_test_user().await;
+        let folder = insert_test_folder(Some(&user), None).await;
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file(&files[1], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file_to_trash(&get_file_by_id(files[0].id, None).await.unwrap())
+            .await
+            .unwrap();
+        move_folder_to_trash(&folder).await.unwrap();
+        delete_folder(&folder).await.unwrap();
+
+        let trashed_file = get_file_by_id(files[0].id, None).await.unwrap();
+
+        assert!(trashed_file.trashed_at.is_some());
+        assert_eq!(trashed_file.parent_folder_id, None);
+        assert!(get_file_by_id(files[1].id, None).await.is_err());
+        assert!(get_folder_by_id(folder.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_merge_a_restored_folder_with_its_items_trashed_on_their_own() {
+        let user = insert_test_user().await;
+        let same_name_folder = insert_test_folder(Some(&user), None).await;
+        let parent_folder = insert_test_folder(Some(&user), None).await;
+        let input = FolderInput {
+            parent_folder_id: Some(parent_folder.id),
+            name: same_name_folder.name.to_string(),
+            visibility: FileVisibility::Private,
+        };
+        let folder = insert_folder(&user, &input).await.unwrap();
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file(&files[0], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file(&files[1], Some(&folder), ConflictPolicy::Fail).await.unwrap();
+        move_file_to_trash(&get_file_by_id(files[0].id, None).await.unwrap())
+            .await
+            .unwrap();
+        move_folder_to_trash(&folder).await.unwrap();
+
+        let folder = get_folder_by_id(folder.id, None).await.unwrap();
+
+        restore_folder_to_folder(&folder, None, ConflictPolicy::Merge)
+            .await
+            .unwrap();
+
+        let trashed_file = get_file_by_id(files[0].id, None).await.unwrap();
+        let restored_file = get_file_by_id(files[1].id, None).await.unwrap();
+
+        assert!(trashed_file.trashed_at.is_some());
+        assert_eq!(trashed_file.parent_folder_id, Some(same_name_folder.id));
+        assert!(restored_file.trashed_at.is_none());
+        assert_eq!(restored_file.parent_folder_id, Some(same_name_folder.id));
+        assert!(get_folder_by_id(folder.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_purge_items_trashed_before_the_retention_period() {
+        let user = insert_test_user().await;
+        let files = insert_test_files(2, Some(&user)).await;
+
+        move_file_to_trash(&files[0]).await.unwrap();
+        move_file_to_trash(&files[1]).await.unwrap();
+
+        sqlx::query!(
+            "UPDATE files SET trashed_at = current_timestamp - make_interval(days => $2) WHERE id = $1",
+            files[0].id,                                       // $1
+            USERS_CONFIG.free_trash_retention_days as i32 + 1, // $2
+        )
+        .execute(db_pool().await)
+        .await
+        .unwrap();
+
+        let result = purge_expired_trash_items().await;
+
+        assert!(result.is_ok_and(|count| count >= 1));
+        assert!(get_file_by_id(files[0].id, None).await.is_err());
+        assert!(get_file_by_id(files[1].id, None).await.is_ok());
+    }
+}
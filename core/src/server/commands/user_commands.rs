@@ -1,9 +1,13 @@
+use chrono::Utc;
+
 use sdk::auth_client::UserInfo;
 
+use crate::enums::OverQuotaPolicy;
+use crate::server::config::USERS_CONFIG;
 use crate::server::db_pool;
 use crate::server::models::User;
 
-use super::finish_all_sessions_by_user;
+use super::{empty_trash, finish_all_sessions_by_user};
 
 pub async fn disable_user(user: &User<'_>) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
@@ -24,6 +28,58 @@ pub async fn disable_user(user: &User<'_>) -> sqlx::Result<()> {
     Ok(())
 }
 
+pub async fn enforce_over_quota_policy() -> anyhow::Result<usize> {
+    let db_pool = db_pool().await;
+
+    // Nobody has less space than the free quota, so anyone below it can be skipped.
+    let users = sqlx::query_as!(
+        User,
+        "SELECT * FROM users AS u WHERE disabled_at IS NULL AND (
+            over_quota_since IS NOT NULL
+            OR (SELECT COALESCE(SUM(byte_size), 0) FROM files WHERE user_id = u.id) > $1
+        )",
+        USERS_CONFIG.free_quota().as_u64() as i64
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut enforced_count = 0;
+
+    for user in users {
+        let user = match update_user_over_quota_since(&user).await {
+            Ok(user) => user,
+            Err(error) => {
+                tracing::error!("Could not update the over quota state of user {}: {error}", user.id);
+
+                continue;
+            }
+        };
+
+        if !user
+            .over_quota_grace_ends_at()
+            .is_some_and(|grace_ends_at| grace_ends_at <= Utc::now())
+        {
+            continue;
+        }
+
+        let result = match user.over_quota_policy() {
+            OverQuotaPolicy::ReadOnly => continue,
+            OverQuotaPolicy::PurgeTrash => empty_trash(&user).await,
+            OverQuotaPolicy::Disable => disable_user(&user).await.map_err(anyhow::Error::from),
+        };
+
+        if let Err(error) = result {
+            tracing::error!("Could not enforce the over quota policy of user {}: {error}", user.id);
+
+            continue;
+        }
+
+        enforced_count += 1;
+    }
+
+    Ok(enforced_count)
+}
+
 pub async fn get_user_by_session_token<'a>(token: &str) -> sqlx::Result<User<'a>> {
     let db_pool = db_pool().await;
 
@@ -76,3 +132,58 @@ pub async fn insert_or_update_user<'a>(user_info: &UserInfo<'_>) -> sqlx::Result
     .fetch_one(db_pool)
     .await
 }
+
+pub async fn update_user_over_quota_since<'a>(user: &User<'_>) -> sqlx::Result<User<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        User,
+        "UPDATE users SET over_quota_since = CASE WHEN $2 THEN COALESCE(over_quota_since, current_timestamp) END
+        WHERE id = $1 RETURNING *",
+        user.id,                    // $1
+        user.is_over_quota().await  // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_track_when_a_user_goes_over_quota() {
+        let user = insert_test_user().await;
+
+        assert!(
+            update_user_over_quota_since(&user)
+                .await
+                .unwrap()
+                .over_quota_since
+                .is_none()
+        );
+
+        let db_pool = db_pool().await;
+
+        sqlx::query!(
+            "UPDATE files SET byte_size = $2 WHERE id = $1",
+            insert_test_file(Some(&user)).await.id,        // $1
+            USERS_CONFIG.free_quota().as_u64() as i64 + 1  // $2
+        )
+        .execute(db_pool)
+        .await
+        .unwrap();
+
+        let user = update_user_over_quota_since(&user).await.unwrap();
+        let over_quota_since = user.over_quota_since;
+
+        assert!(user.is_over_quota().await);
+        assert!(over_quota_since.is_some());
+        assert_eq!(
+            update_user_over_quota_since(&user).await.unwrap().over_quota_since,
+            over_quota_since
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::server::config::POLAR_CONFIG;
+use crate::server::db_pool;
+
+use crate::server::billing::BillingOrder;
+
+use super::{get_plan_by_product_id, get_user_by_id, update_user_over_quota_since, upsert_invoice};
+
+pub struct PolarWebhookHeaders<'a> {
+    pub id: &'a str,
+    pub timestamp: &'a str,
+    pub signature: &'a str,
+}
+
+#[derive(Deserialize)]
+struct PolarWebhookEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    data: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct PolarCustomer {
+    external_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PolarOrder {
+    id: Uuid,
+    status: String,
+    paid: bool,
+    total_amount: u32,
+    currency: String,
+    product_id: Option<Uuid>,
+    subscription_id: Option<Uuid>,
+    customer: PolarCustomer,
+    created_at: DateTime<Utc>,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct PolarSubscription {
+    id: Uuid,
+    status: String,
+    product_id: Uuid,
+    cancel_at_period_end: bool,
+    current_period_end: Option<DateTime<Utc>>,
+    ended_at: Option<DateTime<Utc>>,
+    customer: PolarCustomer,
+    created_at: DateTime<Utc>,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+/// Must be verified with `verify_polar_webhook` first. Redeliveries and outdated events are skipped.
+pub async fn process_polar_webhook(webhook_id: &str, body: &[u8]) -> anyhow::Result<()> {
+    let event = serde_json::from_slice::<PolarWebhookEvent>(body)?;
+
+    let db_pool = db_pool().await;
+    let mut transaction = db_pool.begin().await?;
+
+    let is_new = sqlx::query!(
+        "INSERT INTO polar_webhook_events (id, event_type) VALUES ($1, $2) ON CONFLICT (id) DO NOTHING",
+        webhook_id,       // $1
+        event.event_type  // $2
+    )
+    .execute(&mut *transaction)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if !is_new {
+        return Ok(());
+    }
+
+    let user_id = match event.event_type.as_str() {
+        "subscription.created"
+        | "subscription.updated"
+        | "subscription.active"
+        | "subscription.canceled"
+        | "subscription.uncanceled"
+        | "subscription.revoked" => {
+            sync_polar_subscription(&mut transaction, &serde_json::from_value(event.data)?).await?
+        }
+        // Renewals are paid through orders, but the new period arrives with the `subscription.updated` event.
+        "order.created" | "order.paid" | "order.updated" => {
+            sync_polar_order(&mut transaction, &serde_json::from_value(event.data)?).await?;
+
+            None
+        }
+        "order.refunded" => {
+            let order = serde_json::from_value(event.data)?;
+
+            sync_polar_order(&mut transaction, &order).await?;
+            refund_polar_order(&mut transaction, &order).await?
+        }
+        _ => None,
+    };
+
+    transaction.commit().await?;
+
+    if let Some(user_id) = user_id {
+        update_user_over_quota_since(&get_user_by_id(user_id).await?).await?;
+    }
+
+    Ok(())
+}
+
+pub fn verify_polar_webhook(headers: &PolarWebhookHeaders<'_>, body: &[u8]) -> anyhow::Result<()> {
+    if POLAR_CONFIG.webhook_secret.is_empty() {
+        return Err(anyhow::anyhow!("Webhook secret is not configured"));
+    }
+
+    let timestamp = headers.timestamp.parse::<i64>()?;
+
+    if (Utc::now().timestamp() - timestamp).abs() > POLAR_CONFIG.webhook_tolerance_secs as i64 {
+        return Err(anyhow::anyhow!("Webhook timestamp is too old"));
+    }
+
+    let is_valid = headers
+        .signature
+        .split_whitespace()
+        .filter_map(|signature| signature.strip_prefix("v1,"))
+        .filter_map(|signature| BASE64.decode(signature).ok())
+        .any(|signature| {
+            polar_webhook_mac(headers.id, headers.timestamp, body)
+                .verify_slice(&signature)
+                .is_ok()
+        });
+
+    if !is_valid {
+        return Err(anyhow::anyhow!("Invalid webhook signature"));
+    }
+
+    Ok(())
+}
+
+async fn expire_polar_subscription(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    subscription_id: Uuid,
+    expires_at: DateTime<Utc>,
+    synced_at: DateTime<Utc>,
+) -> sqlx::Result<()> {
+    // A subscription that was replaced by a newer one must not take the plan away.
+    sqlx::query!(
+        "UPDATE users
+        SET polar_subscription_id = NULL, plan_expires_at = LEAST(plan_expires_at, $3), polar_synced_at = $4
+        WHERE id = $1 AND (polar_subscription_id IS NULL OR polar_subscription_id = $2)
+            AND (polar_synced_at IS NULL OR polar_synced_at <= $4)",
+        user_id,         // $1
+        subscription_id, // $2
+        expires_at,      // $3
+        synced_at        // $4
+    )
+    .execute(conn)
+    .await
+    .map(|_| ())
+}
+
+async fn find_polar_customer_user_id(
+    conn: &mut PgConnection,
+    customer: &PolarCustomer,
+    subscription_id: Option<Uuid>,
+) -> sqlx::Result<Option<Uuid>> {
+    let external_id = customer.external_id.as_ref().and_then(|id| id.parse::<Uuid>().ok());
+
+    sqlx::query_scalar!(
+        "SELECT id FROM users WHERE id = $1 OR polar_subscription_id = $2 ORDER BY (id = $1) IS TRUE DESC LIMIT 1",
+        external_id,     // $1
+        subscription_id  // $2
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+fn polar_webhook_mac(webhook_id: &str, timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(POLAR_CONFIG.webhook_secret.as_bytes()).unwrap();
+
+    mac.update(format!("{webhook_id}.{timestamp}.").as_bytes());
+    mac.update(body);
+
+    mac
+}
+
+async fn refund_polar_order(conn: &mut PgConnection, order: &PolarOrder) -> anyhow::Result<Option<Uuid>> {
+    // Partial refunds keep the subscription going.
+    let Some(subscription_id) = order.subscription_id.filter(|_| order.status == "refunded") else {
+        return Ok(None);
+    };
+
+    let Some(user_id) = find_polar_customer_user_id(conn, &order.customer, Some(subscription_id)).await? else {
+        return Ok(None);
+    };
+
+    let synced_at = order.modified_at.unwrap_or(order.created_at);
+
+    expire_polar_subscription(conn, user_id, subscription_id, Utc::now(), synced_at).await?;
+
+    Ok(Some(user_id))
+}
+
+async fn sync_polar_order(conn: &mut PgConnection, order: &PolarOrder) -> anyhow::Result<()> {
+    let Some(user_id) = find_polar_customer_user_id(conn, &order.customer, order.subscription_id).await? else {
+        return Ok(());
+    };
+
+    let billing_order = BillingOrder {
+        id: order.id,
+        product_id: order.product_id,
+        amount_cents: order.total_amount,
+        currency: order.currency.clone(),
+        is_paid: order.paid,
+        created_at: order.created_at,
+    };
+
+    upsert_invoice(conn, user_id, &billing_order).await
+}
+
+async fn sync_polar_subscription(
+    conn: &mut PgConnection,
+    subscription: &PolarSubscription,
+) -> anyhow::Result<Option<Uuid>> {
+    let Some(user_id) = find_polar_customer_user_id(conn, &subscription.customer, Some(subscription.id)).await? else {
+        return Ok(None);
+    };
+
+    let synced_at = subscription.modified_at.unwrap_or(subscription.created_at);
+
+    match subscription.status.as_str() {
+        "active" | "trialing" => {
+            let plan = get_plan_by_product_id(subscription.product_id).await?;
+            // Same as after `cancel_subscription`, the plan is kept until the end of the period but can't be canceled
+            // again.
+            let subscription_id = (!subscription.cancel_at_period_end).then_some(subscription.id);
+
+            sqlx::query!(
+                "UPDATE users SET plan_id = $2, polar_subscription_id = $3, plan_expires_at = $4, polar_synced_at = $5
+                WHERE id = $1 AND (polar_synced_at IS NULL OR polar_synced_at <= $5)",
+                user_id,                         // $1
+                plan.id,                         // $2
+                subscription_id,                 // $3
+                subscription.current_period_end, // $4
+                synced_at                        // $5
+            )
+            .execute(conn)
+            .await?;
+        }
+        "canceled" | "unpaid" | "incomplete_expired" => {
+            let expires_at = subscription
+                .ended_at
+                .or(subscription.current_period_end)
+                .unwrap_or_else(Utc::now);
+
+            expire_polar_subscription(conn, user_id, subscription.id, expires_at, synced_at).await?;
+        }
+        // Past due subscriptions keep the plan until the end of the period while Polar retries the payment.
+        _ => {}
+    }
+
+    Ok(Some(user_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::server::commands::{get_all_invoices, get_user_by_id, insert_plan};
+    use crate::server::models::Entitlements;
+    use crate::test_utils::*;
+
+    use super::*;
+
+    async fn send_test_webhook(webhook_id: &str, body: &serde_json::Value) -> anyhow::Result<()> {
+        let body = body.to_string().into_bytes();
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = sign_polar_webhook(webhook_id, &timestamp, &body);
+        let headers = PolarWebhookHeaders {
+            id: webhook_id,
+            timestamp: &timestamp,
+            signature: &signature,
+        };
+
+        verify_polar_webhook(&headers, &body)?;
+        process_polar_webhook(webhook_id, &body).await
+    }
+
+    fn sign_polar_webhook(webhook_id: &str, timestamp: &str, body: &[u8]) -> String {
+        format!(
+            "v1,{}",
+            BASE64.encode(polar_webhook_mac(webhook_id, timestamp, body).finalize().into_bytes())
+        )
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_polar_webhook_with_an_invalid_signature() {
+        let body = b"{}";
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = sign_polar_webhook("msg_1", &timestamp, b"{\"type\":\"order.paid\"}");
+        let headers = PolarWebhookHeaders {
+            id: "msg_1",
+            timestamp: &timestamp,
+            signature: &signature,
+        };
+
+        assert!(verify_polar_webhook(&headers, body).is_err());
+    }
+
+    #[tokio::test]
+    async fn should_cache_invoices_from_polar_order_webhooks() {
+        let user = insert_test_user().await;
+        let order_id = Uuid::new_v4();
+        let created_at = Utc::now();
+        let order = |status: &str, paid: bool| {
+            json!({
+                "id": order_id,
+                "status": status,
+                "paid": paid,
+                "total_amount": 1000,
+                "currency": "usd",
+                "product_id": null,
+                "subscription_id": null,
+                "customer": { "external_id": user.id.to_string() },
+                "created_at": created_at,
+                "modified_at": null,
+            })
+        };
+
+        send_test_webhook(
+            &Uuid::new_v4().to_string(),
+            &json!({ "type": "order.paid", "data": order("paid", true) }),
+        )
+        .await
+        .unwrap();
+
+        // A late delivery of the pending order doesn't undo the payment.
+        send_test_webhook(
+            &Uuid::new_v4().to_string(),
+            &json!({ "type": "order.created", "data": order("pending", false) }),
+        )
+        .await
+        .unwrap();
+
+        let invoices = get_all_invoices(&user).await.unwrap();
+
+        assert_eq!(invoices.len(), 1);
+        assert_eq!(invoices[0].amount(), "$ 10.00 USD");
+        assert!(invoices[0].is_paid);
+    }
+
+    #[tokio::test]
+    async fn should_sync_the_user_plan_from_polar_subscription_webhooks() {
+        let user = insert_test_user().await;
+        let plan = insert_plan(&fake_name(), "", 10, 100, 1000, "usd", &Entitlements::free(), 0)
+            .await
+            .unwrap();
+        let subscription_id = Uuid::new_v4();
+        let period_end = Utc::now() + chrono::TimeDelta::days(30);
+        let subscription = |status: &str, modified_at: DateTime<Utc>| {
+            json!({
+                "id": subscription_id,
+                "status": status,
+                "product_id": plan.polar_monthly_product_id,
+                "cancel_at_period_end": false,
+                "current_period_end": period_end,
+                "ended_at": if status == "canceled" { Some(modified_at) } else { None },
+                "customer": { "external_id": user.id.to_string() },
+                "created_at": Utc::now() - chrono::TimeDelta::days(1),
+                "modified_at": modified_at,
+            })
+        };
+        let active_id = Uuid::new_v4().to_string();
+        let activated_at = Utc::now() - chrono::TimeDelta::minutes(2);
+
+        send_test_webhook(
+            &active_id,
+            &json!({ "type": "subscription.active", "data": subscription("active", activated_at) }),
+        )
+        .await
+        .unwrap();
+
+        let synced_user = get_user_by_id(user.id).await.unwrap();
+
+        assert_eq!(synced_user.plan_id, Some(plan.id));
+        assert_eq!(synced_user.polar_subscription_id, Some(subscription_id));
+        assert_eq!(
+            synced_user.plan_expires_at.map(|expires_at| expires_at.timestamp()),
+            Some(period_end.timestamp())
+        );
+
+        let revoked_at = activated_at + chrono::TimeDelta::minutes(1);
+
+        send_test_webhook(
+            &Uuid::new_v4().to_string(),
+            &json!({ "type": "subscription.revoked", "data": subscription("canceled", revoked_at) }),
+        )
+        .await
+        .unwrap();
+
+        // A late delivery of the activation is ignored, both under its own ID and a new one.
+        for webhook_id in [active_id, Uuid::new_v4().to_string()] {
+            send_test_webhook(
+                &webhook_id,
+                &json!({ "type": "subscription.updated", "data": subscription("active", activated_at) }),
+            )
+            .await
+            .unwrap();
+        }
+
+        let revoked_user = get_user_by_id(user.id).await.unwrap();
+
+        assert!(revoked_user.polar_subscription_id.is_none());
+        assert!(revoked_user.plan().await.is_none());
+    }
+}
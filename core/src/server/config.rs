@@ -8,11 +8,39 @@ use url::Url;
 
 use sdk::config::extract_config_from_env;
 
+use crate::enums::OverQuotaPolicy;
+
+use super::billing::BillingProviderKind;
+
+pub(crate) static BILLING_CONFIG: LazyLock<BillingConfig> = LazyLock::new(|| extract_config_from_env("BILLING_"));
 pub(crate) static DATABASE_CONFIG: LazyLock<DatabaseConfig> = LazyLock::new(|| extract_config_from_env("DATABASE_"));
 pub(crate) static POLAR_CONFIG: LazyLock<PolarConfig> = LazyLock::new(|| extract_config_from_env("POLAR_"));
 pub(crate) static STORAGE_CONFIG: LazyLock<StorageConfig> = LazyLock::new(|| extract_config_from_env("STORAGE_"));
 pub(crate) static USERS_CONFIG: LazyLock<UsersConfig> = LazyLock::new(|| extract_config_from_env("USERS_"));
 
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BillingConfig {
+    pub provider: BillingProviderKind,
+    pub plan_expiry_check_interval_secs: u32,
+    pub plan_expiry_notice_days: Vec<u16>,
+    pub plan_renewal_grace_days: u16,
+}
+
+impl Default for BillingConfig {
+    fn default() -> Self {
+        Self {
+            provider: if cfg!(test) {
+                BillingProviderKind::Mock
+            } else {
+                BillingProviderKind::Polar
+            },
+            plan_expiry_check_interval_secs: 3600,
+            plan_expiry_notice_days: vec![7, 1],
+            plan_renewal_grace_days: 3,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub(crate) struct DatabaseConfig {
     pub max_connections: u8,
@@ -35,6 +63,8 @@ pub(crate) struct PolarConfig {
     pub base_url: Url,
     pub access_token: String,
     pub success_base_url: Url,
+    pub webhook_secret: String,
+    pub webhook_tolerance_secs: u16,
 }
 
 impl Default for PolarConfig {
@@ -43,6 +73,8 @@ impl Default for PolarConfig {
             base_url: "https://sandbox-api.polar.sh/v1/".parse().unwrap(),
             access_token: String::new(),
             success_base_url: "http://127.0.0.1:8080/".parse().unwrap(),
+            webhook_secret: if cfg!(test) { "polar_whs_test" } else { "" }.to_owned(),
+            webhook_tolerance_secs: 300,
         }
     }
 }
@@ -51,7 +83,7 @@ impl Default for PolarConfig {
 pub(crate) struct StorageConfig {
     pub file_key_duration_secs: u16,
     pub image_filter_type: FilterType,
-    max_size_gib_per_file: u8,
+    pub trash_purge_interval_secs: u32,
     path: String,
 }
 
@@ -60,7 +92,7 @@ impl Default for StorageConfig {
         Self {
             file_key_duration_secs: 60,
             image_filter_type: FilterType::CatmullRom,
-            max_size_gib_per_file: 1,
+            trash_purge_interval_secs: 3600,
             #[cfg(not(test))]
             path: "./storage".to_owned(),
             #[cfg(test)]
@@ -70,10 +102,6 @@ impl Default for StorageConfig {
 }
 
 impl StorageConfig {
-    pub fn max_size_per_file(&self) -> ByteSize {
-        ByteSize::gib(self.max_size_gib_per_file as u64)
-    }
-
     pub fn path(&self) -> PathBuf {
         let storage_path = Path::new(&self.path);
 
@@ -89,6 +117,11 @@ impl StorageConfig {
 pub(crate) struct UsersConfig {
     pub session_token_length: u8,
     pub free_quota_gib: u8,
+    pub free_max_file_size_gib: u8,
+    pub free_trash_retention_days: u16,
+    pub over_quota_check_interval_secs: u32,
+    pub over_quota_grace_days: u16,
+    pub over_quota_policy: OverQuotaPolicy,
 }
 
 impl Default for UsersConfig {
@@ -96,6 +129,11 @@ impl Default for UsersConfig {
         Self {
             session_token_length: 32,
             free_quota_gib: 5,
+            free_max_file_size_gib: 1,
+            free_trash_retention_days: 30,
+            over_quota_check_interval_secs: 3600,
+            over_quota_grace_days: 14,
+            over_quota_policy: OverQuotaPolicy::default(),
         }
     }
 }
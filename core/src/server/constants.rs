@@ -12,9 +12,22 @@ pub const ALLOWED_FILE_FORMATS: [FileFormat; 3] = [
     FileFormat::PortableNetworkGraphics,
 ];
 
+pub static ERROR_IS_OVER_QUOTA: LazyLock<ValidationError> =
+    LazyLock::new(|| ValidationError::new("over-quota").with_message(Cow::Borrowed("Storage is over quota")));
+
 pub static ERROR_IS_TOO_LARGE: LazyLock<ValidationError> =
     LazyLock::new(|| ValidationError::new("too-large").with_message(Cow::Borrowed("Is too large")));
 
+pub const ITEM_PROPERTIES_LIMIT: usize = 50;
+
+pub const ITEM_PROPERTY_KEY_MAX_LENGTH: usize = 64;
+
+pub const ITEM_PROPERTY_VALUE_MAX_LENGTH: usize = 1024;
+
 pub const METADATA_TOTAL_SPACE: &str = "space_quota";
 
+pub const NOTIFICATIONS_LIMIT: u8 = 50;
+
+pub const RECENT_FILES_LIMIT: u8 = 50;
+
 pub static REGEX_FILE_NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"\A[^\/:*?"<>|]+\z"#).unwrap());
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use super::commands::{enforce_over_quota_policy, expire_plans, notify_expiring_plans, purge_expired_trash_items};
+use super::config::{BILLING_CONFIG, STORAGE_CONFIG, USERS_CONFIG};
+
+pub fn spawn_over_quota_job() {
+    tokio::spawn(async {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(USERS_CONFIG.over_quota_check_interval_secs as u64));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = enforce_over_quota_policy().await {
+                tracing::error!("Could not enforce over-quota policy: {err}");
+            }
+        }
+    });
+}
+
+pub fn spawn_plan_expiry_job() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            BILLING_CONFIG.plan_expiry_check_interval_secs as u64,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = notify_expiring_plans().await {
+                tracing::error!("Could not notify about expiring plans: {err}");
+            }
+
+            if let Err(err) = expire_plans().await {
+                tracing::error!("Could not expire plans: {err}");
+            }
+        }
+    });
+}
+
+pub fn spawn_trash_purge_job() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(Duration::from_secs(STORAGE_CONFIG.trash_purge_interval_secs as u64));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = purge_expired_trash_items().await {
+                tracing::error!("Could not purge expired trash items: {err}");
+            }
+        }
+    });
+}
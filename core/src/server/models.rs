@@ -2,21 +2,94 @@ use std::borrow::Cow;
 use std::path::PathBuf;
 
 use bytesize::ByteSize;
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
 use file_format::FileFormat;
 use image::metadata::Orientation;
 use image::{DynamicImage, ImageDecoder, ImageReader};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use url::Url;
 use uuid::Uuid;
 
-use crate::enums::FileVisibility;
+use crate::enums::{FileVisibility, NotificationKind, OverQuotaPolicy};
+use crate::inputs::{FolderItemsFilter, ImageRegion, ItemProperties};
 use crate::server::commands::*;
-use crate::server::config::USERS_CONFIG;
+use crate::server::config::{BILLING_CONFIG, USERS_CONFIG};
 
 use super::config::STORAGE_CONFIG;
 use super::constants::ALLOWED_FILE_FORMATS;
 
+pub struct Album<'a> {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub cover_file_id: Option<Uuid>,
+    pub name: Cow<'a, str>,
+    pub visibility: FileVisibility,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Album<'_> {
+    pub async fn cover_file(&self) -> Option<File<'_>> {
+        get_album_cover_file(self).await.ok()
+    }
+
+    pub async fn files_count(&self) -> i64 {
+        get_album_files_count(self).await.unwrap_or_default()
+    }
+
+    pub fn is_visible_to(&self, user: &User<'_>) -> bool {
+        self.user_id == user.id || self.visibility >= FileVisibility::Users
+    }
+
+    pub async fn user(&self) -> User<'_> {
+        get_user_by_id(self.user_id).await.expect("Could not get user")
+    }
+}
+
+pub struct BatchItemResult {
+    pub id: Uuid,
+    pub is_file: bool,
+    pub error: Option<String>,
+}
+
+impl BatchItemResult {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Version history depth, share-link limits and bandwidth allowance are left out until those features exist.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Entitlements {
+    pub max_file_size_gib: u16,
+    pub allowed_media_types: Vec<String>,
+    pub trash_retention_days: u16,
+}
+
+impl Entitlements {
+    pub(crate) fn free() -> Self {
+        Self {
+            max_file_size_gib: USERS_CONFIG.free_max_file_size_gib as u16,
+            allowed_media_types: Vec::new(),
+            trash_retention_days: USERS_CONFIG.free_trash_retention_days,
+        }
+    }
+
+    pub fn allows_file_format(&self, file_format: &FileFormat) -> bool {
+        ALLOWED_FILE_FORMATS.contains(file_format)
+            && (self.allowed_media_types.is_empty()
+                || self
+                    .allowed_media_types
+                    .iter()
+                    .any(|media_type| media_type == file_format.media_type()))
+    }
+
+    pub fn max_file_size(&self) -> ByteSize {
+        ByteSize::gib(self.max_file_size_gib as u64)
+    }
+}
+
 pub struct File<'a> {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -26,6 +99,8 @@ pub struct File<'a> {
     pub media_type: Cow<'a, str>,
     pub byte_size: i64,
     pub md5_checksum: Cow<'a, str>,
+    pub description: Option<Cow<'a, str>>,
+    pub properties: Json<ItemProperties>,
     pub trashed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
@@ -52,6 +127,14 @@ impl File<'_> {
             .unwrap_or(&FileFormat::ArbitraryBinaryData)
     }
 
+    pub async fn is_starred(&self) -> bool {
+        file_is_starred(self).await
+    }
+
+    pub fn is_visible_to(&self, user: &User<'_>) -> bool {
+        self.user_id == user.id || (self.trashed_at.is_none() && self.visibility >= FileVisibility::Users)
+    }
+
     pub fn name_without_extension(&self) -> &str {
         self.name.split('.').collect::<Vec<&str>>()[0]
     }
@@ -163,6 +246,8 @@ impl<'a> From<&FolderItem<'a>> for File<'a> {
             media_type: Cow::Borrowed(""),
             byte_size: 0,
             md5_checksum: Cow::Borrowed(""),
+            description: None,
+            properties: Json(ItemProperties::new()),
             trashed_at: None,
             created_at: item.created_at,
             updated_at: item.updated_at,
@@ -170,6 +255,39 @@ impl<'a> From<&FolderItem<'a>> for File<'a> {
     }
 }
 
+pub struct FileComment<'a> {
+    pub id: Uuid,
+    pub file_id: Uuid,
+    pub user_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub body: Cow<'a, str>,
+    pub region_x: Option<f64>,
+    pub region_y: Option<f64>,
+    pub region_width: Option<f64>,
+    pub region_height: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl FileComment<'_> {
+    pub async fn file(&self) -> File<'_> {
+        get_file_by_id(self.file_id, None).await.expect("Could not get file")
+    }
+
+    pub fn region(&self) -> Option<ImageRegion> {
+        Some(ImageRegion {
+            x: self.region_x?,
+            y: self.region_y?,
+            width: self.region_width?,
+            height: self.region_height?,
+        })
+    }
+
+    pub async fn user(&self) -> User<'_> {
+        get_user_by_id(self.user_id).await.expect("Could not get user")
+    }
+}
+
 pub struct FileKey {
     pub id: Uuid,
     pub file_id: Uuid,
@@ -189,12 +307,18 @@ pub struct Folder<'a> {
     pub parent_folder_id: Option<Uuid>,
     pub name: Cow<'a, str>,
     pub visibility: FileVisibility,
+    pub description: Option<Cow<'a, str>>,
+    pub properties: Json<ItemProperties>,
     pub trashed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Folder<'_> {
+    pub async fn is_starred(&self) -> bool {
+        folder_is_starred(self).await
+    }
+
     pub async fn is_trashed(&self) -> bool {
         folder_is_trashed(self).await
     }
@@ -217,6 +341,10 @@ impl Folder<'_> {
             .expect("Could not get parent folders")
     }
 
+    pub async fn stats(&self) -> FolderStats {
+        get_folder_stats(self).await.expect("Could not get folder stats")
+    }
+
     pub async fn user(&self) -> User<'_> {
         get_user_by_id(self.user_id).await.expect("Could not get user")
     }
@@ -230,6 +358,8 @@ impl<'a> From<&FolderItem<'a>> for Folder<'a> {
             parent_folder_id: item.parent_folder_id,
             name: item.name.clone(),
             visibility: item.visibility,
+            description: None,
+            properties: Json(ItemProperties::new()),
             trashed_at: None,
             created_at: item.created_at,
             updated_at: item.updated_at,
@@ -237,6 +367,19 @@ impl<'a> From<&FolderItem<'a>> for Folder<'a> {
     }
 }
 
+pub struct FolderStats {
+    pub folder_id: Uuid,
+    pub byte_size: i64,
+    pub files_count: i32,
+    pub folders_count: i32,
+}
+
+impl FolderStats {
+    pub fn byte_size(&self) -> ByteSize {
+        ByteSize(self.byte_size as u64)
+    }
+}
+
 pub struct FolderItem<'a> {
     pub id: Uuid,
     pub is_file: bool,
@@ -249,6 +392,14 @@ pub struct FolderItem<'a> {
 }
 
 impl FolderItem<'_> {
+    pub async fn is_starred(&self) -> bool {
+        if self.is_file {
+            file_is_starred(&self.into()).await
+        } else {
+            folder_is_starred(&self.into()).await
+        }
+    }
+
     pub async fn url(&self) -> Option<Url> {
         if !self.is_file {
             return None;
@@ -264,31 +415,161 @@ impl FolderItem<'_> {
     }
 }
 
+impl<'a> From<&File<'a>> for FolderItem<'a> {
+    fn from(file: &File<'a>) -> Self {
+        Self {
+            id: file.id,
+            is_file: true,
+            user_id: file.user_id,
+            parent_folder_id: file.parent_folder_id,
+            name: file.name.clone(),
+            visibility: file.visibility,
+            created_at: file.created_at,
+            updated_at: file.updated_at,
+        }
+    }
+}
+
+impl<'a> From<&Folder<'a>> for FolderItem<'a> {
+    fn from(folder: &Folder<'a>) -> Self {
+        Self {
+            id: folder.id,
+            is_file: false,
+            user_id: folder.user_id,
+            parent_folder_id: folder.parent_folder_id,
+            name: folder.name.clone(),
+            visibility: folder.visibility,
+            created_at: folder.created_at,
+            updated_at: folder.updated_at,
+        }
+    }
+}
+
+pub struct Invoice {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub plan_id: Option<Uuid>,
+    pub polar_order_id: Uuid,
+    pub amount_cents: i32,
+    pub currency: String,
+    pub is_paid: bool,
+    pub ordered_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Invoice {
+    pub fn amount(&self) -> String {
+        format_price(self.amount_cents, &self.currency)
+    }
+
+    pub async fn plan(&self) -> Option<Plan<'_>> {
+        get_plan_by_id(self.plan_id?).await.ok()
+    }
+}
+
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_user_id: Option<Uuid>,
+    pub kind: NotificationKind,
+    pub file_id: Option<Uuid>,
+    pub file_comment_id: Option<Uuid>,
+    pub plan_id: Option<Uuid>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Notification {
+    pub async fn actor_user(&self) -> Option<User<'_>> {
+        get_user_by_id(self.actor_user_id?).await.ok()
+    }
+
+    pub async fn file(&self) -> Option<File<'_>> {
+        get_file_by_id(self.file_id?, None).await.ok()
+    }
+
+    pub async fn plan(&self) -> Option<Plan<'_>> {
+        get_plan_by_id(self.plan_id?).await.ok()
+    }
+}
+
 #[derive(Serialize)]
 pub struct Plan<'a> {
     pub id: Uuid,
     pub name: Cow<'a, str>,
     pub description: Cow<'a, str>,
-    pub quota_gib: i16,
-    pub monthly_price_cents: i16,
-    pub yearly_price_cents: i16,
+    pub quota_gib: i32,
+    pub monthly_price_cents: i32,
+    pub yearly_price_cents: i32,
     pub polar_monthly_product_id: Uuid,
     pub polar_yearly_product_id: Uuid,
+    pub trash_retention_days: i16,
+    pub currency: Cow<'a, str>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub max_file_size_gib: i16,
+    pub allowed_media_types: Vec<String>,
+    pub trial_days: i16,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Plan<'_> {
+    pub fn entitlements(&self) -> Entitlements {
+        Entitlements {
+            max_file_size_gib: self.max_file_size_gib as u16,
+            allowed_media_types: self.allowed_media_types.clone(),
+            trash_retention_days: self.trash_retention_days as u16,
+        }
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
     pub fn quota(&self) -> ByteSize {
         ByteSize::gib(self.quota_gib as u64)
     }
 
     pub fn monthly_price(&self) -> String {
-        format!("$ {:.2} USD", self.monthly_price_cents as f32 / 100.0)
+        format_price(self.monthly_price_cents, &self.currency)
     }
 
     pub fn yearly_price(&self) -> String {
-        format!("$ {:.2} USD", self.yearly_price_cents as f32 / 100.0)
+        format_price(self.yearly_price_cents, &self.currency)
+    }
+}
+
+#[derive(Serialize)]
+pub struct PromotionCode<'a> {
+    pub id: Uuid,
+    pub code: Cow<'a, str>,
+    pub percent_off: Option<i16>,
+    pub amount_off_cents: Option<i32>,
+    pub currency: Option<Cow<'a, str>>,
+    pub usage_limit: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub polar_discount_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl PromotionCode<'_> {
+    pub fn discount(&self) -> String {
+        match (self.percent_off, self.amount_off_cents, &self.currency) {
+            (Some(percent_off), _, _) => format!("{percent_off}% off"),
+            (_, Some(amount_off_cents), Some(currency)) => format!("{} off", format_price(amount_off_cents, currency)),
+            _ => String::new(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    pub async fn redemptions_count(&self) -> sqlx::Result<i64> {
+        get_promotion_code_redemptions_count(self).await
     }
 }
 
@@ -305,6 +586,209 @@ pub struct Session<'a> {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+pub struct SmartFolder<'a> {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: Cow<'a, str>,
+    pub definition: Json<SmartFolderDefinition>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl SmartFolder<'_> {
+    pub fn filter(&self) -> FolderItemsFilter {
+        self.definition.filter()
+    }
+
+    pub async fn user(&self) -> User<'_> {
+        get_user_by_id(self.user_id).await.expect("Could not get user")
+    }
+}
+
+/// Versioned so that rows saved by older releases can still be read after `FolderItemsFilter` changes.
+/// Unknown versions fail to deserialize instead of falling back to an empty filter.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "version")]
+pub enum SmartFolderDefinition {
+    #[serde(rename = "1")]
+    V1(SmartFolderDefinitionV1),
+    #[serde(rename = "2")]
+    V2(SmartFolderDefinitionV2),
+}
+
+impl SmartFolderDefinition {
+    pub fn filter(&self) -> FolderItemsFilter {
+        let definition = self.clone().upgrade();
+
+        FolderItemsFilter {
+            name_pattern: definition.name_pattern,
+            media_type: definition.media_type,
+            created_after: definition.created_after,
+            created_before: definition.created_before,
+            min_byte_size: definition.min_byte_size,
+            max_byte_size: definition.max_byte_size,
+            visibility: definition.visibility,
+            metadata_pattern: definition.metadata_pattern,
+            tags: definition.tags,
+        }
+    }
+
+    /// Every older version gets an arm here when a new version changes its fields.
+    pub fn upgrade(self) -> SmartFolderDefinitionV2 {
+        match self {
+            SmartFolderDefinition::V1(definition) => SmartFolderDefinitionV2 {
+                name_pattern: definition.name_pattern,
+                media_type: definition.media_type,
+                created_after: definition.created_after,
+                created_before: definition.created_before,
+                min_byte_size: definition.min_byte_size,
+                max_byte_size: definition.max_byte_size,
+                visibility: definition.visibility,
+                metadata_pattern: definition.metadata_pattern,
+                tags: Vec::new(),
+            },
+            SmartFolderDefinition::V2(definition) => definition,
+        }
+    }
+}
+
+impl From<&FolderItemsFilter> for SmartFolderDefinition {
+    fn from(filter: &FolderItemsFilter) -> Self {
+        SmartFolderDefinition::V2(SmartFolderDefinitionV2 {
+            name_pattern: filter.name_pattern.clone(),
+            media_type: filter.media_type.clone(),
+            created_after: filter.created_after,
+            created_before: filter.created_before,
+            min_byte_size: filter.min_byte_size,
+            max_byte_size: filter.max_byte_size,
+            visibility: filter.visibility,
+            metadata_pattern: filter.metadata_pattern.clone(),
+            tags: filter.tags.clone(),
+        })
+    }
+}
+
+/// Saved before tag filters existed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SmartFolderDefinitionV1 {
+    pub name_pattern: Option<String>,
+    pub media_type: Option<String>,
+    pub created_after: Option<NaiveDate>,
+    pub created_before: Option<NaiveDate>,
+    pub min_byte_size: Option<u64>,
+    pub max_byte_size: Option<u64>,
+    pub visibility: Option<FileVisibility>,
+    pub metadata_pattern: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SmartFolderDefinitionV2 {
+    pub name_pattern: Option<String>,
+    pub media_type: Option<String>,
+    pub created_after: Option<NaiveDate>,
+    pub created_before: Option<NaiveDate>,
+    pub min_byte_size: Option<u64>,
+    pub max_byte_size: Option<u64>,
+    pub visibility: Option<FileVisibility>,
+    pub metadata_pattern: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct StorageGrant<'a> {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub byte_size: i64,
+    pub reason: Cow<'a, str>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl StorageGrant<'_> {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > Utc::now())
+    }
+
+    pub fn size(&self) -> ByteSize {
+        ByteSize(self.byte_size as u64)
+    }
+}
+
+pub struct TimelineGroup {
+    pub period: NaiveDate,
+    pub files_count: i64,
+}
+
+pub struct TimelineItem<'a> {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub parent_folder_id: Option<Uuid>,
+    pub name: Cow<'a, str>,
+    pub visibility: FileVisibility,
+    pub captured_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> From<&TimelineItem<'a>> for FolderItem<'a> {
+    fn from(item: &TimelineItem<'a>) -> Self {
+        Self {
+            id: item.id,
+            is_file: true,
+            user_id: item.user_id,
+            parent_folder_id: item.parent_folder_id,
+            name: item.name.clone(),
+            visibility: item.visibility,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
+pub struct TrashItem<'a> {
+    pub id: Uuid,
+    pub is_file: bool,
+    pub user_id: Uuid,
+    pub parent_folder_id: Option<Uuid>,
+    pub name: Cow<'a, str>,
+    pub visibility: FileVisibility,
+    pub trashed_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl TrashItem<'_> {
+    pub async fn can_be_restored_in_place(&self) -> bool {
+        let Some(parent_folder_id) = self.parent_folder_id else {
+            return true;
+        };
+
+        match get_folder_by_id(parent_folder_id, None).await {
+            Ok(parent_folder) => !parent_folder.is_trashed().await,
+            Err(_) => false,
+        }
+    }
+}
+
+impl<'a> From<&TrashItem<'a>> for FolderItem<'a> {
+    fn from(item: &TrashItem<'a>) -> Self {
+        Self {
+            id: item.id,
+            is_file: item.is_file,
+            user_id: item.user_id,
+            parent_folder_id: item.parent_folder_id,
+            name: item.name.clone(),
+            visibility: item.visibility,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct User<'a> {
     pub id: Uuid,
@@ -320,6 +804,8 @@ pub struct User<'a> {
     pub country_alpha2: Cow<'a, str>,
     pub polar_subscription_id: Option<Uuid>,
     pub plan_expires_at: Option<DateTime<Utc>>,
+    pub polar_synced_at: Option<DateTime<Utc>>,
+    pub over_quota_since: Option<DateTime<Utc>>,
     pub disabled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
@@ -327,7 +813,24 @@ pub struct User<'a> {
 
 impl User<'_> {
     pub async fn available_space(&self) -> ByteSize {
-        self.total_space().await - self.used_space().await
+        ByteSize(
+            self.total_space()
+                .await
+                .as_u64()
+                .saturating_sub(self.used_space().await.as_u64()),
+        )
+    }
+
+    pub async fn entitlements(&self) -> Entitlements {
+        if let Some(plan) = self.plan().await {
+            plan.entitlements()
+        } else {
+            Entitlements::free()
+        }
+    }
+
+    pub async fn granted_space(&self) -> sqlx::Result<ByteSize> {
+        get_active_storage_grants_size(self).await
     }
 
     #[allow(dead_code)]
@@ -335,9 +838,34 @@ impl User<'_> {
         self.disabled_at.is_some()
     }
 
+    pub async fn is_over_quota(&self) -> bool {
+        self.used_space().await > self.total_space().await
+    }
+
+    /// Auto-renewing plans are kept for a few days after the period ends, until the renewal webhook arrives.
+    pub fn plan_ends_at(&self) -> Option<DateTime<Utc>> {
+        let grace_days = if self.polar_subscription_id.is_some() {
+            BILLING_CONFIG.plan_renewal_grace_days
+        } else {
+            0
+        };
+
+        self.plan_expires_at
+            .map(|expires_at| expires_at + TimeDelta::days(grace_days as i64))
+    }
+
+    pub fn over_quota_grace_ends_at(&self) -> Option<DateTime<Utc>> {
+        self.over_quota_since
+            .map(|since| since + TimeDelta::days(USERS_CONFIG.over_quota_grace_days as i64))
+    }
+
+    pub fn over_quota_policy(&self) -> OverQuotaPolicy {
+        USERS_CONFIG.over_quota_policy
+    }
+
     pub async fn plan(&self) -> Option<Plan<'_>> {
         if let Some(plan_id) = self.plan_id
-            && self.plan_expires_at.is_some_and(|expires_at| expires_at > Utc::now())
+            && self.plan_ends_at().is_some_and(|ends_at| ends_at > Utc::now())
         {
             Some(get_plan_by_id(plan_id).await.expect("Could not get plan"))
         } else {
@@ -346,18 +874,33 @@ impl User<'_> {
     }
 
     pub async fn plan_is_cancellable(&self) -> bool {
-        self.polar_subscription_id.is_some() && self.used_space().await <= USERS_CONFIG.free_quota()
+        self.polar_subscription_id.is_some()
+            && self.used_space().await
+                <= USERS_CONFIG.free_quota() + self.granted_space().await.expect("Could not get granted space")
     }
 
     pub async fn total_space(&self) -> ByteSize {
-        if let Some(plan) = self.plan().await {
+        let quota = if let Some(plan) = self.plan().await {
             plan.quota()
         } else {
             USERS_CONFIG.free_quota()
-        }
+        };
+
+        quota + self.granted_space().await.expect("Could not get granted space")
     }
 
     pub async fn used_space(&self) -> ByteSize {
         get_used_space_by_user(self).await
     }
 }
+
+fn format_price(cents: i32, currency: &str) -> String {
+    let symbol = match currency {
+        "eur" => "€ ",
+        "gbp" => "£ ",
+        "usd" => "$ ",
+        _ => "",
+    };
+
+    format!("{symbol}{}.{:02} {}", cents / 100, cents % 100, currency.to_uppercase())
+}
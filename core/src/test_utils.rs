@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 pub use sdk::test_utils::{fake_auth, fake_birthdate, fake_country_alpha2, fake_email, fake_name, fake_username};
 
-use crate::enums::FileVisibility;
+use crate::enums::{ConflictPolicy, FileVisibility};
 use crate::inputs::{FileInput, FolderInput};
 use crate::server::commands::{insert_file, insert_folder, insert_or_update_user, insert_session};
 use crate::server::models::{File, Folder, Session, User};
@@ -22,6 +22,7 @@ pub async fn insert_test_file<'a>(user: Option<&User<'_>>) -> File<'a> {
         parent_folder_id: None,
         name: fake_name() + ".jpg",
         content: vec![0xFF, 0xD8, 0xFF],
+        conflict_policy: ConflictPolicy::Fail,
     };
 
     insert_file(user, &input).await.expect("Could not insert folder")
@@ -0,0 +1,3 @@
+DROP TABLE file_accesses;
+
+DROP TABLE stars;
@@ -0,0 +1,35 @@
+CREATE TABLE stars (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    file_id uuid NULL,
+    folder_id uuid NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_stars PRIMARY KEY (id),
+    CONSTRAINT fkey_stars_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_stars_to_files FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_stars_to_folders FOREIGN KEY (folder_id) REFERENCES folders (id) ON DELETE CASCADE,
+    CONSTRAINT check_stars_file_id_or_folder_id CHECK (num_nonnulls(file_id, folder_id) = 1)
+);
+
+CREATE UNIQUE INDEX index_stars_on_user_id_file_id ON stars USING btree (user_id, file_id);
+CREATE UNIQUE INDEX index_stars_on_user_id_folder_id ON stars USING btree (user_id, folder_id);
+
+SELECT manage_updated_at('stars');
+SELECT manage_versions('stars');
+
+CREATE TABLE file_accesses (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    file_id uuid NOT NULL,
+    accessed_at timestamptz NOT NULL DEFAULT current_timestamp,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_file_accesses PRIMARY KEY (id),
+    CONSTRAINT fkey_file_accesses_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_file_accesses_to_files FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_file_accesses_on_user_id_file_id ON file_accesses USING btree (user_id, file_id);
+
+SELECT manage_updated_at('file_accesses');
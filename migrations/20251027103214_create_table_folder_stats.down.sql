@@ -0,0 +1,9 @@
+DROP TRIGGER invalidate_parent_folder_stats ON folders;
+
+DROP TRIGGER invalidate_parent_folder_stats ON files;
+
+DROP FUNCTION invalidate_parent_folder_stats;
+
+DROP FUNCTION invalidate_folder_stats;
+
+DROP TABLE folder_stats;
@@ -0,0 +1,46 @@
+CREATE TABLE folder_stats (
+    folder_id uuid NOT NULL,
+    byte_size bigint NOT NULL,
+    files_count integer NOT NULL,
+    folders_count integer NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_folder_stats PRIMARY KEY (folder_id),
+    CONSTRAINT fkey_folder_stats_to_folders FOREIGN KEY (folder_id) REFERENCES folders (id) ON DELETE CASCADE
+);
+
+SELECT manage_updated_at('folder_stats');
+
+CREATE OR REPLACE FUNCTION invalidate_folder_stats(_folder_id uuid) RETURNS void AS $$
+BEGIN
+    IF (_folder_id IS NULL) THEN
+        RETURN;
+    END IF;
+
+    WITH RECURSIVE parent_folders AS (
+        SELECT id, parent_folder_id FROM folders WHERE id = _folder_id
+        UNION ALL
+        SELECT f.id, f.parent_folder_id FROM folders AS f, parent_folders AS pf WHERE f.id = pf.parent_folder_id
+    ) DELETE FROM folder_stats WHERE folder_id IN (SELECT id FROM parent_folders);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION invalidate_parent_folder_stats() RETURNS trigger AS $$
+BEGIN
+    IF (TG_OP IS DISTINCT FROM 'INSERT') THEN
+        PERFORM invalidate_folder_stats(OLD.parent_folder_id);
+    END IF;
+
+    IF (TG_OP IS DISTINCT FROM 'DELETE') THEN
+        PERFORM invalidate_folder_stats(NEW.parent_folder_id);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER invalidate_parent_folder_stats AFTER INSERT OR DELETE OR UPDATE OF parent_folder_id, byte_size, trashed_at
+    ON files FOR EACH ROW EXECUTE FUNCTION invalidate_parent_folder_stats();
+
+CREATE TRIGGER invalidate_parent_folder_stats AFTER INSERT OR DELETE OR UPDATE OF parent_folder_id, trashed_at
+    ON folders FOR EACH ROW EXECUTE FUNCTION invalidate_parent_folder_stats();
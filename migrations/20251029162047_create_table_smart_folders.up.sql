@@ -0,0 +1,15 @@
+CREATE TABLE smart_folders (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    name citext NOT NULL,
+    definition jsonb NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_smart_folders PRIMARY KEY (id),
+    CONSTRAINT fkey_smart_folders_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_smart_folders_on_user_id_name ON smart_folders USING btree (user_id, name);
+
+SELECT manage_updated_at('smart_folders');
+SELECT manage_versions('smart_folders');
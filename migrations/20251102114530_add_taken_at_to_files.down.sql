@@ -0,0 +1,3 @@
+DROP INDEX index_files_on_user_id_captured_at;
+
+ALTER TABLE files DROP COLUMN taken_at;
@@ -0,0 +1,3 @@
+ALTER TABLE files ADD COLUMN taken_at timestamptz NULL;
+
+CREATE INDEX index_files_on_user_id_captured_at ON files USING btree (user_id, (COALESCE(taken_at, created_at)) DESC);
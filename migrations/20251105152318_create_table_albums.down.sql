@@ -0,0 +1,7 @@
+DROP TRIGGER reset_album_cover ON album_files;
+
+DROP FUNCTION reset_album_cover;
+
+DROP TABLE album_files;
+
+DROP TABLE albums;
@@ -0,0 +1,45 @@
+CREATE TABLE albums (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    cover_file_id uuid NULL,
+    name citext NOT NULL,
+    visibility file_visibility NOT NULL DEFAULT 'private',
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_albums PRIMARY KEY (id),
+    CONSTRAINT fkey_albums_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_albums_to_files FOREIGN KEY (cover_file_id) REFERENCES files (id) ON DELETE SET NULL
+);
+
+CREATE UNIQUE INDEX index_albums_on_user_id_name ON albums USING btree (user_id, name);
+
+SELECT manage_updated_at('albums');
+SELECT manage_versions('albums');
+
+CREATE TABLE album_files (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    album_id uuid NOT NULL,
+    file_id uuid NOT NULL,
+    position integer NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_album_files PRIMARY KEY (id),
+    CONSTRAINT fkey_album_files_to_albums FOREIGN KEY (album_id) REFERENCES albums (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_album_files_to_files FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_album_files_on_album_id_file_id ON album_files USING btree (album_id, file_id);
+CREATE INDEX index_album_files_on_album_id_position ON album_files USING btree (album_id, position);
+CREATE INDEX index_album_files_on_file_id ON album_files USING btree (file_id);
+
+SELECT manage_updated_at('album_files');
+
+CREATE OR REPLACE FUNCTION reset_album_cover() RETURNS trigger AS $$
+BEGIN
+    UPDATE albums SET cover_file_id = NULL WHERE id = OLD.album_id AND cover_file_id = OLD.file_id;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reset_album_cover AFTER DELETE ON album_files FOR EACH ROW EXECUTE FUNCTION reset_album_cover();
@@ -0,0 +1,5 @@
+DROP TABLE notifications;
+
+DROP TYPE notification_kind;
+
+DROP TABLE file_comments;
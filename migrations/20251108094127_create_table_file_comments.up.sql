@@ -0,0 +1,54 @@
+CREATE TABLE file_comments (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    file_id uuid NOT NULL,
+    user_id uuid NOT NULL,
+    parent_comment_id uuid NULL,
+    body text NOT NULL,
+    region_x double precision NULL,
+    region_y double precision NULL,
+    region_width double precision NULL,
+    region_height double precision NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_file_comments PRIMARY KEY (id),
+    CONSTRAINT fkey_file_comments_to_files FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_file_comments_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_file_comments_to_file_comments FOREIGN KEY (parent_comment_id)
+        REFERENCES file_comments (id) ON DELETE CASCADE,
+    CONSTRAINT check_file_comments_region CHECK (
+        num_nulls(region_x, region_y, region_width, region_height) IN (0, 4)
+        AND region_x BETWEEN 0 AND 1
+        AND region_y BETWEEN 0 AND 1
+        AND region_x + region_width BETWEEN 0 AND 1
+        AND region_y + region_height BETWEEN 0 AND 1
+    )
+);
+
+CREATE INDEX index_file_comments_on_file_id_created_at ON file_comments USING btree (file_id, created_at);
+
+SELECT manage_updated_at('file_comments');
+SELECT manage_versions('file_comments');
+
+CREATE TYPE notification_kind AS ENUM ('file_comment');
+
+CREATE TABLE notifications (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    actor_user_id uuid NULL,
+    kind notification_kind NOT NULL,
+    file_id uuid NULL,
+    file_comment_id uuid NULL,
+    read_at timestamptz NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_notifications PRIMARY KEY (id),
+    CONSTRAINT fkey_notifications_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_notifications_to_actor_users FOREIGN KEY (actor_user_id) REFERENCES users (id) ON DELETE SET NULL,
+    CONSTRAINT fkey_notifications_to_files FOREIGN KEY (file_id) REFERENCES files (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_notifications_to_file_comments FOREIGN KEY (file_comment_id)
+        REFERENCES file_comments (id) ON DELETE CASCADE
+);
+
+CREATE INDEX index_notifications_on_user_id_created_at ON notifications USING btree (user_id, created_at DESC);
+
+SELECT manage_updated_at('notifications');
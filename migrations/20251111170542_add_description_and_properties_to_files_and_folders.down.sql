@@ -0,0 +1,9 @@
+DROP INDEX index_folders_on_properties;
+
+ALTER TABLE folders DROP COLUMN properties;
+ALTER TABLE folders DROP COLUMN description;
+
+DROP INDEX index_files_on_properties;
+
+ALTER TABLE files DROP COLUMN properties;
+ALTER TABLE files DROP COLUMN description;
@@ -0,0 +1,9 @@
+ALTER TABLE files ADD COLUMN description text NULL;
+ALTER TABLE files ADD COLUMN properties jsonb NOT NULL DEFAULT '{}';
+
+CREATE INDEX index_files_on_properties ON files USING gin (properties);
+
+ALTER TABLE folders ADD COLUMN description text NULL;
+ALTER TABLE folders ADD COLUMN properties jsonb NOT NULL DEFAULT '{}';
+
+CREATE INDEX index_folders_on_properties ON folders USING gin (properties);
@@ -0,0 +1,4 @@
+DROP INDEX index_folders_on_trashed_at;
+DROP INDEX index_files_on_trashed_at;
+
+ALTER TABLE plans DROP COLUMN trash_retention_days;
@@ -0,0 +1,4 @@
+ALTER TABLE plans ADD COLUMN trash_retention_days smallint NOT NULL DEFAULT 90;
+
+CREATE INDEX index_files_on_trashed_at ON files (trashed_at) WHERE trashed_at IS NOT NULL;
+CREATE INDEX index_folders_on_trashed_at ON folders (trashed_at) WHERE trashed_at IS NOT NULL;
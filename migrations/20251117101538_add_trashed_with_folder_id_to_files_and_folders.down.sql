@@ -0,0 +1,13 @@
+UPDATE folders SET trashed_at = NULL WHERE trashed_with_folder_id IS NOT NULL;
+
+DROP INDEX index_folders_on_trashed_with_folder_id;
+
+ALTER TABLE folders DROP CONSTRAINT fkey_folders_to_trashed_with_folders;
+ALTER TABLE folders DROP COLUMN trashed_with_folder_id;
+
+UPDATE files SET trashed_at = NULL WHERE trashed_with_folder_id IS NOT NULL;
+
+DROP INDEX index_files_on_trashed_with_folder_id;
+
+ALTER TABLE files DROP CONSTRAINT fkey_files_to_trashed_with_folders;
+ALTER TABLE files DROP COLUMN trashed_with_folder_id;
@@ -0,0 +1,30 @@
+ALTER TABLE files ADD COLUMN trashed_with_folder_id uuid NULL;
+ALTER TABLE files ADD CONSTRAINT fkey_files_to_trashed_with_folders
+    FOREIGN KEY (trashed_with_folder_id) REFERENCES folders (id) ON DELETE SET NULL;
+
+CREATE INDEX index_files_on_trashed_with_folder_id ON files (trashed_with_folder_id)
+    WHERE trashed_with_folder_id IS NOT NULL;
+
+ALTER TABLE folders ADD COLUMN trashed_with_folder_id uuid NULL;
+ALTER TABLE folders ADD CONSTRAINT fkey_folders_to_trashed_with_folders
+    FOREIGN KEY (trashed_with_folder_id) REFERENCES folders (id) ON DELETE SET NULL;
+
+CREATE INDEX index_folders_on_trashed_with_folder_id ON folders (trashed_with_folder_id)
+    WHERE trashed_with_folder_id IS NOT NULL;
+
+-- Items inside folders that are already in the trash are trashed along with them.
+WITH RECURSIVE trashed_folders AS (
+    SELECT id AS root_folder_id, id, trashed_at FROM folders WHERE trashed_at IS NOT NULL
+    UNION ALL
+    SELECT tf.root_folder_id, f.id, tf.trashed_at FROM folders AS f, trashed_folders AS tf
+    WHERE f.parent_folder_id = tf.id AND f.trashed_at IS NULL
+) UPDATE files AS f SET trashed_at = tf.trashed_at, trashed_with_folder_id = tf.root_folder_id
+FROM trashed_folders AS tf WHERE f.parent_folder_id = tf.id AND f.trashed_at IS NULL;
+
+WITH RECURSIVE trashed_folders AS (
+    SELECT id AS root_folder_id, id, trashed_at FROM folders WHERE trashed_at IS NOT NULL
+    UNION ALL
+    SELECT tf.root_folder_id, f.id, tf.trashed_at FROM folders AS f, trashed_folders AS tf
+    WHERE f.parent_folder_id = tf.id AND f.trashed_at IS NULL
+) UPDATE folders AS f SET trashed_at = tf.trashed_at, trashed_with_folder_id = tf.root_folder_id
+FROM trashed_folders AS tf WHERE f.id = tf.id AND tf.id <> tf.root_folder_id;
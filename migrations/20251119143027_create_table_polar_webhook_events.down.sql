@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN polar_synced_at;
+
+DROP TABLE polar_webhook_events;
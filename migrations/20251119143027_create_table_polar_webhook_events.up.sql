@@ -0,0 +1,8 @@
+CREATE TABLE polar_webhook_events (
+    id text NOT NULL,
+    event_type varchar NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    CONSTRAINT pkey_polar_webhook_events PRIMARY KEY (id)
+);
+
+ALTER TABLE users ADD COLUMN polar_synced_at timestamptz NULL;
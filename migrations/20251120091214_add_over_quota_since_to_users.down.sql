@@ -0,0 +1,3 @@
+DROP INDEX index_users_on_over_quota_since;
+
+ALTER TABLE users DROP COLUMN over_quota_since;
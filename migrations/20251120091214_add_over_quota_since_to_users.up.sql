@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN over_quota_since timestamptz NULL;
+
+CREATE INDEX index_users_on_over_quota_since ON users (over_quota_since) WHERE over_quota_since IS NOT NULL;
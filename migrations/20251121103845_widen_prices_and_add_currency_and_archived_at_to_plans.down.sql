@@ -0,0 +1,6 @@
+ALTER TABLE plans
+DROP COLUMN archived_at,
+DROP COLUMN currency,
+ALTER COLUMN yearly_price_cents TYPE smallint,
+ALTER COLUMN monthly_price_cents TYPE smallint,
+ALTER COLUMN quota_gib TYPE smallint;
@@ -0,0 +1,6 @@
+ALTER TABLE plans
+ALTER COLUMN quota_gib TYPE integer,
+ALTER COLUMN monthly_price_cents TYPE integer,
+ALTER COLUMN yearly_price_cents TYPE integer,
+ADD COLUMN currency varchar(3) NOT NULL DEFAULT 'usd',
+ADD COLUMN archived_at timestamptz NULL;
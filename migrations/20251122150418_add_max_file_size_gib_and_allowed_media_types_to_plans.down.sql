@@ -0,0 +1,3 @@
+ALTER TABLE plans
+DROP COLUMN allowed_media_types,
+DROP COLUMN max_file_size_gib;
@@ -0,0 +1,3 @@
+ALTER TABLE plans
+ADD COLUMN max_file_size_gib smallint NOT NULL DEFAULT 1,
+ADD COLUMN allowed_media_types varchar[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,18 @@
+CREATE TABLE storage_grants (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    byte_size bigint NOT NULL,
+    reason varchar NOT NULL DEFAULT '',
+    expires_at timestamptz NULL,
+    revoked_at timestamptz NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_storage_grants PRIMARY KEY (id),
+    CONSTRAINT fkey_storage_grants_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT check_storage_grants_byte_size CHECK (byte_size > 0)
+);
+
+CREATE INDEX index_storage_grants_on_user_id ON storage_grants USING btree (user_id);
+
+SELECT manage_updated_at('storage_grants');
+SELECT manage_versions('storage_grants');
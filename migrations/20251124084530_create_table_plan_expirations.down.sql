@@ -0,0 +1,15 @@
+DROP INDEX index_users_on_plan_expires_at;
+
+DROP TABLE plan_expirations;
+
+DELETE FROM notifications WHERE kind IN ('plan_expiring', 'plan_expired');
+
+ALTER TABLE notifications DROP COLUMN plan_id;
+
+ALTER TYPE notification_kind RENAME TO notification_kind_old;
+
+CREATE TYPE notification_kind AS ENUM ('file_comment');
+
+ALTER TABLE notifications ALTER COLUMN kind TYPE notification_kind USING kind::text::notification_kind;
+
+DROP TYPE notification_kind_old;
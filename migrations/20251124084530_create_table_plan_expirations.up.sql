@@ -0,0 +1,23 @@
+ALTER TYPE notification_kind ADD VALUE 'plan_expiring';
+ALTER TYPE notification_kind ADD VALUE 'plan_expired';
+
+ALTER TABLE notifications ADD COLUMN plan_id uuid NULL,
+ADD CONSTRAINT fkey_notifications_to_plans FOREIGN KEY (plan_id) REFERENCES plans (id) ON DELETE CASCADE;
+
+CREATE TABLE plan_expirations (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    plan_id uuid NOT NULL,
+    plan_expired_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_plan_expirations PRIMARY KEY (id),
+    CONSTRAINT fkey_plan_expirations_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_plan_expirations_to_plans FOREIGN KEY (plan_id) REFERENCES plans (id) ON DELETE CASCADE
+);
+
+CREATE INDEX index_plan_expirations_on_user_id ON plan_expirations USING btree (user_id);
+CREATE INDEX index_users_on_plan_expires_at ON users (plan_expires_at) WHERE plan_id IS NOT NULL;
+
+SELECT manage_updated_at('plan_expirations');
+SELECT manage_versions('plan_expirations');
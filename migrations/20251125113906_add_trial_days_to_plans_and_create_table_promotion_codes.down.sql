@@ -0,0 +1,5 @@
+DROP TABLE promotion_code_redemptions;
+
+DROP TABLE promotion_codes;
+
+ALTER TABLE plans DROP COLUMN trial_days;
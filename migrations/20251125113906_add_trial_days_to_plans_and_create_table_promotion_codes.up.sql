@@ -0,0 +1,45 @@
+ALTER TABLE plans ADD COLUMN trial_days smallint NOT NULL DEFAULT 0;
+
+CREATE TABLE promotion_codes (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    code varchar(32) NOT NULL,
+    percent_off smallint NULL,
+    amount_off_cents integer NULL,
+    currency varchar(3) NULL,
+    usage_limit integer NULL,
+    expires_at timestamptz NULL,
+    polar_discount_id uuid NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_promotion_codes PRIMARY KEY (id),
+    CONSTRAINT check_promotion_codes_percent_off_or_amount_off_cents CHECK (
+        num_nonnulls(percent_off, amount_off_cents) = 1
+    ),
+    CONSTRAINT check_promotion_codes_currency CHECK ((amount_off_cents IS NULL) = (currency IS NULL))
+);
+
+CREATE UNIQUE INDEX index_promotion_codes_on_code ON promotion_codes USING btree (code);
+CREATE UNIQUE INDEX index_promotion_codes_on_polar_discount_id ON promotion_codes USING btree (polar_discount_id);
+
+SELECT manage_updated_at('promotion_codes');
+SELECT manage_versions('promotion_codes');
+
+CREATE TABLE promotion_code_redemptions (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    promotion_code_id uuid NOT NULL,
+    user_id uuid NOT NULL,
+    checkout_id uuid NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_promotion_code_redemptions PRIMARY KEY (id),
+    CONSTRAINT fkey_promotion_code_redemptions_to_promotion_codes FOREIGN KEY (promotion_code_id)
+        REFERENCES promotion_codes (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_promotion_code_redemptions_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_promotion_code_redemptions_on_checkout_id ON promotion_code_redemptions
+    USING btree (checkout_id);
+CREATE INDEX index_promotion_code_redemptions_on_promotion_code_id ON promotion_code_redemptions
+    USING btree (promotion_code_id);
+
+SELECT manage_updated_at('promotion_code_redemptions');
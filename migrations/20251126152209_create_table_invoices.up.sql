@@ -0,0 +1,21 @@
+CREATE TABLE invoices (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    plan_id uuid NULL,
+    polar_order_id uuid NOT NULL,
+    amount_cents integer NOT NULL,
+    currency varchar(3) NOT NULL,
+    is_paid boolean NOT NULL DEFAULT false,
+    ordered_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_invoices PRIMARY KEY (id),
+    CONSTRAINT fkey_invoices_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_invoices_to_plans FOREIGN KEY (plan_id) REFERENCES plans (id) ON DELETE SET NULL
+);
+
+CREATE UNIQUE INDEX index_invoices_on_polar_order_id ON invoices USING btree (polar_order_id);
+CREATE INDEX index_invoices_on_user_id_and_ordered_at ON invoices USING btree (user_id, ordered_at);
+
+SELECT manage_updated_at('invoices');
+SELECT manage_versions('invoices');
@@ -0,0 +1,5 @@
+-- Trashed files don't hold their name, so a live item can take it.
+DROP INDEX index_files_on_user_id_parent_folder_id_name;
+
+CREATE UNIQUE INDEX index_files_on_user_id_parent_folder_id_name ON files USING btree (user_id, parent_folder_id, name)
+    WHERE trashed_at IS NULL;